@@ -19,23 +19,296 @@
 //!
 //! `generate_csd_multipliers()` finds repeated substrings across **different** coefficients and
 //! creates a shared common sub-expression (CSE) wire, reducing total hardware across the filter.
+//! Coefficients that equal another one up to sign and a power of two (duplicate taps of a
+//! linear-phase filter, `-c`, `2c`, ...) are not built at all: they reuse the other product
+//! through a negation and/or shift.
+//!
+//! # Adder Structure
+//!
+//! Both generators lower coefficients to a [`netlist::Netlist`] before printing Verilog.
+//! By default every sum is a left-to-right chain (depth NNZ − 1); with
+//! [`AdderStructure::Tree`] the same adders are arranged as a balanced tree of
+//! intermediate wires (depth ⌈log₂ NNZ⌉). [`adder_cost_tradeoff()`] reports both.
+//! [`report`] summarizes a whole design (adders, depth, savings over flat CSD,
+//! wire widths and an area estimate) as text or JSON.
+//!
+//! # Fractional Coefficients
+//!
+//! CSD strings may contain a binary point, as produced by
+//! [`to_csd()`](crate::csd::to_csd) (e.g. `"+00-.0+"` = 7.25). `max_power` is then
+//! the power of the leftmost integer digit, and the digits after the point are
+//! right shifts relative to the output's binary point: the result port carries
+//! $F$ fractional bits for $F$ fractional digits, so the product is exact.
+//! [`MultiplierOptions::output_frac_bits()`] truncates or rounds it to a coarser
+//! output format.
+//!
+//! # Backends
+//!
+//! The same netlist can be printed as Verilog ([`emit_verilog()`]), VHDL
+//! ([`vhdl::emit_vhdl()`]), parameterized SystemVerilog
+//! ([`systemverilog::emit_systemverilog()`]), Chisel ([`chisel::emit_chisel()`]),
+//! Amaranth ([`amaranth::emit_amaranth()`]) or HLS C++ ([`hls::emit_hls()`]), so
+//! all describe identical hardware.
+//! [`testbench`] writes a matching self-checking Verilog testbench, and
+//! [`simulator`] checks the netlist bit-accurately without an HDL simulator.
+//! [`graph`] draws the adder network as a DOT or Mermaid diagram for reviews,
+//! and [`model`] prints it as bit-accurate C and Rust functions for firmware.
+//!
+//! # Coefficient Banks
+//!
+//! [`bank`] builds one multiplier whose coefficient is chosen at run time by a
+//! `sel` input, sharing adders across the selectable coefficients.
+//!
+//! # Complex Multipliers
+//!
+//! [`complex`] multiplies a complex input by a constant such as an FFT twiddle
+//! factor, with four shared real products or Gauss's three-multiplier trick.
+//!
+//! # Truncated Multipliers
+//!
+//! [`truncated`] drops LSB columns of the partial products when only the top
+//! bits of the product are needed, with an analytic worst-case error bound.
+//!
+//! # Matrix-Vector Products
+//!
+//! [`cmvm`] multiplies an input vector `x0..xk` by a constant CSD matrix in one
+//! module with an output per row, sharing sub-expressions across inputs and rows.
+//!
+//! # FIR Filters
+//!
+//! [`fir`] quantizes real tap values to CSD and wraps the multipliers in a
+//! clocked direct- or transposed-form filter, sharing hardware between
+//! symmetric taps of linear-phase filters.
 
 use std::collections::{BTreeSet, HashMap, HashSet};
-use std::fmt::Write;
+use std::fmt::{self, Write};
 
+use crate::csd::{to_csd_i128, CsdError};
 use crate::lcsre::longest_repeated_substring;
 
+pub mod amaranth;
+pub mod bank;
+pub mod chisel;
+pub mod cmvm;
+pub mod complex;
+pub mod fir;
+pub mod graph;
+pub mod hls;
+pub mod model;
+pub mod netlist;
+pub mod report;
+pub mod simulator;
+pub mod systemverilog;
+pub mod testbench;
+pub mod truncated;
+pub mod vhdl;
+
+use netlist::{Netlist, Output, Port, Source, Term, Wire, WireExpr};
+
 /// Error type for CSD multiplier operations.
 #[derive(Debug, Clone, PartialEq)]
 pub enum CsdMultiplierError {
-    /// Invalid character found in CSD string (only '+', '-', '0' allowed)
-    InvalidCharacter,
-    /// Length of CSD string doesn't match expected length (max_power + 1)
-    LengthMismatch,
+    /// Invalid character found in CSD string (only '+', '-', '0' and one '.' allowed)
+    InvalidCharacter {
+        /// The offending character
+        character: char,
+        /// Its character position in the CSD string
+        position: usize,
+    },
+    /// Number of integer digits doesn't match the expected `max_power + 1`
+    LengthMismatch {
+        /// `max_power + 1`
+        expected: usize,
+        /// Integer digits found in the CSD string
+        actual: usize,
+    },
     /// At least one coefficient is required
     EmptyCoefficients,
-    /// All coefficients must share the same input_width and max_power
-    WidthMismatch,
+    /// All coefficients must share the same input_width
+    WidthMismatch {
+        /// Index of the mismatching [`MultiplierSpec`]
+        index: usize,
+        /// Output name of the mismatching [`MultiplierSpec`]
+        name: String,
+        /// Input width of the first coefficient
+        expected: usize,
+        /// Input width of the mismatching coefficient
+        actual: usize,
+    },
+    /// Coefficient is not finite or too large to quantize
+    CoefficientOutOfRange,
+    /// Input width must be at least one bit
+    ZeroInputWidth,
+    /// Two adjacent non-zero digits in a string that must be canonical
+    NotCanonical {
+        /// Character position of the second non-zero digit
+        position: usize,
+    },
+    /// Two coefficients share an output port name
+    DuplicateOutput {
+        /// The repeated output name
+        name: String,
+        /// Index of the first [`MultiplierSpec`] using it
+        first: usize,
+        /// Index of the second [`MultiplierSpec`] using it
+        second: usize,
+    },
+    /// A [`MultiplierSpec`] of a multi-coefficient request is invalid
+    InvalidSpec {
+        /// Index of the offending [`MultiplierSpec`]
+        index: usize,
+        /// Its output name
+        name: String,
+        /// What is wrong with it
+        error: Box<CsdMultiplierError>,
+    },
+    /// A coefficient matrix row has a different number of columns than the first row
+    RaggedMatrix {
+        /// Index of the offending row
+        row: usize,
+        /// Columns of the first row
+        expected: usize,
+        /// Columns of the offending row
+        actual: usize,
+    },
+    /// A product is wider than the generator supports (125 bits for the
+    /// generators that compute exact products in `i128`)
+    ProductTooWide {
+        /// Bits the product (or sum of products) may need
+        width: usize,
+        /// Widest supported product
+        max: usize,
+    },
+    /// A truncated multiplier was asked to drop more bits than its product has
+    TooManyDropBits {
+        /// Requested number of dropped LSBs
+        drop_bits: usize,
+        /// Bit width of the exact product
+        product_width: usize,
+    },
+    /// No truncation of a truncated multiplier stays within the requested error bound
+    ErrorBoundUnreachable {
+        /// Requested worst-case error, in output LSBs
+        requested: f64,
+        /// Smallest worst-case error any truncation achieves
+        achievable: f64,
+    },
+    /// Error from a CSD conversion
+    Csd(CsdError),
+}
+
+impl CsdMultiplierError {
+    /// Attach the index and output name of the [`MultiplierSpec`] that caused `self`.
+    fn in_spec(self, index: usize, name: &str) -> Self {
+        CsdMultiplierError::InvalidSpec {
+            index,
+            name: name.to_string(),
+            error: Box::new(self),
+        }
+    }
+}
+
+impl fmt::Display for CsdMultiplierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsdMultiplierError::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "Invalid character '{}' at position {} in CSD string",
+                character, position
+            ),
+            CsdMultiplierError::LengthMismatch { expected, actual } => write!(
+                f,
+                "CSD string has {} integer digits, expected {} (max_power + 1)",
+                actual, expected
+            ),
+            CsdMultiplierError::EmptyCoefficients => {
+                write!(f, "At least one coefficient is required")
+            }
+            CsdMultiplierError::WidthMismatch {
+                index,
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Coefficient {} ({}) has input width {}, expected {}",
+                index, name, actual, expected
+            ),
+            CsdMultiplierError::CoefficientOutOfRange => {
+                write!(f, "Coefficient is not finite or too large to quantize")
+            }
+            CsdMultiplierError::ZeroInputWidth => {
+                write!(f, "Input width must be at least one bit")
+            }
+            CsdMultiplierError::NotCanonical { position } => write!(
+                f,
+                "Adjacent non-zero digits at position {} in CSD string",
+                position
+            ),
+            CsdMultiplierError::DuplicateOutput {
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "Output name '{}' is used by coefficients {} and {}",
+                name, first, second
+            ),
+            CsdMultiplierError::InvalidSpec { index, name, error } => {
+                write!(f, "Coefficient {} ({}): {}", index, name, error)
+            }
+            CsdMultiplierError::RaggedMatrix {
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Matrix row {} has {} columns, expected {}",
+                row, actual, expected
+            ),
+            CsdMultiplierError::ProductTooWide { width, max } => write!(
+                f,
+                "Product needs up to {} bits, more than the {} bits this generator supports",
+                width, max
+            ),
+            CsdMultiplierError::TooManyDropBits {
+                drop_bits,
+                product_width,
+            } => write!(
+                f,
+                "Cannot drop {} bits from a {}-bit product",
+                drop_bits, product_width
+            ),
+            CsdMultiplierError::ErrorBoundUnreachable {
+                requested,
+                achievable,
+            } => write!(
+                f,
+                "No truncation meets the error bound of {} output LSBs (best achievable: {})",
+                requested, achievable
+            ),
+            CsdMultiplierError::Csd(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CsdMultiplierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsdMultiplierError::InvalidSpec { error, .. } => Some(error.as_ref()),
+            CsdMultiplierError::Csd(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<CsdError> for CsdMultiplierError {
+    fn from(error: CsdError) -> Self {
+        CsdMultiplierError::Csd(error)
+    }
 }
 
 /// A CSD-based constant multiplier that generates Verilog code
@@ -56,6 +329,7 @@ pub struct CsdMultiplier {
     csd: String,
     n: usize,
     m: usize,
+    naming: Naming,
 }
 
 /// Specification for a single CSD multiplier coefficient
@@ -64,7 +338,8 @@ pub struct CsdMultiplier {
 /// cross-common-subexpression elimination.
 #[derive(Debug, Clone)]
 pub struct MultiplierSpec {
-    /// Output port name (e.g. "y0", "y1")
+    /// Output port name (e.g. "y0", "y1"); names starting with `_` are
+    /// reserved for internal wires
     pub name: String,
     /// CSD string ('+', '-', '0')
     pub csd: String,
@@ -74,16 +349,456 @@ pub struct MultiplierSpec {
     pub max_power: usize,
 }
 
-// ---------------------------------------------------------------------------
-// Internal helpers
-// ---------------------------------------------------------------------------
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum TermOp {
+/// Sign of a term in a shift-add sum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TermOp {
+    /// Add the term
     Add,
+    /// Subtract the term
     Sub,
 }
 
+impl TermOp {
+    /// The opposite operation.
+    pub fn negate(self) -> Self {
+        match self {
+            TermOp::Add => TermOp::Sub,
+            TermOp::Sub => TermOp::Add,
+        }
+    }
+}
+
+/// How a product is reduced to fewer fractional bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputRounding {
+    /// Drop the extra fractional bits (round toward −∞)
+    #[default]
+    Truncate,
+    /// Round to the nearest value, ties toward +∞
+    Nearest,
+}
+
+/// How the partial products of a coefficient are summed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdderStructure {
+    /// One `assign` evaluated left to right (adder depth NNZ − 1)
+    #[default]
+    Chain,
+    /// Binary tree of intermediate `_tN` wires (adder depth ⌈log₂ NNZ⌉)
+    Tree,
+}
+
+/// How coefficient digit strings are checked before hardware is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Validation {
+    /// Canonical signed digits only: no two adjacent non-zero digits
+    Strict,
+    /// Any string of '+', '-' and '0' digits (e.g. `"++"` = 3)
+    #[default]
+    SignedDigit,
+    /// Accept any signed-digit string and rewrite it to its CSD form first
+    Canonicalize,
+}
+
+/// How shifted copies of the input are sign-extended in Verilog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignExtension {
+    /// `x <<< p`: the signed shift is extended to the wire width implicitly
+    #[default]
+    Arithmetic,
+    /// `$signed({ {k{x[n-1]}}, x}) << p`: explicit sign-bit replication
+    Concatenation,
+}
+
+/// Amount of commentary in generated Verilog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommentStyle {
+    /// No comments at all
+    None,
+    /// Port descriptions and one comment per section
+    #[default]
+    Normal,
+    /// As `Normal`, plus a header line with every coefficient and its value
+    Verbose,
+}
+
+/// Textual style of generated Verilog; it never changes the hardware.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{
+///     generate_csd_multiplier_with, CommentStyle, MultiplierOptions, SignExtension, VerilogStyle,
+/// };
+///
+/// let style = VerilogStyle::new()
+///     .sign_extension(SignExtension::Concatenation)
+///     .comments(CommentStyle::Verbose);
+/// let options = MultiplierOptions::new().verilog_style(style);
+/// let v = generate_csd_multiplier_with("+0-", 8, 2, &options).unwrap();
+/// assert!(v.starts_with("// CSD Multiplier for pattern: +0- (value: 3)\nmodule csd_multiplier ("));
+/// assert!(v.contains("wire signed [9:0] x_shift2 = $signed({ {2{x[7]}}, x}) << 2;"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerilogStyle {
+    sign_extension: SignExtension,
+    comments: CommentStyle,
+}
+
+impl VerilogStyle {
+    /// Create the default style: `<<<` shifts and normal comments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how shifted inputs are sign-extended.
+    ///
+    /// # Arguments
+    ///
+    /// * `sign_extension` - [`SignExtension::Arithmetic`] or [`SignExtension::Concatenation`]
+    pub fn sign_extension(mut self, sign_extension: SignExtension) -> Self {
+        self.sign_extension = sign_extension;
+        self
+    }
+
+    /// Set the amount of commentary.
+    ///
+    /// # Arguments
+    ///
+    /// * `comments` - [`CommentStyle::None`], [`CommentStyle::Normal`] or [`CommentStyle::Verbose`]
+    pub fn comments(mut self, comments: CommentStyle) -> Self {
+        self.comments = comments;
+        self
+    }
+}
+
+/// Names of the generated module, ports and internal wires.
+///
+/// Unset names keep each generator's default (module `csd_multiplier`, input
+/// `x`, output `result`). A wire prefix is prepended to every internal wire,
+/// and the coefficient suffix appends the coefficient value to the module name
+/// (`csd_multiplier_229`, `csd_multiplier_m0p375` for −0.375), so several
+/// generated multipliers can live in one design without name collisions.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{generate_csd_multiplier_with, MultiplierOptions, Naming};
+///
+/// let naming = Naming::new()
+///     .input_name("a")
+///     .output_name("p")
+///     .wire_prefix("u0")
+///     .coefficient_suffix(true);
+/// let options = MultiplierOptions::new().naming(naming);
+/// let v = generate_csd_multiplier_with("+0-", 8, 2, &options).unwrap();
+/// assert!(v.contains("module csd_multiplier_3 ("));
+/// assert!(v.contains("wire signed [9:0] u0_a_shift2 = a <<< 2;"));
+/// assert!(v.contains("assign p = u0_a_shift2 - u0_a_shift0;"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Naming {
+    module_name: Option<String>,
+    input_name: Option<String>,
+    output_name: Option<String>,
+    wire_prefix: String,
+    coefficient_suffix: bool,
+}
+
+impl Naming {
+    /// Create a naming configuration that keeps every default name.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the module name.
+    ///
+    /// Replaces `csd_multiplier` for single multipliers and the `module_name`
+    /// argument of the multi-coefficient generators.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - HDL module (entity) name
+    pub fn module_name(mut self, name: &str) -> Self {
+        self.module_name = Some(name.to_string());
+        self
+    }
+
+    /// Set the name of the input port (default `x`).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Input port name; shift wires become `{name}_shiftN`
+    pub fn input_name(mut self, name: &str) -> Self {
+        self.input_name = Some(name.to_string());
+        self
+    }
+
+    /// Set the name of the output port of a single multiplier (default `result`).
+    ///
+    /// Multi-coefficient outputs keep their [`MultiplierSpec::name`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Output port name
+    pub fn output_name(mut self, name: &str) -> Self {
+        self.output_name = Some(name.to_string());
+        self
+    }
+
+    /// Prefix every internal wire name with `{prefix}_`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Wire prefix, e.g. `u0` gives `u0_x_shift3` and `u0_pat`
+    pub fn wire_prefix(mut self, prefix: &str) -> Self {
+        self.wire_prefix = prefix.to_string();
+        self
+    }
+
+    /// Append the coefficient value to the module name.
+    ///
+    /// Negative signs become `m` and binary points `p`; a multi-coefficient
+    /// module appends every coefficient.
+    ///
+    /// # Arguments
+    ///
+    /// * `enable` - Whether to append the suffix
+    pub fn coefficient_suffix(mut self, enable: bool) -> Self {
+        self.coefficient_suffix = enable;
+        self
+    }
+
+    /// Module name for coefficients `csds`, given the generator's default.
+    fn module(&self, default: &str, csds: &[&str]) -> String {
+        let mut name = self.module_name.as_deref().unwrap_or(default).to_string();
+        if self.coefficient_suffix {
+            for csd in csds {
+                write!(name, "_{}", coefficient_tag(csd)).unwrap();
+            }
+        }
+        name
+    }
+
+    /// Input port name.
+    fn input(&self) -> &str {
+        self.input_name.as_deref().unwrap_or("x")
+    }
+
+    /// Output port name, given the generator's default.
+    fn output<'a>(&'a self, default: &'a str) -> &'a str {
+        self.output_name.as_deref().unwrap_or(default)
+    }
+
+    /// Name of internal wire `name`, with the prefix applied.
+    fn wire(&self, name: &str) -> String {
+        if self.wire_prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}_{}", self.wire_prefix, name.trim_start_matches('_'))
+        }
+    }
+
+    /// Name of the wire holding the input shifted left by `power`.
+    fn shift_wire(&self, power: usize) -> String {
+        self.wire(&format!("{}_shift{}", self.input(), power))
+    }
+}
+
+/// Identifier-safe coefficient value, e.g. `229`, `m13` or `7p25`.
+fn coefficient_tag(csd_str: &str) -> String {
+    let (_, frac_bits) = split_binary_point(csd_str);
+    let text = match checked_csd_value(csd_str) {
+        Some(value) if frac_bits == 0 => value.to_string(),
+        _ => csd_value_f64(csd_str).to_string(),
+    };
+    text.replace('-', "m").replace('.', "p")
+}
+
+/// Options for the multiplier generators.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{generate_csd_multiplier_with, AdderStructure, MultiplierOptions};
+///
+/// let options = MultiplierOptions::new().adder_structure(AdderStructure::Tree);
+/// let v = generate_csd_multiplier_with("+00-00+0+", 8, 8, &options).unwrap();
+/// assert!(v.contains("wire signed [15:0] _t0 = x_shift8 - x_shift5;"));
+/// assert!(v.contains("assign result = _t0 + _t1;"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiplierOptions {
+    adder_structure: AdderStructure,
+    share_patterns: bool,
+    output_frac_bits: Option<usize>,
+    rounding: OutputRounding,
+    legacy_widths: bool,
+    naming: Naming,
+    verilog_style: VerilogStyle,
+    validation: Validation,
+}
+
+impl Default for MultiplierOptions {
+    fn default() -> Self {
+        Self {
+            adder_structure: AdderStructure::Chain,
+            share_patterns: true,
+            output_frac_bits: None,
+            rounding: OutputRounding::Truncate,
+            legacy_widths: false,
+            naming: Naming::default(),
+            verilog_style: VerilogStyle::default(),
+            validation: Validation::default(),
+        }
+    }
+}
+
+impl MultiplierOptions {
+    /// Create options matching [`generate_csd_multiplier()`]: adder chain, LCSRe/CSE sharing on.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how partial products are summed.
+    ///
+    /// # Arguments
+    ///
+    /// * `structure` - [`AdderStructure::Chain`] (fewest wires) or [`AdderStructure::Tree`] (minimum depth)
+    pub fn adder_structure(mut self, structure: AdderStructure) -> Self {
+        self.adder_structure = structure;
+        self
+    }
+
+    /// Enable or disable LCSRe / cross-CSE sub-expression sharing and the
+    /// folding of duplicate, negated and power-of-two-scaled coefficients.
+    ///
+    /// # Arguments
+    ///
+    /// * `share` - Whether repeated patterns are computed once in a shared wire
+    pub fn share_patterns(mut self, share: bool) -> Self {
+        self.share_patterns = share;
+        self
+    }
+
+    /// Requantize every output to `frac_bits` fractional bits.
+    ///
+    /// By default an output keeps all fractional bits of the coefficient, so
+    /// the product is exact. Fewer bits drop LSBs as set by [`Self::rounding()`];
+    /// more bits append zero LSBs.
+    ///
+    /// # Arguments
+    ///
+    /// * `frac_bits` - Fractional bits of the output ports
+    pub fn output_frac_bits(mut self, frac_bits: usize) -> Self {
+        self.output_frac_bits = Some(frac_bits);
+        self
+    }
+
+    /// Set how dropped fractional bits are handled.
+    ///
+    /// # Arguments
+    ///
+    /// * `rounding` - [`OutputRounding::Truncate`] (free) or [`OutputRounding::Nearest`] (one extra adder)
+    pub fn rounding(mut self, rounding: OutputRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Keep the legacy $W_{\text{in}} + m$ width for every wire and output.
+    ///
+    /// By default each signal is sized from its exact value range (see
+    /// [`Netlist::minimize_widths()`]), e.g. `x * 3` gets $W_{\text{in}} + 2$
+    /// bits rather than $W_{\text{in}} + m$. Legacy widths over-allocate for
+    /// most coefficients and are too narrow when $|c| > 2^m$.
+    ///
+    /// # Arguments
+    ///
+    /// * `legacy` - Whether to use the uniform legacy width
+    pub fn legacy_widths(mut self, legacy: bool) -> Self {
+        self.legacy_widths = legacy;
+        self
+    }
+
+    /// Set the module, port and wire names.
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - Names to use instead of the generator defaults
+    pub fn naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Set the textual style of the Verilog generators.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - Sign-extension and comment style
+    pub fn verilog_style(mut self, style: VerilogStyle) -> Self {
+        self.verilog_style = style;
+        self
+    }
+
+    /// Set how coefficient strings are validated.
+    ///
+    /// # Arguments
+    ///
+    /// * `validation` - [`Validation::Strict`], [`Validation::SignedDigit`] (default)
+    ///   or [`Validation::Canonicalize`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::{generate_csd_multiplier_with, MultiplierOptions, Validation};
+    ///
+    /// let strict = MultiplierOptions::new().validation(Validation::Strict);
+    /// assert!(generate_csd_multiplier_with("++", 8, 1, &strict).is_err());
+    /// let options = MultiplierOptions::new().validation(Validation::Canonicalize);
+    /// let v = generate_csd_multiplier_with("++", 8, 1, &options).unwrap();
+    /// assert!(v.contains("assign result = x_shift2 - x_shift0;"));
+    /// ```
+    pub fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+}
+
+/// Adder count and logic depth of one way of building a multiplier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdderCost {
+    /// How the partial products are summed
+    pub adder_structure: AdderStructure,
+    /// Whether LCSRe sharing was enabled
+    pub share_patterns: bool,
+    /// Number of two-input adders/subtractors
+    pub adders: usize,
+    /// Adder depth from input to output
+    pub depth: usize,
+}
+
+/// A coefficient quantized to a padded CSD string.
+///
+/// Produced by [`quantize_coefficient()`]; the `csd` and `max_power` fields
+/// can be passed straight to [`generate_csd_multiplier()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedCoefficient {
+    /// CSD string with a binary point when `frac_bits > 0` (e.g. "+0-.0+")
+    pub csd: String,
+    /// Highest power of two (number of integer digits minus 1)
+    pub max_power: usize,
+    /// Number of fractional digits
+    pub frac_bits: usize,
+    /// Value actually implemented by `csd`
+    pub value: f64,
+    /// Quantization error, `value` minus the requested coefficient
+    pub error: f64,
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
 /// Parse a CSD string into (power, operation) pairs.
 fn parse_terms(
     csd_str: &str,
@@ -97,269 +812,1381 @@ fn parse_terms(
             b'+' => terms.push((power, TermOp::Add)),
             b'-' => terms.push((power, TermOp::Sub)),
             b'0' => {}
-            _ => return Err(CsdMultiplierError::InvalidCharacter),
+            _ => {
+                return Err(CsdMultiplierError::InvalidCharacter {
+                    character: c as char,
+                    position: i,
+                })
+            }
         }
     }
     Ok(terms)
 }
 
-/// Build a flat Verilog expression for a range [start, start+length) of the CSD string.
-fn build_range_expr(csd_str: &str, start: usize, length: usize, max_power: usize) -> String {
-    let mut expr = String::new();
-    let mut first = true;
-    let bytes = csd_str.as_bytes();
-    let end = start.saturating_add(length).min(bytes.len());
-    for (i, &c) in bytes.iter().enumerate().skip(start).take(end - start) {
-        let power = max_power - i;
+/// Split a CSD string at its binary point.
+///
+/// Returns the digits without the point and the number of fractional digits.
+fn split_binary_point(csd_str: &str) -> (String, usize) {
+    match csd_str.find('.') {
+        Some(point) => (csd_str.replacen('.', "", 1), csd_str.len() - point - 1),
+        None => (csd_str.to_string(), 0),
+    }
+}
+
+/// "1 fractional bit", "3 fractional bits", ...
+fn frac_bits_text(frac_bits: usize) -> String {
+    match frac_bits {
+        1 => "1 fractional bit".to_string(),
+        f => format!("{} fractional bits", f),
+    }
+}
+
+/// Check that `csd_str` has `max_power + 1` integer digits, all of them '+', '-'
+/// or '0', optionally followed by a binary point and fractional digits.
+fn check_csd(csd_str: &str, max_power: usize) -> Result<(), CsdMultiplierError> {
+    let point = csd_str.chars().position(|c| c == '.');
+    if let Some((position, character)) = csd_str
+        .chars()
+        .enumerate()
+        .find(|&(i, c)| !matches!(c, '+' | '-' | '0') && Some(i) != point)
+    {
+        return Err(CsdMultiplierError::InvalidCharacter {
+            character,
+            position,
+        });
+    }
+    let integer_digits = point.unwrap_or(csd_str.len());
+    if integer_digits != max_power + 1 {
+        return Err(CsdMultiplierError::LengthMismatch {
+            expected: max_power + 1,
+            actual: integer_digits,
+        });
+    }
+    Ok(())
+}
+
+/// Validate a coefficient per `validation`.
+///
+/// Returns the string and `max_power` to lower: unchanged, or the canonical
+/// form for [`Validation::Canonicalize`].
+fn check_coefficient(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    validation: Validation,
+) -> Result<(String, usize), CsdMultiplierError> {
+    check_csd(csd_str, max_power)?;
+    if input_width == 0 {
+        return Err(CsdMultiplierError::ZeroInputWidth);
+    }
+    let width = input_width.saturating_add(csd_str.len());
+    if width > MAX_WIDTH {
+        return Err(CsdMultiplierError::ProductTooWide {
+            width,
+            max: MAX_WIDTH,
+        });
+    }
+    match validation {
+        Validation::Strict => {
+            check_canonical(csd_str)?;
+            Ok((csd_str.to_string(), max_power))
+        }
+        Validation::SignedDigit => Ok((csd_str.to_string(), max_power)),
+        Validation::Canonicalize => canonicalize(csd_str, max_power),
+    }
+}
+
+/// Check that no two non-zero digits are adjacent (across the binary point too).
+fn check_canonical(csd_str: &str) -> Result<(), CsdMultiplierError> {
+    let mut previous_nonzero = false;
+    for (position, c) in csd_str.chars().enumerate() {
         match c {
-            b'+' => {
-                if first {
-                    write!(expr, "x_shift{}", power).unwrap();
-                    first = false;
-                } else {
-                    write!(expr, " + x_shift{}", power).unwrap();
-                }
-            }
-            b'-' => {
-                if first {
-                    write!(expr, "-x_shift{}", power).unwrap();
-                    first = false;
-                } else {
-                    write!(expr, " - x_shift{}", power).unwrap();
-                }
+            '+' | '-' if previous_nonzero => {
+                return Err(CsdMultiplierError::NotCanonical { position })
             }
+            '+' | '-' => previous_nonzero = true,
+            '0' => previous_nonzero = false,
             _ => {}
         }
     }
-    expr
+    Ok(())
+}
+
+/// Rewrite a signed-digit string to the CSD string of the same value.
+///
+/// The fractional digit count is kept; integer digits are added in front
+/// if the canonical form needs more (`"++"` → `"+0-"`), so `max_power` may grow.
+/// Returns `CsdMultiplierError::CoefficientOutOfRange` for strings whose value
+/// does not fit an `i128`.
+fn canonicalize(csd_str: &str, max_power: usize) -> Result<(String, usize), CsdMultiplierError> {
+    let (digits, frac_bits) = split_binary_point(csd_str);
+    let value = checked_csd_value(&digits).ok_or(CsdMultiplierError::CoefficientOutOfRange)?;
+    let canonical = to_csd_i128(value);
+    let integer_digits = canonical.len().saturating_sub(frac_bits).max(max_power + 1);
+    let canonical = format!("{:0>width$}", canonical, width = integer_digits + frac_bits);
+    let csd = if frac_bits == 0 {
+        canonical
+    } else {
+        format!(
+            "{}.{}",
+            &canonical[..integer_digits],
+            &canonical[integer_digits..]
+        )
+    };
+    Ok((csd, integer_digits - 1))
 }
 
-/// Compute output width from input_width and max_power.
+/// Compute the legacy output width from input_width and max_power.
 ///
 /// $$ W_{\text{out}} = W_{\text{in}} + m $$
 ///
 /// where $W_{\text{in}}$ is the input bit width and $m$ is the maximum power of two.
+/// Netlists start at this width and are then tightened unless
+/// [`MultiplierOptions::legacy_widths()`] is set.
 fn output_width(input_width: usize, max_power: usize) -> usize {
     input_width + max_power
 }
 
-// ---------------------------------------------------------------------------
-// CsdMultiplier (struct-based, backward compatible)
-// ---------------------------------------------------------------------------
+/// Widest product any generator emits, the smallest vector width Verilog
+/// tools must support.
+const MAX_WIDTH: usize = 1 << 16;
 
-impl CsdMultiplier {
-    /// Create a new CSD multiplier.
-    ///
-    /// # Arguments
-    ///
-    /// * `csd` - The CSD pattern string (e.g., "+0-")
-    /// * `n` - Input bit width
-    /// * `m` - Highest power index (length of CSD minus 1)
-    ///
-    /// # Errors
-    ///
-    /// Returns `CsdMultiplierError::InvalidCharacter` if the CSD string contains
-    /// characters other than '+', '-', or '0'.
-    ///
-    /// Returns `CsdMultiplierError::LengthMismatch` if the CSD string length
-    /// doesn't equal `m + 1`.
-    pub fn new(csd: &str, n: usize, m: usize) -> Result<Self, CsdMultiplierError> {
-        let bytes = csd.as_bytes();
-        if !bytes.iter().all(|&c| matches!(c, b'+' | b'-' | b'0')) {
-            return Err(CsdMultiplierError::InvalidCharacter);
+/// Widest product the generators that evaluate products in `i128` accept.
+const MAX_EXACT_WIDTH: usize = 125;
+
+/// Check that a product of up to `width` bits can be evaluated in `i128`.
+fn check_product_width(width: usize) -> Result<(), CsdMultiplierError> {
+    if width > MAX_EXACT_WIDTH {
+        return Err(CsdMultiplierError::ProductTooWide {
+            width,
+            max: MAX_EXACT_WIDTH,
+        });
+    }
+    Ok(())
+}
+
+/// Check that the rounding constant $2^{s-1}$ for dropping $s$ of a product's
+/// `frac_bits` fractional bits fits the netlist's `i128` constants.
+fn check_rounding(frac_bits: usize, options: &MultiplierOptions) -> Result<(), CsdMultiplierError> {
+    let drop = options
+        .output_frac_bits
+        .map_or(0, |bits| frac_bits.saturating_sub(bits));
+    if options.rounding == OutputRounding::Nearest && drop > 0 {
+        // The constant is a drop-bit value plus its sign bit
+        check_product_width(drop + 1)?;
+    }
+    Ok(())
+}
+
+/// Range of `multiple · x` for a signed `input_width`-bit `x`.
+///
+/// The product must fit an `i128` (see [`check_product_width()`]).
+fn product_range(multiple: i128, input_width: usize) -> (i128, i128) {
+    let lo = -(1i128 << (input_width - 1));
+    let hi = (1i128 << (input_width - 1)) - 1;
+    let (a, b) = (multiple * lo, multiple * hi);
+    (a.min(b), a.max(b))
+}
+
+/// Integer value of a validated CSD string, ignoring any binary point.
+///
+/// $$ v = \sum_{i=0}^{m} d_i \cdot 2^{m-i}, \quad d_i \in \{-1,0,+1\} $$
+///
+/// For a string with $F$ fractional digits this is the coefficient scaled by $2^F$.
+/// The string must have fewer than 127 digits (see [`checked_csd_value()`]).
+fn csd_value(csd_str: &str) -> i128 {
+    csd_str.bytes().filter(|&c| c != b'.').fold(0, |acc, c| {
+        let acc = acc << 1;
+        match c {
+            b'+' => acc + 1,
+            b'-' => acc - 1,
+            _ => acc,
         }
-        if csd.len() != m + 1 {
-            return Err(CsdMultiplierError::LengthMismatch);
+    })
+}
+
+/// [`csd_value()`], or `None` when the value does not fit an `i128`.
+fn checked_csd_value(csd_str: &str) -> Option<i128> {
+    csd_str
+        .bytes()
+        .filter(|&c| c != b'.')
+        .try_fold(0i128, |acc, c| {
+            let acc = acc.checked_mul(2)?;
+            match c {
+                b'+' => acc.checked_add(1),
+                b'-' => acc.checked_sub(1),
+                _ => Some(acc),
+            }
+        })
+}
+
+/// Coefficient value of a CSD string as `f64`, for strings of any length.
+fn csd_value_f64(csd_str: &str) -> f64 {
+    let (digits, frac_bits) = split_binary_point(csd_str);
+    let value = digits.bytes().fold(0f64, |acc, c| {
+        let acc = acc * 2.0;
+        match c {
+            b'+' => acc + 1.0,
+            b'-' => acc - 1.0,
+            _ => acc,
+        }
+    });
+    value / 2f64.powi(frac_bits as i32)
+}
+
+/// Find the longest repeated pattern worth sharing (≥2 non-zero digits, ≥2 occurrences).
+///
+/// Returns the pattern and its non-overlapping positions.
+fn lcsre_pattern(csd_str: &str) -> Option<(String, Vec<usize>)> {
+    let repeated = longest_repeated_substring(csd_str);
+    if repeated.len() <= 1 || count_nnz(&repeated) < 2 {
+        return None;
+    }
+    let positions = find_pattern_occurrences(csd_str, &repeated);
+    if positions.len() >= 2 {
+        Some((repeated, positions))
+    } else {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Coefficient quantization
+// ---------------------------------------------------------------------------
+
+/// Quantize a coefficient to `frac_bits` fractional bits and convert it to CSD.
+///
+/// $$ C = \operatorname{round}(c \cdot 2^F) \cdot 2^{-F} $$
+///
+/// The CSD string is padded with leading zeros so it has at least one integer
+/// digit and exactly `frac_bits` fractional digits, and `max_power` is sized
+/// to match, so no manual padding or length bookkeeping is needed.
+///
+/// # Arguments
+///
+/// * `value` - Coefficient to implement
+/// * `frac_bits` - Fractional word length of the coefficient
+///
+/// # Errors
+///
+/// Returns `CsdMultiplierError::CoefficientOutOfRange` if `value` is not
+/// finite or $|c \cdot 2^F| \ge 2^{63}$.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::quantize_coefficient;
+///
+/// let q = quantize_coefficient(0.3, 4).unwrap();
+/// assert_eq!(q.csd, "0.0+0+");
+/// assert_eq!(q.max_power, 0);
+/// assert_eq!(q.value, 0.3125);
+/// assert!((q.error - 0.0125).abs() < 1e-12);
+/// ```
+pub fn quantize_coefficient(
+    value: f64,
+    frac_bits: usize,
+) -> Result<QuantizedCoefficient, CsdMultiplierError> {
+    let scale = i32::try_from(frac_bits).map_or(f64::INFINITY, |bits| 2f64.powi(bits));
+    let scaled = (value * scale).round();
+    if !scaled.is_finite() || scaled.abs() >= 2f64.powi(63) {
+        return Err(CsdMultiplierError::CoefficientOutOfRange);
+    }
+    let digits = to_csd_i128(i128::from(scaled as i64));
+    let digits = format!("{:0>width$}", digits, width = frac_bits + 1);
+    let point = digits.len() - frac_bits;
+    let csd = if frac_bits == 0 {
+        digits
+    } else {
+        format!("{}.{}", &digits[..point], &digits[point..])
+    };
+    Ok(QuantizedCoefficient {
+        csd,
+        max_power: point - 1,
+        frac_bits,
+        value: scaled / scale,
+        error: scaled / scale - value,
+    })
+}
+
+impl MultiplierSpec {
+    /// Specification for an integer coefficient, sized to its CSD length.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Output port name
+    /// * `value` - Integer coefficient
+    /// * `input_width` - Bit width of input x
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::MultiplierSpec;
+    ///
+    /// let spec = MultiplierSpec::from_integer("y0", 229, 8);
+    /// assert_eq!(spec.csd, "+00-00+0+");
+    /// assert_eq!(spec.max_power, 8);
+    /// ```
+    pub fn from_integer(name: &str, value: i64, input_width: usize) -> Self {
+        let csd = to_csd_i128(i128::from(value));
+        Self {
+            name: name.to_string(),
+            max_power: csd.len() - 1,
+            csd,
+            input_width,
         }
+    }
+
+    /// Specification for a real coefficient quantized to `frac_bits` fractional bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Output port name
+    /// * `value` - Coefficient to implement
+    /// * `frac_bits` - Fractional word length of the coefficient
+    /// * `input_width` - Bit width of input x
+    ///
+    /// # Errors
+    ///
+    /// Same as [`quantize_coefficient()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::MultiplierSpec;
+    ///
+    /// let spec = MultiplierSpec::from_f64("y0", -0.4, 6, 12).unwrap();
+    /// assert_eq!(spec.csd, "0.-0+0-0");
+    /// assert_eq!(spec.value(), -0.40625);
+    /// ```
+    pub fn from_f64(
+        name: &str,
+        value: f64,
+        frac_bits: usize,
+        input_width: usize,
+    ) -> Result<Self, CsdMultiplierError> {
+        let q = quantize_coefficient(value, frac_bits)?;
         Ok(Self {
-            csd: csd.to_string(),
+            name: name.to_string(),
+            csd: q.csd,
+            input_width,
+            max_power: q.max_power,
+        })
+    }
+
+    /// Coefficient value realized by the CSD string, including fractional digits.
+    pub fn value(&self) -> f64 {
+        csd_value_f64(&self.csd)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CsdMultiplier (struct-based, backward compatible)
+// ---------------------------------------------------------------------------
+
+impl CsdMultiplier {
+    /// Create a new CSD multiplier.
+    ///
+    /// # Arguments
+    ///
+    /// * `csd` - The CSD pattern string (e.g., "+0-" or "+00-.0+")
+    /// * `n` - Input bit width
+    /// * `m` - Highest power index (number of integer digits minus 1)
+    ///
+    /// # Errors
+    ///
+    /// Returns `CsdMultiplierError::InvalidCharacter` if the CSD string contains
+    /// characters other than '+', '-', '0' and a single binary point '.'.
+    ///
+    /// Returns `CsdMultiplierError::LengthMismatch` if the number of integer
+    /// digits doesn't equal `m + 1`.
+    ///
+    /// Returns `CsdMultiplierError::ZeroInputWidth` if `n` is 0.
+    pub fn new(csd: &str, n: usize, m: usize) -> Result<Self, CsdMultiplierError> {
+        Self::new_with_validation(csd, n, m, Validation::SignedDigit)
+    }
+
+    /// Create a new multiplier, validating `csd` per `validation`.
+    ///
+    /// With [`Validation::Canonicalize`] the stored string (and `m`, if the
+    /// canonical form needs another digit) is the CSD form of `csd`.
+    ///
+    /// # Arguments
+    ///
+    /// * `csd` - Signed-digit pattern string
+    /// * `n` - Input bit width
+    /// * `m` - Highest power index (number of integer digits minus 1)
+    /// * `validation` - How to treat non-canonical strings
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::new()`], plus `CsdMultiplierError::NotCanonical` if two
+    /// non-zero digits are adjacent and `validation` is [`Validation::Strict`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::{CsdMultiplier, Validation};
+    ///
+    /// let multiplier = CsdMultiplier::new_with_validation("++0", 8, 2, Validation::Canonicalize).unwrap();
+    /// assert_eq!(multiplier.csd(), "+0-0");
+    /// assert_eq!(multiplier.max_power(), 3);
+    /// ```
+    pub fn new_with_validation(
+        csd: &str,
+        n: usize,
+        m: usize,
+        validation: Validation,
+    ) -> Result<Self, CsdMultiplierError> {
+        let (csd, m) = check_coefficient(csd, n, m, validation)?;
+        Ok(Self {
+            csd,
             n,
             m,
+            naming: Naming::default(),
         })
     }
 
-    /// Calculate the decimal value represented by the CSD string.
+    /// Create a multiplier for an integer coefficient, sized automatically.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Integer coefficient
+    /// * `n` - Input bit width
+    ///
+    /// # Errors
+    ///
+    /// Returns `CsdMultiplierError::ZeroInputWidth` if `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::CsdMultiplier;
+    ///
+    /// let multiplier = CsdMultiplier::from_integer(57, 8).unwrap();
+    /// assert_eq!(multiplier.csd(), "+00-00+");
+    /// assert_eq!(multiplier.value(), 57.0);
+    /// ```
+    pub fn from_integer(value: i64, n: usize) -> Result<Self, CsdMultiplierError> {
+        let csd = to_csd_i128(i128::from(value));
+        Self::new(&csd, n, csd.len() - 1)
+    }
+
+    /// Create a multiplier for a real coefficient quantized to `frac_bits`
+    /// fractional bits (see [`quantize_coefficient()`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Coefficient to implement
+    /// * `frac_bits` - Fractional word length of the coefficient
+    /// * `n` - Input bit width
+    ///
+    /// # Errors
+    ///
+    /// Same as [`quantize_coefficient()`], plus `CsdMultiplierError::ZeroInputWidth`
+    /// if `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::CsdMultiplier;
+    ///
+    /// let multiplier = CsdMultiplier::from_f64(28.4, 2, 8).unwrap();
+    /// assert_eq!(multiplier.csd(), "+00-00.+0");
+    /// assert_eq!(multiplier.value(), 28.5);
+    /// ```
+    pub fn from_f64(value: f64, frac_bits: usize, n: usize) -> Result<Self, CsdMultiplierError> {
+        let q = quantize_coefficient(value, frac_bits)?;
+        Self::new(&q.csd, n, q.max_power)
+    }
+
+    /// Use `naming` for the module, ports and wires of [`Self::generate_verilog()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - Names to use instead of `csd_multiplier`, `x`, `result`, ...
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::{CsdMultiplier, Naming};
+    ///
+    /// let naming = Naming::new().module_name("times").coefficient_suffix(true);
+    /// let multiplier = CsdMultiplier::from_integer(-13, 8).unwrap().with_naming(naming);
+    /// assert!(multiplier.generate_verilog().contains("module times_m13 ("));
+    /// ```
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// The CSD string being implemented.
+    pub fn csd(&self) -> &str {
+        &self.csd
+    }
+
+    /// Highest power index (number of integer digits minus 1).
+    pub fn max_power(&self) -> usize {
+        self.m
+    }
+
+    /// Calculate the decimal value represented by the CSD digits, ignoring any
+    /// binary point (i.e. the coefficient scaled by $2^F$).
     ///
     /// $$ v = \sum_{i=0}^{m} d_i \cdot 2^{m-i}, \quad d_i \in \{-1,0,+1\} $$
     ///
-    fn decimal_value(&self) -> i32 {
-        self.csd.as_bytes().iter().fold(0, |acc, &c| {
-            let acc = acc << 1;
-            match c {
-                b'+' => acc + 1,
-                b'-' => acc - 1,
-                b'0' => acc,
-                _ => unreachable!(),
-            }
-        })
+    #[cfg(test)]
+    fn decimal_value(&self) -> i128 {
+        csd_value(&self.csd)
+    }
+
+    /// Coefficient value realized by the CSD string, including fractional digits.
+    pub fn value(&self) -> f64 {
+        csd_value_f64(&self.csd)
     }
 
     /// Generate the Verilog module code (with LCSRe optimization).
+    ///
+    /// Identical to [`generate_csd_multiplier()`] for the same coefficient.
     pub fn generate_verilog(&self) -> String {
-        let mut output = String::new();
-        self.generate_header(&mut output);
-        self.generate_wires(&mut output);
-        self.generate_result_lcsre(&mut output);
-        writeln!(output, "endmodule").unwrap();
-        output
+        // The default options don't round, so there is nothing to check
+        self.print(&MultiplierOptions::new())
     }
 
-    fn generate_header(&self, output: &mut String) {
-        writeln!(
-            output,
-            "// CSD Multiplier for pattern: {} (value: {})",
-            self.csd,
-            self.decimal_value()
+    /// Generate the Verilog module with explicit [`MultiplierOptions`].
+    ///
+    /// Identical to [`generate_csd_multiplier_with()`] for the same coefficient
+    /// and options; names set with [`Self::with_naming()`] take precedence
+    /// over those in `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CsdMultiplierError::ProductTooWide` if rounding to
+    /// [`MultiplierOptions::output_frac_bits()`] needs a rounding constant
+    /// wider than 125 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::{
+    ///     CommentStyle, CsdMultiplier, MultiplierOptions, SignExtension, VerilogStyle,
+    /// };
+    ///
+    /// // The classic struct output: explicit sign extension and a value comment
+    /// let style = VerilogStyle::new()
+    ///     .sign_extension(SignExtension::Concatenation)
+    ///     .comments(CommentStyle::Verbose);
+    /// let options = MultiplierOptions::new().verilog_style(style);
+    /// let v = CsdMultiplier::new("+0-", 8, 2).unwrap().generate_verilog_with(&options);
+    /// assert!(v.unwrap().contains("(value: 3)"));
+    /// ```
+    pub fn generate_verilog_with(
+        &self,
+        options: &MultiplierOptions,
+    ) -> Result<String, CsdMultiplierError> {
+        check_rounding(split_binary_point(&self.csd).1, options)?;
+        Ok(self.print(options))
+    }
+
+    /// The shift-add [`Netlist`] behind [`Self::generate_verilog()`], for the
+    /// other backends and the [`model`] generators.
+    pub fn netlist(&self) -> Netlist {
+        self.lower(&MultiplierOptions::new())
+    }
+
+    /// The shift-add [`Netlist`] behind [`Self::generate_verilog_with()`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::generate_verilog_with()`].
+    pub fn netlist_with(&self, options: &MultiplierOptions) -> Result<Netlist, CsdMultiplierError> {
+        check_rounding(split_binary_point(&self.csd).1, options)?;
+        Ok(self.lower(options))
+    }
+
+    /// Verilog text for already checked `options`.
+    fn print(&self, options: &MultiplierOptions) -> String {
+        let options = self.effective_options(options);
+        let netlist = lower_csd_multiplier(&self.csd, self.n, self.m, &options);
+        print_verilog(&netlist, &[&self.csd], &options)
+    }
+
+    /// Netlist for already checked `options` (the coefficient was validated
+    /// by the constructors).
+    fn lower(&self, options: &MultiplierOptions) -> Netlist {
+        let options = self.effective_options(options);
+        lower_csd_multiplier(&self.csd, self.n, self.m, &options)
+    }
+
+    /// `options` with the names set by [`Self::with_naming()`] applied.
+    fn effective_options(&self, options: &MultiplierOptions) -> MultiplierOptions {
+        if self.naming == Naming::default() {
+            options.clone()
+        } else {
+            options.clone().naming(self.naming.clone())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Netlist lowering
+// ---------------------------------------------------------------------------
+
+/// Incrementally builds the shift-add [`Netlist`] of one module.
+struct NetlistBuilder<'a> {
+    netlist: Netlist,
+    options: &'a MultiplierOptions,
+    width: usize,
+    shift_wires: HashMap<usize, usize>,
+    tree_wires: usize,
+    round_wire: Option<usize>,
+}
+
+impl<'a> NetlistBuilder<'a> {
+    /// Start a module with the single signed input `x`; new wires are `width` bits.
+    fn new(
+        module_name: &str,
+        input_width: usize,
+        width: usize,
+        options: &'a MultiplierOptions,
+    ) -> Self {
+        Self {
+            netlist: Netlist {
+                module_name: module_name.to_string(),
+                inputs: vec![Port {
+                    name: options.naming.input().to_string(),
+                    width: input_width,
+                    frac_bits: 0,
+                    comment: Some("Input value".to_string()),
+                }],
+                wires: Vec::new(),
+                outputs: Vec::new(),
+            },
+            options,
+            width,
+            shift_wires: HashMap::new(),
+            tree_wires: 0,
+            round_wire: None,
+        }
+    }
+
+    fn add_wire(&mut self, name: String, expr: WireExpr, comment: Option<String>) -> usize {
+        self.netlist.wires.push(Wire {
+            name,
+            width: self.width,
+            expr,
+            comment,
+        });
+        self.netlist.wires.len() - 1
+    }
+
+    fn add_output(&mut self, port: Port, terms: Vec<Term>, comment: String) {
+        self.netlist.outputs.push(Output {
+            port,
+            terms,
+            comment: Some(comment),
+        });
+    }
+
+    /// Sum `parts` into `port`, requantized to the requested fractional bits.
+    ///
+    /// Dropping $s$ fractional bits computes the exact product in a
+    /// `{port}_full` wire and takes `{port}_full >>> s`; rounding first adds the
+    /// constant $2^{s-1}$.
+    fn add_product(&mut self, mut port: Port, mut parts: Vec<Term>, comment: String) {
+        let frac_bits = self.options.output_frac_bits.unwrap_or(port.frac_bits);
+        if frac_bits >= port.frac_bits {
+            let pad = (frac_bits - port.frac_bits) as isize;
+            let terms = self
+                .sum(parts)
+                .into_iter()
+                .map(|t| Term {
+                    shift: t.shift + pad,
+                    ..t
+                })
+                .collect();
+            port.width += frac_bits - port.frac_bits;
+            port.frac_bits = frac_bits;
+            self.add_output(port, terms, comment);
+            return;
+        }
+
+        let drop = port.frac_bits - frac_bits;
+        let requantize = match self.options.rounding {
+            OutputRounding::Truncate => format!("Truncate to {}", frac_bits_text(frac_bits)),
+            OutputRounding::Nearest => {
+                let round = match self.round_wire {
+                    Some(idx) => idx,
+                    None => {
+                        let idx = self.add_wire(
+                            self.options.naming.wire("_round"),
+                            // Bounded by check_rounding()
+                            WireExpr::Constant(1i128 << (drop - 1)),
+                            Some("Rounding constant (half an output LSB)".to_string()),
+                        );
+                        self.round_wire = Some(idx);
+                        idx
+                    }
+                };
+                parts.push(Term::add(Source::Wire(round)));
+                format!("Round to nearest, {}", frac_bits_text(frac_bits))
+            }
+        };
+        let terms = self.sum(parts);
+        let full = self.add_wire(
+            self.options.naming.wire(&format!("{}_full", port.name)),
+            WireExpr::Sum(terms),
+            Some(comment),
+        );
+        port.width -= drop;
+        port.frac_bits = frac_bits;
+        let term = Term {
+            op: TermOp::Add,
+            source: Source::Wire(full),
+            shift: -(drop as isize),
+        };
+        self.add_output(port, vec![term], requantize);
+    }
+
+    /// The finished netlist, with tight widths unless legacy widths were requested.
+    fn finish(mut self) -> Netlist {
+        if !self.options.legacy_widths {
+            self.netlist.minimize_widths();
+        }
+        self.netlist
+    }
+
+    /// Declare `x_shift{p}` for every power, highest first.
+    fn add_shift_wires(&mut self, powers: &BTreeSet<usize>) {
+        for (i, &power) in powers.iter().rev().enumerate() {
+            let comment = (i == 0).then(|| "Create shifted versions of input".to_string());
+            let idx = self.add_wire(
+                self.options.naming.shift_wire(power),
+                WireExpr::Shift { input: 0, power },
+                comment,
+            );
+            self.shift_wires.insert(power, idx);
+        }
+    }
+
+    /// Terms for the digits of `csd_str[start..start + length]`.
+    fn range_terms(
+        &self,
+        csd_str: &str,
+        start: usize,
+        length: usize,
+        max_power: usize,
+    ) -> Vec<Term> {
+        let bytes = csd_str.as_bytes();
+        let end = start.saturating_add(length).min(bytes.len());
+        bytes[start..end]
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &c)| {
+                let op = match c {
+                    b'+' => TermOp::Add,
+                    b'-' => TermOp::Sub,
+                    _ => return None,
+                };
+                let power = max_power - (start + i);
+                Some(Term {
+                    op,
+                    source: Source::Wire(self.shift_wires[&power]),
+                    shift: 0,
+                })
+            })
+            .collect()
+    }
+
+    /// Terms for `csd_str` with each non-overlapping occurrence of `pattern`
+    /// replaced by wire `pat_wire`, whose digits sit at position `base_pos`.
+    fn pattern_terms(
+        &self,
+        csd_str: &str,
+        max_power: usize,
+        pattern: &str,
+        base_pos: usize,
+        pat_wire: usize,
+    ) -> Vec<Term> {
+        let mut terms = Vec::new();
+        let mut cur = 0;
+        for pos in find_pattern_occurrences(csd_str, pattern) {
+            terms.extend(self.range_terms(csd_str, cur, pos - cur, max_power));
+            terms.push(Term {
+                op: TermOp::Add,
+                source: Source::Wire(pat_wire),
+                shift: base_pos as isize - pos as isize,
+            });
+            cur = pos + pattern.len();
+        }
+        terms.extend(self.range_terms(csd_str, cur, csd_str.len() - cur, max_power));
+        terms
+    }
+
+    /// Arrange `terms` per the adder structure; returns the operands of the final sum.
+    fn sum(&mut self, terms: Vec<Term>) -> Vec<Term> {
+        match self.options.adder_structure {
+            AdderStructure::Chain => terms,
+            AdderStructure::Tree => self.reduce_tree(terms),
+        }
+    }
+
+    /// Repeatedly add the two shallowest operands until two remain.
+    ///
+    /// Merging the two earliest-arriving operands first minimizes the depth
+    /// of the final sum, and yields a balanced tree when all terms arrive together.
+    fn reduce_tree(&mut self, terms: Vec<Term>) -> Vec<Term> {
+        let first_new = self.netlist.wires.len();
+        let depths = self.netlist.wire_depths();
+        let mut items: Vec<(Term, usize)> = terms
+            .into_iter()
+            .map(|t| (t, Netlist::term_depth(&t, &depths)))
+            .collect();
+        while items.len() > 2 {
+            let mut order: Vec<usize> = (0..items.len()).collect();
+            order.sort_by_key(|&i| (items[i].1, i));
+            let (a, b) = (order[0].min(order[1]), order[0].max(order[1]));
+            let (tb, db) = items.remove(b);
+            let (ta, da) = items[a];
+            items[a] = (self.tree_node(ta, tb), da.max(db) + 1);
+        }
+        if let Some(wire) = self.netlist.wires.get_mut(first_new) {
+            wire.comment = Some("Balanced adder tree".to_string());
+        }
+        // `b - a` rather than `-a + b`: no separate negation
+        if items.len() == 2 && items[0].0.op == TermOp::Sub && items[1].0.op == TermOp::Add {
+            items.swap(0, 1);
+        }
+        items.into_iter().map(|(t, _)| t).collect()
+    }
+
+    /// Add `a + b` as a new tree wire, factoring out a leading minus sign.
+    fn tree_node(&mut self, mut a: Term, mut b: Term) -> Term {
+        let op = a.op;
+        if op == TermOp::Sub {
+            a.op = a.op.negate();
+            b.op = b.op.negate();
+        }
+        let name = self.options.naming.wire(&format!("_t{}", self.tree_wires));
+        self.tree_wires += 1;
+        let idx = self.add_wire(name, WireExpr::Sum(vec![a, b]), None);
+        Term {
+            op,
+            source: Source::Wire(idx),
+            shift: 0,
+        }
+    }
+}
+
+/// Lower a single coefficient to a [`Netlist`] (input already validated).
+fn lower_csd_multiplier(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Netlist {
+    // Fractional digits are scaled to integer powers; the output keeps the binary point
+    let module_name = options.naming.module("csd_multiplier", &[csd_str]);
+    let (digits, frac_bits) = split_binary_point(csd_str);
+    let (csd_str, max_power) = (digits.as_str(), max_power + frac_bits);
+    let ow = output_width(input_width, max_power);
+    let mut builder = NetlistBuilder::new(&module_name, input_width, ow, options);
+
+    let powers: BTreeSet<usize> = parse_terms(csd_str, max_power)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(p, _)| p)
+        .collect();
+    builder.add_shift_wires(&powers);
+
+    let pattern = if options.share_patterns {
+        lcsre_pattern(csd_str)
+    } else {
+        None
+    };
+    let (comment, parts) = if powers.is_empty() {
+        ("CSD implementation", Vec::new())
+    } else if let Some((pat, positions)) = pattern {
+        // LCSRe-optimized path
+        let base_pos = positions[0];
+        let pat_terms = builder.range_terms(csd_str, base_pos, pat.len(), max_power);
+        let pat_terms = builder.sum(pat_terms);
+        let pat_wire = builder.add_wire(
+            options.naming.wire("_pat"),
+            WireExpr::Sum(pat_terms),
+            Some(format!("LCSRe: repeated pattern \"{}\"", pat)),
+        );
+        let parts = builder.pattern_terms(csd_str, max_power, &pat, base_pos, pat_wire);
+        ("CSD implementation (LCSRe optimized)", parts)
+    } else {
+        // flat path (no repeated pattern)
+        let parts = builder.range_terms(csd_str, 0, csd_str.len(), max_power);
+        ("CSD implementation", parts)
+    };
+
+    let port_comment = match options.output_frac_bits.unwrap_or(frac_bits) {
+        0 => "Result of multiplication".to_string(),
+        f => format!("Result of multiplication ({})", frac_bits_text(f)),
+    };
+    builder.add_product(
+        Port {
+            name: options.naming.output("result").to_string(),
+            width: ow,
+            frac_bits,
+            comment: Some(port_comment),
+        },
+        parts,
+        comment.to_string(),
+    );
+    builder.finish()
+}
+
+/// Align coefficients on a common power axis.
+///
+/// Every CSD string is padded with leading zeros up to the largest
+/// `max_power` and with trailing zeros up to the largest number of fractional
+/// digits $F$, so digit `i` of every returned string has weight $2^{m-i}$.
+/// Returns the padded digit strings (binary point removed) and $F$.
+fn align_coefficients(coeffs: &[MultiplierSpec]) -> (Vec<String>, usize) {
+    let top = coeffs.iter().map(|s| s.max_power).max().unwrap_or(0);
+    let frac_bits = coeffs
+        .iter()
+        .map(|s| split_binary_point(&s.csd).1)
+        .max()
+        .unwrap_or(0);
+    let digits = coeffs
+        .iter()
+        .map(|s| {
+            let (digits, f) = split_binary_point(&s.csd);
+            format!(
+                "{}{}{}",
+                "0".repeat(top - s.max_power),
+                digits,
+                "0".repeat(frac_bits - f)
+            )
+        })
+        .collect();
+    (digits, frac_bits)
+}
+
+/// Scaled integer value of every coefficient on the common axis of
+/// [`align_coefficients()`], i.e. the coefficients times $2^F$.
+fn aligned_values(coeffs: &[MultiplierSpec]) -> (Vec<i128>, usize) {
+    let (digits, frac_bits) = align_coefficients(coeffs);
+    (digits.iter().map(|d| csd_value(d)).collect(), frac_bits)
+}
+
+/// A coefficient derived from another one's product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fold {
+    /// Index of the coefficient whose product is reused
+    source: usize,
+    /// The product is negated
+    negate: bool,
+    /// The product is shifted left by this many bits
+    shift: usize,
+}
+
+/// Find coefficients that equal another one up to sign and a power of two.
+///
+/// `aligned` are digit strings on a common power axis. Coefficients with the
+/// same non-zero digits (after flipping a leading '-') form a group; the one
+/// with the fewest trailing zeros is built, and every other member gets a
+/// [`Fold`] that derives it with a negation and/or left shift.
+fn fold_coefficients(aligned: &[String]) -> Vec<Option<Fold>> {
+    let mut folds = vec![None; aligned.len()];
+    let mut groups: HashMap<String, Vec<(usize, bool, usize)>> = HashMap::new();
+    for (i, digits) in aligned.iter().enumerate() {
+        let body = digits.trim_matches('0');
+        if body.is_empty() {
+            continue;
+        }
+        let trailing = digits.len() - digits.trim_end_matches('0').len();
+        let negative = body.starts_with('-');
+        let key = if negative {
+            body.chars()
+                .map(|c| match c {
+                    '+' => '-',
+                    '-' => '+',
+                    c => c,
+                })
+                .collect()
+        } else {
+            body.to_string()
+        };
+        groups.entry(key).or_default().push((i, negative, trailing));
+    }
+    for members in groups.values() {
+        let &(source, source_negative, source_trailing) = members
+            .iter()
+            .min_by_key(|&&(i, _, trailing)| (trailing, i))
+            .unwrap();
+        for &(i, negative, trailing) in members {
+            if i != source {
+                folds[i] = Some(Fold {
+                    source,
+                    negate: negative != source_negative,
+                    shift: trailing - source_trailing,
+                });
+            }
+        }
+    }
+    folds
+}
+
+/// Lower a validated coefficient list (sharing one input width) to a [`Netlist`].
+fn lower_csd_multipliers(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Netlist {
+    let input_width = coeffs[0].input_width;
+    let (csd_strings, frac_bits) = align_coefficients(coeffs);
+    let max_power = csd_strings[0].len() - 1;
+    let ow = output_width(input_width, max_power);
+    let csds: Vec<&str> = coeffs.iter().map(|spec| spec.csd.as_str()).collect();
+    let module_name = options.naming.module(module_name, &csds);
+    let mut builder = NetlistBuilder::new(&module_name, input_width, ow, options);
+
+    // Duplicates, negations and power-of-two multiples reuse one product
+    let folds = if options.share_patterns {
+        fold_coefficients(&csd_strings)
+    } else {
+        vec![None; coeffs.len()]
+    };
+
+    // Collect the x_shift powers of every coefficient that is built
+    let mut all_powers: BTreeSet<usize> = BTreeSet::new();
+    for (csd, _) in csd_strings.iter().zip(&folds).filter(|(_, f)| f.is_none()) {
+        for (i, c) in csd.char_indices() {
+            if c != '0' {
+                all_powers.insert(max_power - i);
+            }
+        }
+    }
+    builder.add_shift_wires(&all_powers);
+
+    // Find best cross-CSD pattern among the coefficients that are built
+    let mut best_pattern = String::new();
+    let mut best_occurrences: Vec<(usize, usize)> = Vec::new();
+    if options.share_patterns {
+        let built: Vec<String> = csd_strings
+            .iter()
+            .zip(&folds)
+            .map(|(csd, fold)| match fold {
+                Some(_) => String::new(),
+                None => csd.clone(),
+            })
+            .collect();
+        let cross = find_cross_patterns(&built);
+        let mut best_score = 0;
+        for (pat, occ) in &cross {
+            let nnz = count_nnz(pat);
+            let score = (nnz.saturating_sub(1)) * (occ.len().saturating_sub(1));
+            // Ties broken by the pattern itself so the output is deterministic
+            if score > best_score || (score == best_score && score > 0 && *pat < best_pattern) {
+                best_score = score;
+                best_pattern.clone_from(pat);
+                best_occurrences.clone_from(occ);
+            }
+        }
+    }
+
+    // Shared CSE wire, placed at the lowest occurrence position
+    let mut cse_wire = None;
+    let cse_base_pos = best_occurrences
+        .iter()
+        .map(|(_, pos)| *pos)
+        .min()
+        .unwrap_or(0);
+    if !best_pattern.is_empty() {
+        let cse_terms = builder.range_terms(
+            &best_pattern,
+            0,
+            best_pattern.len(),
+            max_power - cse_base_pos,
+        );
+        let cse_terms = builder.sum(cse_terms);
+        cse_wire = Some(builder.add_wire(
+            options.naming.wire("_cse_0"),
+            WireExpr::Sum(cse_terms),
+            Some(format!("Cross-CSE: shared pattern \"{}\"", best_pattern)),
+        ));
+    }
+
+    // Set of coeff indices that have the pattern
+    let cse_coeffs: HashSet<usize> = best_occurrences.iter().map(|(ci, _)| *ci).collect();
+
+    let parts_of = |builder: &mut NetlistBuilder, idx: usize| {
+        let csd = &csd_strings[idx];
+        match cse_wire {
+            Some(wire) if cse_coeffs.contains(&idx) => {
+                builder.pattern_terms(csd, max_power, &best_pattern, cse_base_pos, wire)
+            }
+            _ => builder.range_terms(csd, 0, csd.len(), max_power),
+        }
+    };
+
+    // Products reused by folded coefficients get their own wire
+    let mut shared: HashMap<usize, usize> = HashMap::new();
+    for (idx, spec) in coeffs.iter().enumerate() {
+        let users: Vec<&str> = folds
+            .iter()
+            .zip(coeffs)
+            .filter(|(fold, _)| matches!(fold, Some(f) if f.source == idx))
+            .map(|(_, s)| s.name.as_str())
+            .collect();
+        if users.is_empty() {
+            continue;
+        }
+        let parts = parts_of(&mut builder, idx);
+        let terms = builder.sum(parts);
+        let wire = builder.add_wire(
+            options.naming.wire(&format!("_{}_prod", spec.name)),
+            WireExpr::Sum(terms),
+            Some(format!(
+                "Shared product of {} (reused by {})",
+                spec.name,
+                users.join(", ")
+            )),
+        );
+        shared.insert(idx, wire);
+    }
+
+    // Per-coefficient assignments
+    for (idx, spec) in coeffs.iter().enumerate() {
+        let mut comment = format!("{}: {}", spec.name, spec.csd);
+        let parts = match (folds[idx], shared.get(&idx)) {
+            (Some(fold), _) => {
+                write!(
+                    comment,
+                    " (= {}{}",
+                    if fold.negate { "-" } else { "" },
+                    coeffs[fold.source].name
+                )
+                .unwrap();
+                if fold.shift > 0 {
+                    write!(comment, " << {}", fold.shift).unwrap();
+                }
+                comment.push(')');
+                vec![Term {
+                    op: if fold.negate {
+                        TermOp::Sub
+                    } else {
+                        TermOp::Add
+                    },
+                    source: Source::Wire(shared[&fold.source]),
+                    shift: fold.shift as isize,
+                }]
+            }
+            (None, Some(&wire)) => vec![Term::add(Source::Wire(wire))],
+            (None, None) => parts_of(&mut builder, idx),
+        };
+        builder.add_product(
+            Port {
+                name: spec.name.clone(),
+                width: output_width(spec.input_width, spec.max_power + frac_bits),
+                frac_bits,
+                comment: None,
+            },
+            parts,
+            comment,
+        );
+    }
+    builder.finish()
+}
+
+// ---------------------------------------------------------------------------
+// Verilog emission
+// ---------------------------------------------------------------------------
+
+/// Render a signed sum of terms as a Verilog expression.
+fn verilog_sum(netlist: &Netlist, terms: &[Term]) -> String {
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+    let mut expr = String::new();
+    for (i, term) in terms.iter().enumerate() {
+        match (i, term.op) {
+            (0, TermOp::Add) => {}
+            (0, TermOp::Sub) => expr.push('-'),
+            (_, TermOp::Add) => expr.push_str(" + "),
+            (_, TermOp::Sub) => expr.push_str(" - "),
+        }
+        let name = netlist.source_name(term.source);
+        match term.shift {
+            0 => expr.push_str(name),
+            s if s > 0 => write!(expr, "({} <<< {})", name, s).unwrap(),
+            s => write!(expr, "({} >>> {})", name, -s).unwrap(),
+        }
+    }
+    expr
+}
+
+/// Render a constant as a sized signed Verilog literal.
+fn verilog_constant(value: i128, width: usize) -> String {
+    if value < 0 {
+        format!("-{}'sd{}", width, value.unsigned_abs())
+    } else {
+        format!("{}'sd{}", width, value)
+    }
+}
+
+/// Print a [`Netlist`] as a Verilog-2001 module.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{csd_multiplier_netlist, emit_verilog, MultiplierOptions};
+///
+/// let netlist = csd_multiplier_netlist("+0-", 8, 2, &MultiplierOptions::new()).unwrap();
+/// assert!(emit_verilog(&netlist).contains("assign result = x_shift2 - x_shift0;"));
+/// ```
+pub fn emit_verilog(netlist: &Netlist) -> String {
+    emit_verilog_with(netlist, &VerilogStyle::new())
+}
+
+/// Print a [`Netlist`] as a Verilog-2001 module in the given style.
+///
+/// [`CommentStyle::Verbose`] needs the coefficients, so its header is only
+/// added by the generators; here it prints like [`CommentStyle::Normal`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{
+///     csd_multiplier_netlist, emit_verilog_with, CommentStyle, MultiplierOptions, VerilogStyle,
+/// };
+///
+/// let netlist = csd_multiplier_netlist("+0-", 8, 2, &MultiplierOptions::new()).unwrap();
+/// let v = emit_verilog_with(&netlist, &VerilogStyle::new().comments(CommentStyle::None));
+/// assert!(!v.contains("//"));
+/// ```
+pub fn emit_verilog_with(netlist: &Netlist, style: &VerilogStyle) -> String {
+    let comments = style.comments != CommentStyle::None;
+    let mut verilog = String::new();
+
+    // --- module header ---
+    writeln!(verilog).unwrap();
+    writeln!(verilog, "module {} (", netlist.module_name).unwrap();
+    let ports: Vec<(&str, &Port)> = netlist
+        .inputs
+        .iter()
+        .map(|p| ("input", p))
+        .chain(netlist.outputs.iter().map(|o| ("output", &o.port)))
+        .collect();
+    for (i, (dir, port)) in ports.iter().enumerate() {
+        let last = i + 1 == ports.len();
+        write!(
+            verilog,
+            "    {} signed [{}:0] {}",
+            dir,
+            port.width - 1,
+            port.name
         )
         .unwrap();
+        match (port.comment.as_ref().filter(|_| comments), last) {
+            (Some(c), false) => writeln!(verilog, ",      // {}", c).unwrap(),
+            (Some(c), true) => writeln!(verilog, " // {}", c).unwrap(),
+            (None, false) => writeln!(verilog, ",").unwrap(),
+            (None, true) => writeln!(verilog).unwrap(),
+        }
+    }
+    writeln!(verilog, ");").unwrap();
+
+    // --- wire declarations ---
+    for (i, wire) in netlist.wires.iter().enumerate() {
+        match &wire.comment {
+            Some(comment) if comments => {
+                writeln!(verilog).unwrap();
+                writeln!(verilog, "    // {}", comment).unwrap();
+            }
+            _ if i == 0 && !comments => writeln!(verilog).unwrap(),
+            _ => {}
+        }
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                let input = &netlist.inputs[*input];
+                let pad = wire.width.saturating_sub(input.width);
+                match style.sign_extension {
+                    SignExtension::Arithmetic => format!("{} <<< {}", input.name, power),
+                    SignExtension::Concatenation if pad == 0 => {
+                        format!("{} << {}", input.name, power)
+                    }
+                    SignExtension::Concatenation => format!(
+                        "$signed({{ {{{}{{{}[{}]}}}}, {}}}) << {}",
+                        pad,
+                        input.name,
+                        input.width - 1,
+                        input.name,
+                        power
+                    ),
+                }
+            }
+            WireExpr::Sum(terms) => verilog_sum(netlist, terms),
+            WireExpr::Constant(value) => verilog_constant(*value, wire.width),
+        };
         writeln!(
-            output,
-            "module csd_multiplier (
-    input signed [{}:0] x,      // Input value (signed)
-    output signed [{}:0] result // Result (signed)
-);",
-            self.n - 1,
-            self.n + self.m - 1
+            verilog,
+            "    wire signed [{}:0] {} = {};",
+            wire.width - 1,
+            wire.name,
+            expr
         )
         .unwrap();
     }
 
-    /// Return sorted unique powers of non-zero digits, descending.
-    fn get_unique_powers(&self) -> Vec<usize> {
-        let mut powers: Vec<usize> = self
-            .csd
-            .char_indices()
-            .filter(|(_, c)| *c != '0')
-            .map(|(i, _)| self.m - i)
-            .collect();
-        powers.sort_unstable_by(|a, b| b.cmp(a));
-        powers.dedup();
-        powers
-    }
-
-    fn generate_wires(&self, output: &mut String) {
-        let shift_powers = self.get_unique_powers();
-        if shift_powers.is_empty() {
-            return;
+    // --- combinational logic ---
+    for (i, output) in netlist.outputs.iter().enumerate() {
+        match &output.comment {
+            Some(comment) if comments => {
+                writeln!(verilog).unwrap();
+                writeln!(verilog, "    // {}", comment).unwrap();
+            }
+            _ if i == 0 && !comments => writeln!(verilog).unwrap(),
+            _ => {}
         }
         writeln!(
-            output,
-            "\n    // Signed shifted versions (Verilog handles sign extension)"
+            verilog,
+            "    assign {} = {};",
+            output.port.name,
+            verilog_sum(netlist, &output.terms)
         )
         .unwrap();
-        for &power in &shift_powers {
-            let padding = self.m - power;
+    }
+
+    writeln!(verilog, "endmodule").unwrap();
+    verilog
+}
+
+/// Print `netlist` in the style of `options`, with the [`CommentStyle::Verbose`]
+/// header listing `csds` (one per output).
+fn print_verilog(netlist: &Netlist, csds: &[&str], options: &MultiplierOptions) -> String {
+    let style = &options.verilog_style;
+    let verilog = emit_verilog_with(netlist, style);
+    if style.comments != CommentStyle::Verbose {
+        return verilog;
+    }
+    let mut header = String::new();
+    for (output, csd) in netlist.outputs.iter().zip(csds) {
+        let value = csd_value_f64(csd);
+        if csds.len() == 1 {
             writeln!(
-                output,
-                "    wire signed [{}:0] x_shift{} = $signed({{ {{{}{{x[{}]}}}}, x}}) << {};",
-                self.n + self.m - 1,
-                power,
-                padding,
-                self.n - 1,
-                power
+                header,
+                "// CSD Multiplier for pattern: {} (value: {})",
+                csd, value
             )
             .unwrap();
-        }
-    }
-
-    /// Generate assign statement with LCSRe optimization.
-    fn generate_result_lcsre(&self, output: &mut String) {
-        let terms = parse_terms(&self.csd, self.m).unwrap_or_default();
-        if terms.is_empty() {
-            writeln!(output, "\n    // CSD implementation").unwrap();
-            writeln!(output, "    assign result = 0;").unwrap();
-            return;
-        }
-
-        // Detect LCSRe optimization opportunity
-        let repeated = longest_repeated_substring(&self.csd);
-        let pat_positions = if repeated.len() > 1 {
-            let pat_nnz = repeated.chars().filter(|c| *c == '+' || *c == '-').count();
-            if pat_nnz >= 2 {
-                let pos = find_pattern_occurrences(&self.csd, &repeated);
-                if pos.len() >= 2 {
-                    Some((repeated, pos))
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
         } else {
-            None
-        };
-
-        if let Some((ref pat, ref positions)) = pat_positions {
-            // LCSRe-optimized path
-            let base_pos = positions[0];
-            let ow = output_width(self.n, self.m);
-
-            let pat_expr = build_range_expr(&self.csd, base_pos, pat.len(), self.m);
-            writeln!(output, "\n    // LCSRe: repeated pattern \"{}\"", pat).unwrap();
             writeln!(
-                output,
-                "    wire signed [{}:0] _pat = {};",
-                ow - 1,
-                pat_expr
+                header,
+                "// {}: {} (value: {})",
+                output.port.name, csd, value
             )
             .unwrap();
-
-            let mut expr = String::new();
-            let mut cur = 0;
-            for &pos in positions {
-                // gap before this occurrence
-                if pos > cur {
-                    let gap = build_range_expr(&self.csd, cur, pos - cur, self.m);
-                    if !gap.is_empty() {
-                        if expr.is_empty() {
-                            expr = gap;
-                        } else {
-                            write!(expr, " + {}", gap).unwrap();
-                        }
-                    }
-                }
-                // pattern occurrence
-                let shift = pos as isize - base_pos as isize;
-                let pat_ref = if shift == 0 {
-                    "_pat".to_string()
-                } else {
-                    format!("(_pat >>> {})", shift)
-                };
-                if expr.is_empty() {
-                    expr = pat_ref;
-                } else {
-                    write!(expr, " + {}", pat_ref).unwrap();
-                }
-                cur = pos + pat.len();
-            }
-            // suffix
-            if cur < self.csd.len() {
-                let suffix = build_range_expr(&self.csd, cur, self.csd.len() - cur, self.m);
-                if !suffix.is_empty() {
-                    write!(expr, " + {}", suffix).unwrap();
-                }
-            }
-
-            writeln!(output, "\n    // CSD implementation (LCSRe optimized)").unwrap();
-            writeln!(output, "    assign result = {};", expr).unwrap();
-        } else {
-            // flat path (no repeated pattern)
-            writeln!(output, "\n    // CSD implementation with signed arithmetic").unwrap();
-            let (first_power, first_op) = terms[0];
-            let mut expr = format!(
-                "{}x_shift{}",
-                if first_op == TermOp::Sub { "-" } else { "" },
-                first_power
-            );
-            for (power, op) in &terms[1..] {
-                match op {
-                    TermOp::Add => write!(expr, " + x_shift{}", power).unwrap(),
-                    TermOp::Sub => write!(expr, " - x_shift{}", power).unwrap(),
-                }
-            }
-            writeln!(output, "    assign result = {};", expr).unwrap();
         }
     }
+    header + verilog.trim_start_matches('\n')
 }
 
 // ---------------------------------------------------------------------------
@@ -386,57 +2213,6 @@ fn count_nnz(s: &str) -> usize {
         .count()
 }
 
-/// Build a coefficient expression using CSE wire + flat gap terms.
-fn build_coeff_expr(
-    csd: &str,
-    max_power: usize,
-    pattern: &str,
-    cse_base_pos: usize,
-    cse_name: &str,
-) -> String {
-    if pattern.is_empty() {
-        return build_range_expr(csd, 0, csd.len(), max_power);
-    }
-
-    let positions = find_pattern_occurrences(csd, pattern);
-    let mut parts: Vec<String> = Vec::new();
-    let mut cur = 0;
-
-    for pos in positions {
-        // gap before this occurrence
-        if pos > cur {
-            let gap = build_range_expr(csd, cur, pos - cur, max_power);
-            if !gap.is_empty() {
-                parts.push(gap);
-            }
-        }
-        // CSE reference
-        let shift = pos as isize - cse_base_pos as isize;
-        if shift == 0 {
-            parts.push(cse_name.to_string());
-        } else {
-            parts.push(format!("({} >>> {})", cse_name, shift));
-        }
-        cur = pos + pattern.len();
-    }
-    // suffix
-    if cur < csd.len() {
-        let gap = build_range_expr(csd, cur, csd.len() - cur, max_power);
-        if !gap.is_empty() {
-            parts.push(gap);
-        }
-    }
-
-    if parts.is_empty() {
-        return String::new();
-    }
-    let mut result = parts[0].clone();
-    for p in &parts[1..] {
-        write!(result, " + {}", p).unwrap();
-    }
-    result
-}
-
 /// Find substrings (NNZ >= 2) that appear in >= 2 different CSD strings.
 /// Returns a map: pattern -> [(coeff_index, position), ...].
 fn find_cross_patterns(csd_list: &[String]) -> HashMap<String, Vec<(usize, usize)>> {
@@ -467,6 +2243,41 @@ fn find_cross_patterns(csd_list: &[String]) -> HashMap<String, Vec<(usize, usize
     patterns
 }
 
+/// Build the shift-add [`Netlist`] for a single CSD coefficient.
+///
+/// This is the structure [`generate_csd_multiplier_with()`] prints; it can be
+/// inspected directly, e.g. for [`Netlist::adder_count()`] and [`Netlist::adder_depth()`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+///
+/// let netlist = csd_multiplier_netlist("+00-00+0+", 8, 8, &MultiplierOptions::new()).unwrap();
+/// assert_eq!(netlist.adder_count(), 3);
+/// assert_eq!(netlist.adder_depth(), 3);
+/// ```
+pub fn csd_multiplier_netlist(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<Netlist, CsdMultiplierError> {
+    let (csd_str, max_power) =
+        check_coefficient(csd_str, input_width, max_power, options.validation)?;
+    check_rounding(split_binary_point(&csd_str).1, options)?;
+    Ok(lower_csd_multiplier(
+        &csd_str,
+        input_width,
+        max_power,
+        options,
+    ))
+}
+
 /// Generate Verilog code for a single CSD multiplier module (no cross-CSE).
 ///
 /// Converts a Canonical Signed Digit (CSD) string into a synthesizable
@@ -482,14 +2293,15 @@ fn find_cross_patterns(csd_list: &[String]) -> HashMap<String, Vec<(usize, usize
 ///
 /// # Arguments
 ///
-/// * `csd_str` - CSD string using '+', '-', '0' (e.g. "+00-00+0+")
+/// * `csd_str` - CSD string using '+', '-', '0' and an optional binary point (e.g. "+00-00+0+")
 /// * `input_width` - Bit width of the input signal x
-/// * `max_power` - Highest power of two in the CSD (must be csd_str.len() - 1)
+/// * `max_power` - Highest power of two in the CSD (the number of integer digits minus 1)
 ///
 /// # Errors
 ///
-/// Returns `CsdMultiplierError` if csd_str length doesn't match max_power+1
-/// or if the string contains characters other than '+', '-', '0'.
+/// Returns `CsdMultiplierError` if the number of integer digits doesn't match
+/// max_power+1 or if the string contains characters other than '+', '-', '0'
+/// and a single '.'.
 ///
 /// # Examples
 ///
@@ -505,162 +2317,148 @@ pub fn generate_csd_multiplier(
     input_width: usize,
     max_power: usize,
 ) -> Result<String, CsdMultiplierError> {
-    // --- validation ---
-    let len = csd_str.len();
-    if len != max_power + 1 {
-        return Err(CsdMultiplierError::LengthMismatch);
-    }
-    for &c in csd_str.as_bytes() {
-        if c != b'+' && c != b'-' && c != b'0' {
-            return Err(CsdMultiplierError::InvalidCharacter);
-        }
-    }
-
-    let terms = parse_terms(csd_str, max_power)?;
-    let ow = output_width(input_width, max_power);
-
-    let mut verilog = String::new();
+    generate_csd_multiplier_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
 
-    // --- module header ---
-    writeln!(verilog).unwrap();
-    writeln!(verilog, "module csd_multiplier (").unwrap();
-    writeln!(
-        verilog,
-        "    input signed [{}:0] x,      // Input value",
-        input_width - 1
-    )
-    .unwrap();
-    writeln!(
-        verilog,
-        "    output signed [{}:0] result // Result of multiplication",
-        ow - 1
-    )
-    .unwrap();
-    writeln!(verilog, ");").unwrap();
+/// Generate a single CSD multiplier module with explicit [`MultiplierOptions`].
+///
+/// With [`AdderStructure::Tree`] the partial products are summed through a
+/// balanced tree of `_tN` wires, so the adder depth is $\lceil \log_2 \text{NNZ} \rceil$
+/// instead of $\text{NNZ} - 1$ for the same number of adders.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{generate_csd_multiplier_with, AdderStructure, MultiplierOptions};
+///
+/// let options = MultiplierOptions::new().adder_structure(AdderStructure::Tree);
+/// let v = generate_csd_multiplier_with("+0-0+00+", 8, 7, &options).unwrap();
+/// assert!(v.contains("// Balanced adder tree"));
+/// ```
+pub fn generate_csd_multiplier_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(print_verilog(&netlist, &[csd_str], options))
+}
 
-    // --- wire declarations (deduplicated powers) ---
-    if !terms.is_empty() {
-        writeln!(verilog).unwrap();
-        writeln!(verilog, "    // Create shifted versions of input").unwrap();
-        let mut powers_needed: BTreeSet<usize> = BTreeSet::new();
-        // Reverse order: highest power first
-        for (p, _) in &terms {
-            powers_needed.insert(*p);
-        }
-        for p in powers_needed.into_iter().rev() {
-            writeln!(
-                verilog,
-                "    wire signed [{}:0] x_shift{} = x <<< {};",
-                ow - 1,
-                p,
-                p
-            )
-            .unwrap();
+/// Compare adder count and depth of every way of building one coefficient.
+///
+/// Returns one [`AdderCost`] per combination of [`AdderStructure`] and LCSRe
+/// sharing, so area (adders) can be traded against speed (depth).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{adder_cost_tradeoff, AdderStructure};
+///
+/// let costs = adder_cost_tradeoff("+0-0+0-0+0-0", 8, 11).unwrap();
+/// let flat_tree = costs
+///     .iter()
+///     .find(|c| c.adder_structure == AdderStructure::Tree && !c.share_patterns)
+///     .unwrap();
+/// assert_eq!((flat_tree.adders, flat_tree.depth), (5, 3));
+/// let shared_chain = costs
+///     .iter()
+///     .find(|c| c.adder_structure == AdderStructure::Chain && c.share_patterns)
+///     .unwrap();
+/// assert_eq!((shared_chain.adders, shared_chain.depth), (3, 3));
+/// ```
+pub fn adder_cost_tradeoff(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<Vec<AdderCost>, CsdMultiplierError> {
+    let mut costs = Vec::new();
+    for adder_structure in [AdderStructure::Chain, AdderStructure::Tree] {
+        for share_patterns in [true, false] {
+            let options = MultiplierOptions::new()
+                .adder_structure(adder_structure)
+                .share_patterns(share_patterns);
+            let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, &options)?;
+            costs.push(AdderCost {
+                adder_structure,
+                share_patterns,
+                adders: netlist.adder_count(),
+                depth: netlist.adder_depth(),
+            });
         }
     }
+    Ok(costs)
+}
 
-    // --- detect LCSRe optimization opportunity ---
-    let repeated = longest_repeated_substring(csd_str);
-
-    let pat_positions: Vec<usize> = if repeated.len() > 1 {
-        let pat_nnz = count_nnz(&repeated);
-        if pat_nnz >= 2 {
-            let pos = find_pattern_occurrences(csd_str, &repeated);
-            if pos.len() >= 2 {
-                pos
-            } else {
-                Vec::new()
-            }
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
-    };
-
-    let use_opt = !pat_positions.is_empty();
+/// Build the shift-add [`Netlist`] for a set of coefficients with cross-CSE.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`].
+pub fn csd_multipliers_netlist(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<Netlist, CsdMultiplierError> {
+    let checked = check_specs(coeffs, options.validation)?;
+    let frac_bits = checked
+        .iter()
+        .map(|spec| split_binary_point(&spec.csd).1)
+        .max()
+        .unwrap_or(0);
+    check_rounding(frac_bits, options)?;
+    Ok(lower_csd_multipliers(&checked, module_name, options))
+}
 
-    // --- combinational logic ---
-    if terms.is_empty() {
-        writeln!(verilog).unwrap();
-        writeln!(verilog, "    // CSD implementation").unwrap();
-        writeln!(verilog, "    assign result = 0;").unwrap();
-    } else if use_opt {
-        // LCSRe-optimized path
-        let base_pos = pat_positions[0];
-        let pat_expr = build_range_expr(csd_str, base_pos, repeated.len(), max_power);
-        writeln!(verilog).unwrap();
-        writeln!(verilog, "    // LCSRe: repeated pattern \"{}\"", repeated).unwrap();
-        writeln!(
-            verilog,
-            "    wire signed [{}:0] _pat = {};",
-            ow - 1,
-            pat_expr
-        )
-        .unwrap();
+/// Validate coefficient specifications that multiply the same input `x`.
+///
+/// Returns the specs with every CSD string checked (and canonicalized with
+/// [`Validation::Canonicalize`]).
+fn check_specs(
+    coeffs: &[MultiplierSpec],
+    validation: Validation,
+) -> Result<Vec<MultiplierSpec>, CsdMultiplierError> {
+    if coeffs.is_empty() {
+        return Err(CsdMultiplierError::EmptyCoefficients);
+    }
 
-        let mut expr = String::new();
-        let mut cur = 0;
-        for &pos in &pat_positions {
-            // prefix/gap before this occurrence
-            if pos > cur {
-                let gap = build_range_expr(csd_str, cur, pos - cur, max_power);
-                if !gap.is_empty() {
-                    if expr.is_empty() {
-                        expr = gap;
-                    } else {
-                        write!(expr, " + {}", gap).unwrap();
-                    }
-                }
-            }
-            // pattern occurrence
-            let shift = pos as isize - base_pos as isize;
-            let pat_ref = if shift == 0 {
-                "_pat".to_string()
-            } else {
-                format!("(_pat >>> {})", shift)
-            };
-            if expr.is_empty() {
-                expr = pat_ref;
-            } else {
-                write!(expr, " + {}", pat_ref).unwrap();
-            }
-            cur = pos + repeated.len();
-        }
-        // suffix
-        if cur < csd_str.len() {
-            let suffix = build_range_expr(csd_str, cur, csd_str.len() - cur, max_power);
-            if !suffix.is_empty() {
-                write!(expr, " + {}", suffix).unwrap();
-            }
+    let input_width = coeffs[0].input_width;
+    let mut checked = Vec::with_capacity(coeffs.len());
+    for (index, spec) in coeffs.iter().enumerate() {
+        if spec.input_width != input_width {
+            return Err(CsdMultiplierError::WidthMismatch {
+                index,
+                name: spec.name.clone(),
+                expected: input_width,
+                actual: spec.input_width,
+            });
         }
-
-        writeln!(verilog).unwrap();
-        writeln!(verilog, "    // CSD implementation (LCSRe optimized)").unwrap();
-        writeln!(verilog, "    assign result = {};", expr).unwrap();
-    } else {
-        // flat path (no repeated pattern)
-        writeln!(verilog).unwrap();
-        writeln!(verilog, "    // CSD implementation").unwrap();
-        let mut expr = String::new();
-        for (i, (power, op)) in terms.iter().enumerate() {
-            if i == 0 {
-                if *op == TermOp::Sub {
-                    write!(expr, "-").unwrap();
-                }
-                write!(expr, "x_shift{}", power).unwrap();
-            } else {
-                match op {
-                    TermOp::Add => write!(expr, " + x_shift{}", power).unwrap(),
-                    TermOp::Sub => write!(expr, " - x_shift{}", power).unwrap(),
-                }
-            }
+        if let Some(first) = coeffs[..index].iter().position(|s| s.name == spec.name) {
+            return Err(CsdMultiplierError::DuplicateOutput {
+                name: spec.name.clone(),
+                first,
+                second: index,
+            });
         }
-        writeln!(verilog, "    assign result = {};", expr).unwrap();
+        let (csd, max_power) =
+            check_coefficient(&spec.csd, spec.input_width, spec.max_power, validation)
+                .map_err(|e| e.in_spec(index, &spec.name))?;
+        checked.push(MultiplierSpec {
+            name: spec.name.clone(),
+            csd,
+            input_width,
+            max_power,
+        });
     }
-
-    writeln!(verilog, "endmodule").unwrap();
-    Ok(verilog)
+    Ok(checked)
 }
 
 /// Generate Verilog for multiple CSD multipliers with cross-CSE.
@@ -673,8 +2471,11 @@ pub fn generate_csd_multiplier(
 ///
 /// $$ y_k = \sum_{i=0}^{m} d_{k,i} \cdot (x \ll i), \quad d_{k,i} \in \{-1,0,+1\} $$
 ///
-/// All coefficients **must** share the same `input_width` and `max_power`
-/// so that the same bit position encodes the same power of two.
+/// All coefficients **must** share the same `input_width`. Their `max_power`
+/// and number of fractional digits may differ: digits are aligned on a common
+/// power axis internally (so shared sub-expressions line up by weight), every
+/// output port carries the largest number of fractional digits $F$ among the
+/// coefficients, and is $W_{\text{in}} + m_k + F$ bits wide for its own $m_k$.
 ///
 /// # Arguments
 ///
@@ -684,7 +2485,9 @@ pub fn generate_csd_multiplier(
 /// # Errors
 ///
 /// Returns `CsdMultiplierError::EmptyCoefficients` if the list is empty.
-/// Returns `CsdMultiplierError::WidthMismatch` if coefficient widths differ.
+/// Returns `CsdMultiplierError::WidthMismatch` if input widths differ,
+/// `CsdMultiplierError::DuplicateOutput` if two coefficients share a name, and
+/// `CsdMultiplierError::InvalidSpec` wrapping the CSD error of an invalid coefficient.
 ///
 /// # Examples
 ///
@@ -712,158 +2515,22 @@ pub fn generate_csd_multipliers(
     coeffs: &[MultiplierSpec],
     module_name: &str,
 ) -> Result<String, CsdMultiplierError> {
-    if coeffs.is_empty() {
-        return Err(CsdMultiplierError::EmptyCoefficients);
-    }
-
-    // Validation and uniform-width enforcement
-    let input_width = coeffs[0].input_width;
-    let max_power = coeffs[0].max_power;
-
-    for spec in coeffs {
-        if spec.input_width != input_width || spec.max_power != max_power {
-            return Err(CsdMultiplierError::WidthMismatch);
-        }
-        let len = spec.csd.len();
-        if len != max_power + 1 {
-            return Err(CsdMultiplierError::LengthMismatch);
-        }
-        for c in spec.csd.chars() {
-            if c != '+' && c != '-' && c != '0' {
-                return Err(CsdMultiplierError::InvalidCharacter);
-            }
-        }
-    }
-
-    let ow = output_width(input_width, max_power);
-
-    // Collect all x_shift powers
-    let mut all_powers: BTreeSet<usize> = BTreeSet::new();
-    for spec in coeffs {
-        for (i, c) in spec.csd.char_indices() {
-            if c != '0' {
-                all_powers.insert(max_power - i);
-            }
-        }
-    }
-
-    // Find best cross-CSD pattern
-    let csd_strings: Vec<String> = coeffs.iter().map(|s| s.csd.clone()).collect();
-    let cross = find_cross_patterns(&csd_strings);
-
-    let mut best_pattern = String::new();
-    let mut best_occurrences: Vec<(usize, usize)> = Vec::new();
-    let mut best_score = 0;
-
-    for (pat, occ) in &cross {
-        let nnz = count_nnz(pat);
-        let score = (nnz.saturating_sub(1)) * (occ.len().saturating_sub(1));
-        if score > best_score {
-            best_score = score;
-            best_pattern.clone_from(pat);
-            best_occurrences.clone_from(occ);
-        }
-    }
-
-    // Base position for the CSE wire
-    let cse_base_pos = if best_pattern.is_empty() {
-        0
-    } else {
-        best_occurrences
-            .iter()
-            .map(|(_, pos)| *pos)
-            .min()
-            .unwrap_or(0)
-    };
-
-    // Build the Verilog module
-    let mut verilog = String::new();
-    writeln!(verilog).unwrap();
-    writeln!(verilog, "module {} (", module_name).unwrap();
-    writeln!(
-        verilog,
-        "    input signed [{}:0] x,      // Input value",
-        input_width - 1
-    )
-    .unwrap();
-    for spec in coeffs {
-        let ow_spec = output_width(spec.input_width, spec.max_power);
-        writeln!(
-            verilog,
-            "    output signed [{}:0] {}",
-            ow_spec - 1,
-            spec.name
-        )
-        .unwrap();
-    }
-    writeln!(verilog, ");").unwrap();
-
-    // x_shift wires
-    if !all_powers.is_empty() {
-        writeln!(verilog).unwrap();
-        writeln!(verilog, "    // Create shifted versions of input").unwrap();
-        for p in all_powers.iter().rev() {
-            writeln!(
-                verilog,
-                "    wire signed [{}:0] x_shift{} = x <<< {};",
-                ow - 1,
-                p,
-                p
-            )
-            .unwrap();
-        }
-    }
-
-    // Shared CSE wire
-    let cse_name = "_cse_0";
-    if !best_pattern.is_empty() {
-        let cse_expr = build_range_expr(
-            &best_pattern,
-            0,
-            best_pattern.len(),
-            max_power.saturating_sub(cse_base_pos),
-        );
-        writeln!(verilog).unwrap();
-        writeln!(
-            verilog,
-            "    // Cross-CSE: shared pattern \"{}\"",
-            best_pattern
-        )
-        .unwrap();
-        writeln!(
-            verilog,
-            "    wire signed [{}:0] {} = {};",
-            ow - 1,
-            cse_name,
-            cse_expr
-        )
-        .unwrap();
-    }
-
-    // Set of coeff indices that have the pattern
-    let cse_coeffs: HashSet<usize> = best_occurrences.iter().map(|(ci, _)| *ci).collect();
-
-    // Per-coefficient assignments
-    for (idx, spec) in coeffs.iter().enumerate() {
-        writeln!(verilog).unwrap();
-        writeln!(verilog, "    // {}: {}", spec.name, spec.csd).unwrap();
-
-        let has_cse = !best_pattern.is_empty() && cse_coeffs.contains(&idx);
-        let expr = if has_cse {
-            build_coeff_expr(&spec.csd, max_power, &best_pattern, cse_base_pos, cse_name)
-        } else {
-            build_coeff_expr(&spec.csd, max_power, "", 0, "")
-        };
-
-        if expr.is_empty() {
-            writeln!(verilog, "    assign {} = 0;", spec.name).unwrap();
-        } else {
-            writeln!(verilog, "    assign {} = {};", spec.name, expr).unwrap();
-        }
-    }
+    generate_csd_multipliers_with(coeffs, module_name, &MultiplierOptions::new())
+}
 
-    writeln!(verilog, "endmodule").unwrap();
-    Ok(verilog)
+/// Generate a multi-coefficient module with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`].
+pub fn generate_csd_multipliers_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    let csds: Vec<&str> = coeffs.iter().map(|spec| spec.csd.as_str()).collect();
+    Ok(print_verilog(&netlist, &csds, options))
 }
 
 // ---------------------------------------------------------------------------
@@ -910,14 +2577,26 @@ mod tests {
     fn test_invalid_csd_chars() {
         let csd = "+01-00+0+";
         let result = CsdMultiplier::new(csd, 8, 6);
-        assert!(matches!(result, Err(CsdMultiplierError::InvalidCharacter)));
+        assert_eq!(
+            result.err(),
+            Some(CsdMultiplierError::InvalidCharacter {
+                character: '1',
+                position: 2
+            })
+        );
     }
 
     #[test]
     fn test_length_mismatch() {
         let csd = "+00-00+0+";
         let result = CsdMultiplier::new(csd, 8, 5);
-        assert!(matches!(result, Err(CsdMultiplierError::LengthMismatch)));
+        assert_eq!(
+            result.err(),
+            Some(CsdMultiplierError::LengthMismatch {
+                expected: 6,
+                actual: 9
+            })
+        );
     }
 
     #[test]
@@ -926,21 +2605,93 @@ mod tests {
         let n = 8;
         let m = 2;
         let multiplier = CsdMultiplier::new(csd, n, m).unwrap();
-        let expected_verilog = r###"// CSD Multiplier for pattern: +0- (value: 3)
+        let expected_verilog = r###"
 module csd_multiplier (
-    input signed [7:0] x,      // Input value (signed)
-    output signed [9:0] result // Result (signed)
+    input signed [7:0] x,      // Input value
+    output signed [9:0] result // Result of multiplication
 );
 
-    // Signed shifted versions (Verilog handles sign extension)
-    wire signed [9:0] x_shift2 = $signed({ {0{x[7]}}, x}) << 2;
-    wire signed [9:0] x_shift0 = $signed({ {2{x[7]}}, x}) << 0;
+    // Create shifted versions of input
+    wire signed [9:0] x_shift2 = x <<< 2;
+    wire signed [7:0] x_shift0 = x <<< 0;
 
-    // CSD implementation with signed arithmetic
+    // CSD implementation
     assign result = x_shift2 - x_shift0;
 endmodule
 "###;
         assert_eq!(multiplier.generate_verilog(), expected_verilog);
+        assert_eq!(
+            generate_csd_multiplier(csd, n, m).unwrap(),
+            expected_verilog
+        );
+    }
+
+    #[test]
+    fn test_verilog_classic_style() {
+        let style = VerilogStyle::new()
+            .sign_extension(SignExtension::Concatenation)
+            .comments(CommentStyle::Verbose);
+        let options = MultiplierOptions::new().verilog_style(style);
+        let expected_verilog = r###"// CSD Multiplier for pattern: +0- (value: 3)
+module csd_multiplier (
+    input signed [7:0] x,      // Input value
+    output signed [9:0] result // Result of multiplication
+);
+
+    // Create shifted versions of input
+    wire signed [9:0] x_shift2 = $signed({ {2{x[7]}}, x}) << 2;
+    wire signed [7:0] x_shift0 = x << 0;
+
+    // CSD implementation
+    assign result = x_shift2 - x_shift0;
+endmodule
+"###;
+        let multiplier = CsdMultiplier::new("+0-", 8, 2).unwrap();
+        assert_eq!(
+            multiplier.generate_verilog_with(&options).unwrap(),
+            expected_verilog
+        );
+        assert_eq!(
+            generate_csd_multiplier_with("+0-", 8, 2, &options).unwrap(),
+            expected_verilog
+        );
+    }
+
+    #[test]
+    fn test_verilog_without_comments() {
+        let style = VerilogStyle::new().comments(CommentStyle::None);
+        let options = MultiplierOptions::new().verilog_style(style);
+        let expected_verilog = r###"
+module csd_multiplier (
+    input signed [7:0] x,
+    output signed [14:0] result
+);
+
+    wire signed [14:0] x_shift7 = x <<< 7;
+    wire signed [12:0] x_shift5 = x <<< 5;
+    wire signed [10:0] x_shift3 = x <<< 3;
+    wire signed [8:0] x_shift1 = x <<< 1;
+    wire signed [14:0] _pat = x_shift7 - x_shift5;
+
+    assign result = _pat + (_pat >>> 4);
+endmodule
+"###;
+        assert_eq!(
+            generate_csd_multiplier_with("+0-0+0-0", 8, 7, &options).unwrap(),
+            expected_verilog
+        );
+    }
+
+    #[test]
+    fn test_verbose_multi_header() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 3, 8),
+            MultiplierSpec::from_f64("y1", -0.75, 2, 8).unwrap(),
+        ];
+        let style = VerilogStyle::new().comments(CommentStyle::Verbose);
+        let options = MultiplierOptions::new().verilog_style(style);
+        let v = generate_csd_multipliers_with(&coeffs, "taps", &options).unwrap();
+        assert!(v.starts_with("// y0: +0- (value: 3)\n// y1: -.0+ (value: -0.75)\nmodule taps ("));
     }
 
     // ---- Free-function tests (matching C++ test_csd_multiplier.cpp) ----
@@ -985,13 +2736,25 @@ endmodule
     #[test]
     fn test_fn_invalid_chars() {
         let r = generate_csd_multiplier("123", 8, 2);
-        assert_eq!(r, Err(CsdMultiplierError::InvalidCharacter));
+        assert_eq!(
+            r,
+            Err(CsdMultiplierError::InvalidCharacter {
+                character: '1',
+                position: 0
+            })
+        );
     }
 
     #[test]
     fn test_fn_invalid_length() {
         let r = generate_csd_multiplier("+0-", 8, 3);
-        assert_eq!(r, Err(CsdMultiplierError::LengthMismatch));
+        assert_eq!(
+            r,
+            Err(CsdMultiplierError::LengthMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
     }
 
     // LCSRe optimization tests
@@ -1141,8 +2904,363 @@ endmodule
             },
         ];
         let v = generate_csd_multipliers(&coeffs, "csd_filter").unwrap();
-        assert!(v.contains("Cross-CSE"));
-        assert!(v.contains("_cse_0"));
+        assert!(!v.contains("_cse_0"));
+        assert!(v.contains("    // Shared product of y0 (reused by y1)\n"));
+        assert!(v.contains("    // y1: +00-00+0+ (= y0)\n    assign y1 = _y0_prod;\n"));
+    }
+
+    fn folded_specs() -> Vec<MultiplierSpec> {
+        [
+            ("y0", 57),
+            ("y1", -57),
+            ("y2", 228),
+            ("y3", -114),
+            ("y4", 19),
+        ]
+        .iter()
+        .map(|&(name, value)| MultiplierSpec::from_integer(name, value, 8))
+        .collect()
+    }
+
+    #[test]
+    fn test_fold_coefficients() {
+        let (aligned, _) = align_coefficients(&folded_specs());
+        let folds = fold_coefficients(&aligned);
+        let fold = |source, negate, shift| {
+            Some(Fold {
+                source,
+                negate,
+                shift,
+            })
+        };
+        assert_eq!(
+            folds,
+            vec![
+                None,
+                fold(0, true, 0),
+                fold(0, false, 2),
+                fold(0, true, 1),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coefficient_tag() {
+        assert_eq!(coefficient_tag("+00-00+0+"), "229");
+        assert_eq!(coefficient_tag("-0+0-"), "m13");
+        assert_eq!(coefficient_tag("+00-.0+"), "7p25");
+        assert_eq!(coefficient_tag("0.-0+"), "m0p375");
+        assert_eq!(coefficient_tag("000"), "0");
+    }
+
+    #[test]
+    fn test_naming_single() {
+        let naming = Naming::new()
+            .module_name("mul")
+            .input_name("a")
+            .output_name("p")
+            .wire_prefix("u1");
+        let options = MultiplierOptions::new()
+            .naming(naming.clone())
+            .adder_structure(AdderStructure::Tree)
+            .output_frac_bits(0)
+            .rounding(OutputRounding::Nearest);
+        let v = generate_csd_multiplier_with("+0-0+0-0.+", 8, 7, &options).unwrap();
+        assert!(v.contains("module mul (\n    input signed [7:0] a,"));
+        assert!(v.contains("wire signed [15:0] u1_a_shift8 = a <<< 8;"));
+        assert!(v.contains("wire signed [1:0] u1_round = 2'sd1;"));
+        assert!(v.contains("u1_pat = u1_a_shift8 - u1_a_shift6;"));
+        assert!(v.contains("wire signed [15:0] u1_p_full = "));
+        assert!(v.contains("assign p = (u1_p_full >>> 1);"));
+        assert!(!v.contains("x_shift") && !v.contains("result"));
+
+        // The struct-based generator follows the same names
+        let v = CsdMultiplier::new("+0-0+0-0", 8, 7)
+            .unwrap()
+            .with_naming(naming)
+            .generate_verilog();
+        assert!(v.contains("module mul (\n    input signed [7:0] a,"));
+        assert!(v.contains("wire signed [14:0] u1_a_shift7 = a <<< 7;"));
+        assert!(v.contains("wire signed [14:0] u1_pat = u1_a_shift7 - u1_a_shift5;"));
+        assert!(v.contains("assign p = u1_pat + (u1_pat >>> 4);"));
+    }
+
+    #[test]
+    fn test_naming_multi() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -199, 8),
+            MultiplierSpec::from_integer("y2", -229, 8),
+        ];
+        let naming = Naming::new()
+            .input_name("s")
+            .output_name("ignored")
+            .wire_prefix("b0")
+            .coefficient_suffix(true);
+        let options = MultiplierOptions::new().naming(naming);
+        let v = generate_csd_multipliers_with(&coeffs, "taps", &options).unwrap();
+        assert!(v.contains("module taps_229_m199_m229 (\n    input signed [7:0] s,"));
+        assert!(v.contains(
+            "    wire signed [15:0] b0_cse_0 = b0_s_shift8 - b0_s_shift5 + b0_s_shift2;"
+        ));
+        assert!(v.contains("    wire signed [15:0] b0_y0_prod = "));
+        assert!(v.contains("    assign y2 = -b0_y0_prod;"));
+        assert!(!v.contains("ignored"));
+
+        let renamed = generate_csd_multipliers_with(
+            &coeffs,
+            "taps",
+            &MultiplierOptions::new().naming(Naming::new().module_name("bank")),
+        )
+        .unwrap();
+        assert!(renamed.contains("module bank ("));
+    }
+
+    #[test]
+    fn test_shared_product_name_does_not_collide() {
+        // y1 reuses y0's product, which must not take the name of output y0_prod
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -229, 8),
+            MultiplierSpec::from_integer("y0_prod", 3, 8),
+        ];
+        let netlist = csd_multipliers_netlist(&coeffs, "taps", &MultiplierOptions::new()).unwrap();
+        let mut names: Vec<&str> = netlist.wires.iter().map(|w| w.name.as_str()).collect();
+        names.extend(netlist.outputs.iter().map(|o| o.port.name.as_str()));
+        assert!(names.contains(&"_y0_prod"));
+        let count = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), count, "{:?}", names);
+        let v = emit_verilog(&netlist);
+        assert!(v.contains("    assign y1 = -_y0_prod;\n"));
+        assert!(v.contains("    assign y0_prod = "));
+        assert_eq!(
+            simulator::simulate(&netlist, &[-5]),
+            vec![-5 * 229, 5 * 229, -15]
+        );
+    }
+
+    #[test]
+    fn test_naming_every_backend() {
+        use super::amaranth::*;
+        use super::chisel::*;
+        use super::graph::*;
+        use super::hls::*;
+        use super::model::*;
+        use super::systemverilog::*;
+        use super::vhdl::*;
+
+        type Single =
+            fn(&str, usize, usize, &MultiplierOptions) -> Result<String, CsdMultiplierError>;
+        type Multi =
+            fn(&[MultiplierSpec], &str, &MultiplierOptions) -> Result<String, CsdMultiplierError>;
+        fn sv_multi(
+            coeffs: &[MultiplierSpec],
+            module_name: &str,
+            options: &MultiplierOptions,
+        ) -> Result<String, CsdMultiplierError> {
+            generate_csd_multipliers_sv_with(
+                coeffs,
+                module_name,
+                options,
+                &SystemVerilogOptions::new(),
+            )
+        }
+
+        // Backend, its generators, and the renamed input, output and wires it must print
+        let backends: [(&str, Single, Multi, &[&str]); 10] = [
+            (
+                "verilog",
+                generate_csd_multiplier_with,
+                generate_csd_multipliers_with,
+                &[
+                    "input signed [7:0] din,",
+                    "output signed [13:0] dout",
+                    "wire signed [13:0] u0_din_shift6 = din <<< 6;",
+                    "assign dout = u0_pat + (u0_pat >>> 4);",
+                ],
+            ),
+            (
+                "vhdl",
+                generate_csd_multiplier_vhdl_with,
+                generate_csd_multipliers_vhdl_with,
+                &[
+                    "din : in  signed(7 downto 0);",
+                    "dout : out signed(13 downto 0)",
+                    "u0_din_shift6 <= shift_left(resize(din, 14), 6);",
+                    "dout <= u0_pat + shift_right(u0_pat, 4);",
+                ],
+            ),
+            (
+                "systemverilog",
+                generate_csd_multiplier_sv_with,
+                sv_multi,
+                &[
+                    "input  logic signed [W_IN-1:0] din",
+                    "output logic signed [W_IN+5:0] dout",
+                    "u0_din_shift6 = din <<< 6;",
+                    "dout = u0_pat + (u0_pat >>> 4);",
+                ],
+            ),
+            (
+                "chisel",
+                generate_csd_multiplier_chisel_with,
+                generate_csd_multipliers_chisel_with,
+                &[
+                    "val din = Input(SInt(8.W))",
+                    "val dout = Output(SInt(14.W))",
+                    "u0_din_shift6 := (io.din << 6)",
+                    "io.dout := u0_pat +% (u0_pat >> 4).pad(14)",
+                ],
+            ),
+            (
+                "amaranth",
+                generate_csd_multiplier_amaranth_with,
+                generate_csd_multipliers_amaranth_with,
+                &[
+                    "self.din = Signal(signed(8))",
+                    "self.dout = Signal(signed(14))",
+                    "m.d.comb += u0_din_shift6.eq(self.din << 6)",
+                    "m.d.comb += self.dout.eq(u0_pat + (u0_pat >> 4))",
+                ],
+            ),
+            (
+                "hls",
+                generate_csd_multiplier_hls_with,
+                generate_csd_multipliers_hls_with,
+                &[
+                    "ap_int<14> csd_multiplier(ap_int<8> din)",
+                    "ap_int<14> u0_din_shift6 = ap_int<14>(din) << 6;",
+                    "return u0_pat + (u0_pat >> 4);",
+                ],
+            ),
+            (
+                "c",
+                generate_csd_multiplier_c_with,
+                generate_csd_multipliers_c_with,
+                &[
+                    "int64_t csd_multiplier(int64_t din)",
+                    "int64_t u0_din_shift6 = csd_wrap(((uint64_t)din << 6), 14);",
+                    "return csd_wrap((uint64_t)u0_pat + (uint64_t)(u0_pat >> 4), 14);",
+                ],
+            ),
+            (
+                "rust",
+                generate_csd_multiplier_rust_with,
+                generate_csd_multipliers_rust_with,
+                &[
+                    "pub fn csd_multiplier(din: i64) -> i64 {",
+                    "let u0_din_shift6 = wrap(din << 6, 14);",
+                    "wrap(u0_pat.wrapping_add(u0_pat >> 4), 14)",
+                ],
+            ),
+            (
+                "dot",
+                generate_csd_multiplier_dot_with,
+                generate_csd_multipliers_dot_with,
+                &[
+                    "i0 [label=\"din\\n8 bits\"",
+                    "w4 [label=\"u0_pat\\n= 48·din\"",
+                    "o0 [label=\"dout\\n= 51·din\"",
+                ],
+            ),
+            (
+                "mermaid",
+                generate_csd_multiplier_mermaid_with,
+                generate_csd_multipliers_mermaid_with,
+                &[
+                    "i0[/\"din<br/>8 bits\"/]",
+                    "w4[\"u0_pat<br/>= 48·din\"]",
+                    "o0[[\"dout<br/>= 51·din\"]]",
+                ],
+            ),
+        ];
+
+        let naming = Naming::new()
+            .input_name("din")
+            .output_name("dout")
+            .wire_prefix("u0");
+        let options = MultiplierOptions::new().naming(naming);
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 51, 8),
+            MultiplierSpec::from_integer("y1", -51, 8),
+        ];
+        // Default names that renaming must replace; "u0_pat" contains "_pat"
+        let defaults = |out: &str| -> Vec<&str> {
+            let out = out.replace("u0_pat", "");
+            ["x_shift", "result", "_pat"]
+                .into_iter()
+                .filter(|name| out.contains(name))
+                .collect()
+        };
+        for (backend, single, multi, expected) in backends {
+            let default = single("+0-0+0-", 8, 6, &MultiplierOptions::new()).unwrap();
+            assert!(default.contains("_pat"), "{}:\n{}", backend, default);
+
+            let out = single("+0-0+0-", 8, 6, &options).unwrap();
+            for name in expected {
+                assert!(
+                    out.contains(name),
+                    "{}: {} missing:\n{}",
+                    backend,
+                    name,
+                    out
+                );
+            }
+            assert!(
+                defaults(&out).is_empty(),
+                "{}: {:?} left:\n{}",
+                backend,
+                defaults(&out),
+                out
+            );
+
+            let out = multi(&coeffs, "taps", &options).unwrap();
+            assert!(
+                out.contains("din") && out.contains("u0_"),
+                "{}:\n{}",
+                backend,
+                out
+            );
+            assert!(
+                defaults(&out).is_empty(),
+                "{}: {:?} left:\n{}",
+                backend,
+                defaults(&out),
+                out
+            );
+        }
+    }
+
+    #[test]
+    fn test_multi_negated_and_shifted_coeffs() {
+        let coeffs = folded_specs();
+        let netlist = csd_multipliers_netlist(&coeffs, "taps", &MultiplierOptions::new()).unwrap();
+        let v = emit_verilog(&netlist);
+        assert!(v.contains("    // Shared product of y0 (reused by y1, y2, y3)\n"));
+        assert!(v.contains("    assign y0 = _y0_prod;\n"));
+        assert!(v.contains("    // y1: -00+00- (= -y0)\n    assign y1 = -_y0_prod;\n"));
+        assert!(v.contains("    // y2: +00-00+00 (= y0 << 2)\n    assign y2 = (_y0_prod <<< 2);\n"));
+        assert!(
+            v.contains("    // y3: -00+00-0 (= -y0 << 1)\n    assign y3 = -(_y0_prod <<< 1);\n")
+        );
+        // Only the shifts of 57 and 19 are needed
+        assert!(!v.contains("x_shift8"));
+        // 57 costs two adders; the copies are free
+        let unfolded = csd_multipliers_netlist(
+            &coeffs,
+            "taps",
+            &MultiplierOptions::new().share_patterns(false),
+        )
+        .unwrap();
+        assert!(netlist.adder_count() < unfolded.adder_count());
+        let (values, _) = aligned_values(&coeffs);
+        assert_eq!(
+            simulator::simulate(&netlist, &[-128]),
+            values.iter().map(|c| c * -128).collect::<Vec<_>>()
+        );
     }
 
     #[test]
@@ -1162,7 +3280,15 @@ endmodule
             },
         ];
         let r = generate_csd_multipliers(&coeffs, "test");
-        assert_eq!(r, Err(CsdMultiplierError::WidthMismatch));
+        assert_eq!(
+            r,
+            Err(CsdMultiplierError::WidthMismatch {
+                index: 1,
+                name: "y1".to_string(),
+                expected: 8,
+                actual: 16
+            })
+        );
     }
 
     #[test]
@@ -1174,6 +3300,579 @@ endmodule
             max_power: 2,
         }];
         let r = generate_csd_multipliers(&coeffs, "test");
-        assert_eq!(r, Err(CsdMultiplierError::InvalidCharacter));
+        assert_eq!(
+            r,
+            Err(CsdMultiplierError::InvalidSpec {
+                index: 0,
+                name: "y0".to_string(),
+                error: Box::new(CsdMultiplierError::InvalidCharacter {
+                    character: '1',
+                    position: 0
+                })
+            })
+        );
+    }
+
+    #[test]
+    fn test_multi_duplicate_output() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 3, 8),
+            MultiplierSpec::from_integer("y1", 5, 8),
+            MultiplierSpec::from_integer("y0", 7, 8),
+        ];
+        let r = generate_csd_multipliers(&coeffs, "test");
+        assert_eq!(
+            r,
+            Err(CsdMultiplierError::DuplicateOutput {
+                name: "y0".to_string(),
+                first: 0,
+                second: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        use std::error::Error;
+
+        let e = CsdMultiplier::new("+0x", 8, 2).err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "Invalid character 'x' at position 2 in CSD string"
+        );
+        let e = generate_csd_multiplier("+0-", 8, 3).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "CSD string has 3 integer digits, expected 4 (max_power + 1)"
+        );
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 3, 8),
+            MultiplierSpec {
+                name: "y1".to_string(),
+                csd: "+0-".to_string(),
+                input_width: 8,
+                max_power: 1,
+            },
+        ];
+        let e = generate_csd_multipliers(&coeffs, "test").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Coefficient 1 (y1): CSD string has 3 integer digits, expected 2 (max_power + 1)"
+        );
+        assert!(e.source().is_some());
+
+        let e = CsdMultiplierError::RaggedMatrix {
+            row: 2,
+            expected: 4,
+            actual: 3,
+        };
+        assert_eq!(e.to_string(), "Matrix row 2 has 3 columns, expected 4");
+
+        let e: CsdMultiplierError = CsdError::EmptyString.into();
+        assert_eq!(e, CsdMultiplierError::Csd(CsdError::EmptyString));
+        assert_eq!(e.to_string(), "Empty string provided");
+    }
+
+    #[test]
+    fn test_validation_modes() {
+        assert_eq!(
+            CsdMultiplier::new_with_validation("++", 8, 1, Validation::Strict).err(),
+            Some(CsdMultiplierError::NotCanonical { position: 1 })
+        );
+        assert_eq!(
+            CsdMultiplier::new_with_validation("+0-.+", 8, 2, Validation::Strict).err(),
+            Some(CsdMultiplierError::NotCanonical { position: 4 })
+        );
+        assert_eq!(
+            CsdMultiplier::new("+0-", 0, 2).err(),
+            Some(CsdMultiplierError::ZeroInputWidth)
+        );
+        assert_eq!(
+            CsdMultiplier::new_with_validation("+0-", 0, 2, Validation::Strict).err(),
+            Some(CsdMultiplierError::ZeroInputWidth)
+        );
+        let m = CsdMultiplier::new("++", 8, 1).unwrap();
+        assert_eq!((m.csd(), m.max_power()), ("++", 1));
+        // to_csd() leaves adjacent non-zeros where it truncates
+        assert!(CsdMultiplier::new(&crate::to_csd(0.875, 3), 8, 0).is_ok());
+
+        let options = MultiplierOptions::new().validation(Validation::Canonicalize);
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 3, 8),
+            MultiplierSpec {
+                name: "y1".to_string(),
+                csd: "0++".to_string(),
+                input_width: 8,
+                max_power: 2,
+            },
+        ];
+        let netlist = csd_multipliers_netlist(&coeffs, "taps", &options).unwrap();
+        // 0++ = 3 is folded onto y0 once canonical
+        assert_eq!(netlist.adder_count(), 1);
+        let strict = MultiplierOptions::new().validation(Validation::Strict);
+        assert_eq!(
+            csd_multipliers_netlist(&coeffs, "taps", &strict).err(),
+            Some(CsdMultiplierError::NotCanonical { position: 2 }.in_spec(1, "y1"))
+        );
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        assert_eq!(canonicalize("++", 1).unwrap(), ("+0-".to_string(), 2));
+        assert_eq!(canonicalize("0++", 2).unwrap(), ("+0-".to_string(), 2));
+        assert_eq!(canonicalize("00+", 2).unwrap(), ("00+".to_string(), 2));
+        assert_eq!(canonicalize("--", 1).unwrap(), ("-0+".to_string(), 2));
+        assert_eq!(canonicalize("+0-.+", 2).unwrap(), ("+00.-".to_string(), 2));
+        assert_eq!(canonicalize("0.++", 0).unwrap(), ("+.0-".to_string(), 0));
+        assert_eq!(canonicalize("000", 2).unwrap(), ("000".to_string(), 2));
+    }
+
+    #[test]
+    fn test_wide_products_do_not_overflow() {
+        let v = generate_csd_multiplier("+00-00+0+", 200, 8).unwrap();
+        assert!(v.contains("input signed [199:0] x"));
+        assert!(v.contains("output signed [207:0] result"));
+        let m = CsdMultiplier::new("+00-00+0+", 200, 8).unwrap();
+        assert!(m
+            .generate_verilog()
+            .contains("output signed [207:0] result"));
+
+        let long = "+0-0".repeat(40);
+        let v = generate_csd_multiplier(&long, 8, long.len() - 1).unwrap();
+        assert!(v.contains(&format!("output signed [{}:0] result", 8 + long.len() - 2)));
+        assert_eq!(
+            check_coefficient(&long, 8, long.len() - 1, Validation::Canonicalize).map(|_| ()),
+            Err(CsdMultiplierError::CoefficientOutOfRange)
+        );
+
+        let q = quantize_coefficient(0.7, 60).unwrap();
+        assert!(generate_csd_multiplier(&q.csd, 70, q.max_power).is_ok());
+
+        let specs = [MultiplierSpec {
+            name: "y".to_string(),
+            csd: "+00-00+0+".to_string(),
+            input_width: 200,
+            max_power: 8,
+        }];
+        assert_eq!(
+            bank::coefficient_bank(&specs, "bank", &MultiplierOptions::new()).unwrap_err(),
+            CsdMultiplierError::ProductTooWide {
+                width: 209,
+                max: 125
+            }
+        );
+    }
+
+    #[test]
+    fn test_multi_ports_comma_separated() {
+        let coeffs = vec![
+            MultiplierSpec {
+                name: "y0".to_string(),
+                csd: "+0-".to_string(),
+                input_width: 8,
+                max_power: 2,
+            },
+            MultiplierSpec {
+                name: "y1".to_string(),
+                csd: "+0+".to_string(),
+                input_width: 8,
+                max_power: 2,
+            },
+        ];
+        let v = generate_csd_multipliers(&coeffs, "test").unwrap();
+        assert!(v.contains("output signed [9:0] y0,\n    output signed [10:0] y1\n);"));
+    }
+
+    // ---- Adder structure tests ----
+
+    #[test]
+    fn test_tree_flat_golden() {
+        let options = MultiplierOptions::new().adder_structure(AdderStructure::Tree);
+        let v = generate_csd_multiplier_with("+00-00+0+", 8, 8, &options).unwrap();
+        let expected = r###"
+module csd_multiplier (
+    input signed [7:0] x,      // Input value
+    output signed [15:0] result // Result of multiplication
+);
+
+    // Create shifted versions of input
+    wire signed [15:0] x_shift8 = x <<< 8;
+    wire signed [12:0] x_shift5 = x <<< 5;
+    wire signed [9:0] x_shift2 = x <<< 2;
+    wire signed [7:0] x_shift0 = x <<< 0;
+
+    // Balanced adder tree
+    wire signed [15:0] _t0 = x_shift8 - x_shift5;
+    wire signed [10:0] _t1 = x_shift2 + x_shift0;
+
+    // CSD implementation
+    assign result = _t0 + _t1;
+endmodule
+"###;
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn test_tree_factors_out_negation() {
+        let options = MultiplierOptions::new().adder_structure(AdderStructure::Tree);
+        let v = generate_csd_multiplier_with("-0-0+0-", 8, 6, &options).unwrap();
+        assert!(v.contains("_t0 = x_shift6 + x_shift4;"));
+        assert!(v.contains("_t1 = x_shift2 - x_shift0;"));
+        assert!(v.contains("assign result = _t1 - _t0;"));
+    }
+
+    #[test]
+    fn test_tree_odd_term_count() {
+        let options = MultiplierOptions::new()
+            .adder_structure(AdderStructure::Tree)
+            .share_patterns(false);
+        let netlist = csd_multiplier_netlist("+0+0+0+0+", 8, 8, &options).unwrap();
+        assert_eq!(netlist.adder_count(), 4);
+        assert_eq!(netlist.adder_depth(), 3);
+        let chain = csd_multiplier_netlist("+0+0+0+0+", 8, 8, &MultiplierOptions::new()).unwrap();
+        // LCSRe shares "+0+0" but chains through the pattern wire
+        assert_eq!(chain.adder_count(), 3);
+        assert_eq!(chain.adder_depth(), 3);
+    }
+
+    #[test]
+    fn test_tree_small_sums_unchanged() {
+        let options = MultiplierOptions::new().adder_structure(AdderStructure::Tree);
+        for (csd, m) in [("+0-", 2), ("+", 0), ("000", 2)] {
+            assert_eq!(
+                generate_csd_multiplier_with(csd, 8, m, &options).unwrap(),
+                generate_csd_multiplier(csd, 8, m).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_share_patterns_disabled() {
+        let options = MultiplierOptions::new().share_patterns(false);
+        let v = generate_csd_multiplier_with("+0-0+0-0", 8, 7, &options).unwrap();
+        assert!(!v.contains("_pat"));
+        assert!(v.contains("assign result = x_shift7 - x_shift5 + x_shift3 - x_shift1;"));
+    }
+
+    #[test]
+    fn test_adder_cost_tradeoff() {
+        let costs = adder_cost_tradeoff("+0-0+0-0+0-0", 8, 11).unwrap();
+        let pairs: Vec<(usize, usize)> = costs.iter().map(|c| (c.adders, c.depth)).collect();
+        // Chain/shared, chain/flat, tree/shared, tree/flat
+        assert_eq!(pairs, vec![(3, 3), (5, 5), (3, 3), (5, 3)]);
+        assert_eq!(
+            adder_cost_tradeoff("+0-", 8, 3),
+            Err(CsdMultiplierError::LengthMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_multi_tree() {
+        let coeffs = vec![
+            MultiplierSpec {
+                name: "y0".to_string(),
+                csd: "+0+0-0+0-".to_string(),
+                input_width: 8,
+                max_power: 8,
+            },
+            MultiplierSpec {
+                name: "y1".to_string(),
+                csd: "-0+0+0-0+".to_string(),
+                input_width: 8,
+                max_power: 8,
+            },
+        ];
+        let options = MultiplierOptions::new()
+            .adder_structure(AdderStructure::Tree)
+            .share_patterns(false);
+        let netlist = csd_multipliers_netlist(&coeffs, "fir", &options).unwrap();
+        assert_eq!(netlist.adder_count(), 8);
+        assert_eq!(netlist.adder_depth(), 3);
+        let v = generate_csd_multipliers_with(&coeffs, "fir", &options).unwrap();
+        assert!(v.contains("_t5"));
+        assert!(!v.contains("_t6"));
+    }
+
+    #[test]
+    fn test_fractional_coefficient() {
+        // to_csd(28.5, 2): 28.5 * 2^2 = 114
+        let csd = crate::csd::to_csd(28.5, 2);
+        assert_eq!(csd, "+00-00.+0");
+        let netlist = csd_multiplier_netlist(&csd, 8, 5, &MultiplierOptions::new()).unwrap();
+        let port = &netlist.outputs[0].port;
+        assert_eq!((port.width, port.frac_bits), (15, 2));
+        let v = emit_verilog(&netlist);
+        assert!(v.contains(
+            "output signed [14:0] result // Result of multiplication (2 fractional bits)"
+        ));
+        assert!(v.contains("assign result = x_shift7 - x_shift4 + x_shift1;"));
+    }
+
+    #[test]
+    fn test_fractional_validation() {
+        assert!(generate_csd_multiplier("+00-.0+", 8, 3).is_ok());
+        assert!(generate_csd_multiplier("+00-.", 8, 3).is_ok());
+        assert_eq!(
+            generate_csd_multiplier("+00-.0+", 8, 6),
+            Err(CsdMultiplierError::LengthMismatch {
+                expected: 7,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            generate_csd_multiplier("+00-.0.+", 8, 3),
+            Err(CsdMultiplierError::InvalidCharacter {
+                character: '.',
+                position: 6
+            })
+        );
+        assert_eq!(
+            CsdMultiplier::new("+0.-.0", 8, 1).err(),
+            Some(CsdMultiplierError::InvalidCharacter {
+                character: '.',
+                position: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_fractional_struct() {
+        let multiplier = CsdMultiplier::new("+00-.0+", 6, 3).unwrap();
+        assert_eq!(multiplier.decimal_value(), 29);
+        let v = multiplier.generate_verilog();
+        assert_eq!(v, generate_csd_multiplier("+00-.0+", 6, 3).unwrap());
+        assert!(v.contains(
+            "output signed [10:0] result // Result of multiplication (2 fractional bits)"
+        ));
+        assert!(v.contains("assign result = x_shift5 - x_shift2 + x_shift0;"));
+    }
+
+    #[test]
+    fn test_truncated_output() {
+        let options = MultiplierOptions::new().output_frac_bits(0);
+        let v = generate_csd_multiplier_with("+00-00.+0", 8, 5, &options).unwrap();
+        assert!(v.contains("output signed [12:0] result // Result of multiplication\n"));
+        assert!(v.contains("wire signed [14:0] result_full = x_shift7 - x_shift4 + x_shift1;"));
+        assert!(v.contains(
+            "    // Truncate to 0 fractional bits\n    assign result = (result_full >>> 2);"
+        ));
+        assert!(!v.contains("_round"));
+    }
+
+    #[test]
+    fn test_rounded_output() {
+        let options = MultiplierOptions::new()
+            .output_frac_bits(1)
+            .rounding(OutputRounding::Nearest);
+        let v = generate_csd_multiplier_with("+00-.0-0+", 6, 3, &options).unwrap();
+        assert!(v.contains("wire signed [3:0] _round = 4'sd4;"));
+        assert!(v.contains("result_full = x_shift7 - x_shift4 - x_shift2 + x_shift0 + _round;"));
+        assert!(v.contains(
+            "    // Round to nearest, 1 fractional bit\n    assign result = (result_full >>> 3);"
+        ));
+        assert!(v.contains("output signed [9:0] result"));
+    }
+
+    #[test]
+    fn test_rounding_constant_limit() {
+        // Dropping 130 fractional bits needs the constant 2^129
+        let csd = format!("+.{}+", "0".repeat(129));
+        let options = MultiplierOptions::new()
+            .output_frac_bits(0)
+            .rounding(OutputRounding::Nearest);
+        let too_wide = CsdMultiplierError::ProductTooWide {
+            width: 131,
+            max: 125,
+        };
+        assert_eq!(
+            csd_multiplier_netlist(&csd, 8, 0, &options).unwrap_err(),
+            too_wide
+        );
+        let spec = MultiplierSpec {
+            name: "y0".to_string(),
+            csd: csd.clone(),
+            input_width: 8,
+            max_power: 0,
+        };
+        assert_eq!(
+            csd_multipliers_netlist(&[spec], "taps", &options).unwrap_err(),
+            too_wide
+        );
+        let multiplier = CsdMultiplier::new(&csd, 8, 0).unwrap();
+        assert_eq!(multiplier.netlist_with(&options).unwrap_err(), too_wide);
+        assert_eq!(
+            multiplier.generate_verilog_with(&options).unwrap_err(),
+            too_wide
+        );
+
+        // Truncation needs no constant, and 124 dropped bits still round
+        let truncate = MultiplierOptions::new().output_frac_bits(0);
+        assert!(csd_multiplier_netlist(&csd, 8, 0, &truncate).is_ok());
+        let v = generate_csd_multiplier_with(&csd, 8, 0, &options.output_frac_bits(6)).unwrap();
+        assert!(v.contains("_round = 125'sd"));
+    }
+
+    #[test]
+    fn test_extended_output() {
+        let options = MultiplierOptions::new().output_frac_bits(3);
+        let netlist = csd_multiplier_netlist("+0-.+", 8, 2, &options).unwrap();
+        assert_eq!(netlist.outputs[0].port.width, 13);
+        assert_eq!(netlist.outputs[0].port.frac_bits, 3);
+        let v = emit_verilog(&netlist);
+        assert!(
+            v.contains("assign result = (x_shift3 <<< 2) - (x_shift1 <<< 2) + (x_shift0 <<< 2);")
+        );
+    }
+
+    #[test]
+    fn test_multi_fractional() {
+        let spec = |name: &str, csd: &str| MultiplierSpec {
+            name: name.to_string(),
+            csd: csd.to_string(),
+            input_width: 8,
+            max_power: 2,
+        };
+        let options = MultiplierOptions::new().output_frac_bits(0);
+        let netlist = csd_multipliers_netlist(
+            &[spec("y0", "+0-.0+"), spec("y1", "-0+.0-")],
+            "taps",
+            &options,
+        )
+        .unwrap();
+        let names: Vec<&str> = netlist.wires.iter().map(|w| w.name.as_str()).collect();
+        assert!(names.contains(&"y0_full") && names.contains(&"y1_full"));
+        assert_eq!(
+            netlist.outputs[1].comment.as_deref(),
+            Some("Truncate to 0 fractional bits")
+        );
+    }
+
+    #[test]
+    fn test_multi_heterogeneous_widths() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", 3, 8),
+            MultiplierSpec {
+                name: "y2".to_string(),
+                csd: "+0-.+".to_string(),
+                input_width: 8,
+                max_power: 2,
+            },
+        ];
+        let netlist = csd_multipliers_netlist(&coeffs, "taps", &MultiplierOptions::new()).unwrap();
+        let widths: Vec<(usize, usize)> = netlist
+            .outputs
+            .iter()
+            .map(|o| (o.port.width, o.port.frac_bits))
+            .collect();
+        assert_eq!(widths, vec![(17, 1), (11, 1), (11, 1)]);
+        let v = emit_verilog(&netlist);
+        // "+0-" and "+0-.+" are aligned, so the shared pattern covers both
+        assert!(v.contains("wire signed [16:0] x_shift9 = x <<< 9;"));
+        assert!(v.contains("wire signed [10:0] _cse_0 = x_shift3 - x_shift1;"));
+        assert!(v.contains("assign y2 = _cse_0 + x_shift0;"));
+        assert!(v.contains("output signed [10:0] y2"));
+        assert!(v.contains("// y1: +0-\n"));
+    }
+
+    #[test]
+    fn test_quantize_coefficient() {
+        let q = quantize_coefficient(-0.75, 2).unwrap();
+        assert_eq!((q.csd.as_str(), q.max_power, q.value), ("-.0+", 0, -0.75));
+        let q = quantize_coefficient(0.0, 3).unwrap();
+        assert_eq!(q.csd, "0.000");
+        let q = quantize_coefficient(5.0, 0).unwrap();
+        assert_eq!((q.csd.as_str(), q.max_power, q.error), ("+0+", 2, 0.0));
+        assert_eq!(
+            quantize_coefficient(f64::INFINITY, 2),
+            Err(CsdMultiplierError::CoefficientOutOfRange)
+        );
+        assert_eq!(
+            quantize_coefficient(1e18, 8),
+            Err(CsdMultiplierError::CoefficientOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_quantized_coefficients_generate() {
+        for value in [0.3, -0.4, 28.4, 1.0, -1.0, 0.001] {
+            let q = quantize_coefficient(value, 5).unwrap();
+            assert!(q.error.abs() <= 1.0 / 64.0);
+            assert!(generate_csd_multiplier(&q.csd, 8, q.max_power).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_from_integer() {
+        let multiplier = CsdMultiplier::from_integer(-229, 8).unwrap();
+        assert_eq!(multiplier.csd(), "-00+00-0-");
+        assert_eq!(multiplier.max_power(), 8);
+        assert_eq!(multiplier.value(), -229.0);
+        assert_eq!(
+            multiplier.generate_verilog(),
+            generate_csd_multiplier("-00+00-0-", 8, 8).unwrap()
+        );
+
+        let spec = MultiplierSpec::from_integer("y3", 0, 8);
+        assert_eq!((spec.csd.as_str(), spec.max_power), ("0", 0));
+        assert_eq!(spec.value(), 0.0);
+
+        assert_eq!(
+            CsdMultiplier::from_integer(229, 0).err(),
+            Some(CsdMultiplierError::ZeroInputWidth)
+        );
+        assert_eq!(
+            CsdMultiplier::from_f64(0.3, 4, 0).err(),
+            Some(CsdMultiplierError::ZeroInputWidth)
+        );
+    }
+
+    #[test]
+    fn test_from_f64() {
+        let spec = MultiplierSpec::from_f64("y0", 0.3, 4, 10).unwrap();
+        assert_eq!(spec.name, "y0");
+        assert_eq!(spec.csd, "0.0+0+");
+        assert_eq!(spec.value(), 0.3125);
+        let v = generate_csd_multipliers(&[spec], "tap").unwrap();
+        assert!(v.contains("output signed [12:0] y0"));
+    }
+
+    #[test]
+    fn test_minimal_widths() {
+        let netlist = csd_multiplier_netlist("+0-", 8, 2, &MultiplierOptions::new()).unwrap();
+        assert_eq!(netlist.outputs[0].port.width, 10);
+        assert_eq!(netlist.output_ranges(), vec![(-384, 381)]);
+        let widths: Vec<usize> = netlist.wires.iter().map(|w| w.width).collect();
+        assert_eq!(widths, vec![10, 8]);
+
+        // 5 needs 3 extra bits even though max_power is 2
+        let netlist = csd_multiplier_netlist("+0+", 8, 2, &MultiplierOptions::new()).unwrap();
+        assert_eq!(netlist.outputs[0].port.width, 11);
+
+        // Leading zeros no longer widen the output
+        let netlist = csd_multiplier_netlist("000000+0-", 8, 8, &MultiplierOptions::new()).unwrap();
+        assert_eq!(netlist.outputs[0].port.width, 10);
+    }
+
+    #[test]
+    fn test_minimal_pattern_wire_width() {
+        // _pat = x * 96 and the result x * 102 both need 15 bits
+        let netlist = csd_multiplier_netlist("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+        let pat = netlist.wires.iter().find(|w| w.name == "_pat").unwrap();
+        assert_eq!(pat.width, 15);
+        assert_eq!(netlist.wire_ranges().last(), Some(&(-12288, 12192)));
+    }
+
+    #[test]
+    fn test_legacy_widths() {
+        let options = MultiplierOptions::new().legacy_widths(true);
+        let netlist = csd_multiplier_netlist("000000+0-", 8, 8, &options).unwrap();
+        assert!(netlist.wires.iter().all(|w| w.width == 16));
+        assert_eq!(netlist.outputs[0].port.width, 16);
+        let v = generate_csd_multiplier_with("+0-", 8, 2, &options).unwrap();
+        assert!(v.contains("wire signed [9:0] x_shift0 = x <<< 0;"));
     }
 }
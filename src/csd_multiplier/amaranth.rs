@@ -0,0 +1,270 @@
+//! Amaranth HDL backend.
+//!
+//! Prints a [`Netlist`] as an Amaranth `Elaboratable` (Python source) with
+//! `signed` ports as attributes and every wire driven in the `comb` domain.
+//! Amaranth arithmetic never overflows and `.eq()` wraps the result to the
+//! width of the assigned signal, which is exactly what the Verilog `assign`
+//! does, so operands are written without any width conversion and the module
+//! shares the Verilog's LCSRe, cross-CSE and folding decisions bit for bit.
+//!
+//! Internal wires such as `_pat` are renamed with a `w` prefix (`w_pat`), as
+//! in the VHDL and Chisel backends.
+
+use std::fmt::Write;
+
+use super::netlist::{Netlist, Source, Term, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, CsdMultiplierError, MultiplierOptions,
+    MultiplierSpec, TermOp,
+};
+
+/// Map a netlist signal name to a Python variable name.
+fn amaranth_identifier(name: &str) -> String {
+    if name.starts_with('_') {
+        format!("w{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Python expression for a signal: ports are attributes of `self`.
+fn amaranth_signal(netlist: &Netlist, source: Source) -> String {
+    match source {
+        Source::Input(i) => format!("self.{}", netlist.inputs[i].name),
+        Source::Wire(i) => amaranth_identifier(&netlist.wires[i].name),
+    }
+}
+
+/// Render one term's operand, without its sign.
+fn amaranth_operand(netlist: &Netlist, term: &Term) -> String {
+    let signal = amaranth_signal(netlist, term.source);
+    match term.shift {
+        0 => signal,
+        s if s > 0 => format!("({} << {})", signal, s),
+        s => format!("({} >> {})", signal, -s),
+    }
+}
+
+/// Render a signed sum of terms.
+fn amaranth_sum(netlist: &Netlist, terms: &[Term]) -> String {
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+    let mut expr = String::new();
+    for (i, term) in terms.iter().enumerate() {
+        match (i, term.op) {
+            (0, TermOp::Add) => {}
+            (0, TermOp::Sub) => expr.push('-'),
+            (_, TermOp::Add) => expr.push_str(" + "),
+            (_, TermOp::Sub) => expr.push_str(" - "),
+        }
+        expr.push_str(&amaranth_operand(netlist, term));
+    }
+    expr
+}
+
+/// Print a [`Netlist`] as an Amaranth `Elaboratable`.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::amaranth::emit_amaranth;
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+///
+/// let netlist = csd_multiplier_netlist("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+/// let py = emit_amaranth(&netlist);
+/// assert!(py.contains("class csd_multiplier(Elaboratable):"));
+/// assert!(py.contains("        w_pat = Signal(signed(15))\n"));
+/// assert!(py.contains("        m.d.comb += self.result.eq(w_pat + (w_pat >> 4))\n"));
+/// ```
+pub fn emit_amaranth(netlist: &Netlist) -> String {
+    let mut py = String::new();
+    writeln!(py, "from amaranth import *").unwrap();
+    writeln!(py).unwrap();
+    writeln!(py).unwrap();
+    writeln!(py, "class {}(Elaboratable):", netlist.module_name).unwrap();
+    writeln!(py, "    def __init__(self):").unwrap();
+    let ports = netlist
+        .inputs
+        .iter()
+        .chain(netlist.outputs.iter().map(|o| &o.port));
+    for port in ports {
+        write!(
+            py,
+            "        self.{} = Signal(signed({}))",
+            port.name, port.width
+        )
+        .unwrap();
+        match &port.comment {
+            Some(c) => writeln!(py, "  # {}", c).unwrap(),
+            None => writeln!(py).unwrap(),
+        }
+    }
+    writeln!(py).unwrap();
+    writeln!(py, "    def elaborate(self, platform):").unwrap();
+    writeln!(py, "        m = Module()").unwrap();
+
+    for wire in &netlist.wires {
+        if let Some(comment) = &wire.comment {
+            writeln!(py).unwrap();
+            writeln!(py, "        # {}", comment).unwrap();
+        }
+        let name = amaranth_identifier(&wire.name);
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                let signal = amaranth_signal(netlist, Source::Input(*input));
+                match power {
+                    0 => signal,
+                    p => format!("{} << {}", signal, p),
+                }
+            }
+            WireExpr::Sum(terms) => amaranth_sum(netlist, terms),
+            WireExpr::Constant(value) => value.to_string(),
+        };
+        writeln!(py, "        {} = Signal(signed({}))", name, wire.width).unwrap();
+        writeln!(py, "        m.d.comb += {}.eq({})", name, expr).unwrap();
+    }
+    for output in &netlist.outputs {
+        if let Some(comment) = &output.comment {
+            writeln!(py).unwrap();
+            writeln!(py, "        # {}", comment).unwrap();
+        }
+        writeln!(
+            py,
+            "        m.d.comb += self.{}.eq({})",
+            output.port.name,
+            amaranth_sum(netlist, &output.terms)
+        )
+        .unwrap();
+    }
+    writeln!(py, "        return m").unwrap();
+    py
+}
+
+/// Generate an Amaranth CSD multiplier, the counterpart of
+/// [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::amaranth::generate_csd_multiplier_amaranth;
+///
+/// let py = generate_csd_multiplier_amaranth("+0-", 8, 2).unwrap();
+/// assert!(py.contains("        m.d.comb += self.result.eq(x_shift2 - x_shift0)\n"));
+/// ```
+pub fn generate_csd_multiplier_amaranth(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_amaranth_with(
+        csd_str,
+        input_width,
+        max_power,
+        &MultiplierOptions::new(),
+    )
+}
+
+/// Same as [`generate_csd_multiplier_amaranth()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_amaranth_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_amaranth(&netlist))
+}
+
+/// Generate an Amaranth multi-coefficient block with cross-CSE, the counterpart of
+/// [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_amaranth(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_amaranth_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_amaranth()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_amaranth_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_amaranth(&netlist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::OutputRounding;
+
+    #[test]
+    fn test_amaranth_golden() {
+        let py = generate_csd_multiplier_amaranth("+0-", 8, 2).unwrap();
+        let expected = r###"from amaranth import *
+
+
+class csd_multiplier(Elaboratable):
+    def __init__(self):
+        self.x = Signal(signed(8))  # Input value
+        self.result = Signal(signed(10))  # Result of multiplication
+
+    def elaborate(self, platform):
+        m = Module()
+
+        # Create shifted versions of input
+        x_shift2 = Signal(signed(10))
+        m.d.comb += x_shift2.eq(self.x << 2)
+        x_shift0 = Signal(signed(8))
+        m.d.comb += x_shift0.eq(self.x)
+
+        # CSD implementation
+        m.d.comb += self.result.eq(x_shift2 - x_shift0)
+        return m
+"###;
+        assert_eq!(py, expected);
+    }
+
+    #[test]
+    fn test_amaranth_multi_cse() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -187, 8),
+        ];
+        let py = generate_csd_multipliers_amaranth(&coeffs, "taps").unwrap();
+        assert!(py.contains("class taps(Elaboratable):"));
+        assert!(py.contains("        w_cse_0 = Signal(signed(11))\n"));
+        assert!(py.contains("        m.d.comb += self.y1.eq(-x_shift8 + x_shift6 + w_cse_0)\n"));
+    }
+
+    #[test]
+    fn test_amaranth_rounding_and_zero() {
+        let options = MultiplierOptions::new()
+            .output_frac_bits(0)
+            .rounding(OutputRounding::Nearest);
+        let netlist = csd_multiplier_netlist("+0-.+", 8, 2, &options).unwrap();
+        let py = emit_amaranth(&netlist);
+        assert!(py.contains("        m.d.comb += w_round.eq(1)\n"));
+        assert!(py.contains("        m.d.comb += self.result.eq((result_full >> 1))\n"));
+        let py = generate_csd_multiplier_amaranth("000", 8, 2).unwrap();
+        assert!(py.contains("        m.d.comb += self.result.eq(0)\n"));
+    }
+}
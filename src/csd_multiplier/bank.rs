@@ -0,0 +1,758 @@
+//! Runtime-reconfigurable coefficient bank.
+//!
+//! Adaptive filters switch between a few coefficients at run time. Instead of
+//! one multiplier per coefficient and an output multiplexer, a
+//! [`CoefficientBank`] builds a single shift-add network with a `sel` input:
+//!
+//! * the coefficients are aligned on a common power axis and every power
+//!   gets the vector of its digits across the bank;
+//! * powers whose digit vectors are equal (or negated) always enter the
+//!   product together, so their shifted inputs are summed once into a shared
+//!   wire $g_j$;
+//! * a group whose digit is the same for every coefficient is added as is,
+//!   and only the others pass through a small mux selecting $+g_j$, $-g_j$ or
+//!   $0$ by `sel`.
+//!
+//! Selecting coefficient $k$ therefore computes
+//!
+//! $$ y = \sum_j d_{k,j} \cdot g_j, \quad d_{k,j} \in \{-1,0,+1\} $$
+//!
+//! Out-of-range `sel` values select coefficient 0. [`BankReport`] compares
+//! the area with separate multipliers followed by an output mux.
+//!
+//! Ports and wires are named through [`Naming`]; `sel` keeps its name, like
+//! the clock of the FIR filters.
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use super::netlist::signed_width;
+use super::report::{full_adder_cells, json_string};
+use super::simulator::wrap;
+use super::{
+    align_coefficients, check_product_width, check_specs, csd_value, frac_bits_text,
+    lower_csd_multiplier, product_range, CsdMultiplierError, MultiplierOptions, MultiplierSpec,
+    Naming, TermOp,
+};
+
+/// Shifted inputs that enter the product together, with their digit per
+/// coefficient.
+#[derive(Debug, Clone)]
+struct Group {
+    /// Sign and power of each shifted input, highest power first
+    terms: Vec<(TermOp, usize)>,
+    /// Digit of the group in each coefficient
+    digits: Vec<i8>,
+    /// Width of the shared sum (when there are several terms)
+    width: usize,
+    /// Width of the mux output, `None` when the digit never changes
+    mux_width: Option<usize>,
+}
+
+impl Group {
+    /// Constant multiple of the input computed by the group.
+    fn multiple(&self) -> i128 {
+        self.terms
+            .iter()
+            .map(|&(op, p)| match op {
+                TermOp::Add => 1i128 << p,
+                TermOp::Sub => -(1i128 << p),
+            })
+            .sum()
+    }
+}
+
+/// A single multiplier whose coefficient is selected at run time.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::bank::coefficient_bank;
+/// use csd::csd_multiplier::{MultiplierOptions, MultiplierSpec};
+///
+/// let coeffs = vec![
+///     MultiplierSpec::from_integer("h0", 229, 8),
+///     MultiplierSpec::from_integer("h1", -187, 8),
+/// ];
+/// let bank = coefficient_bank(&coeffs, "adaptive", &MultiplierOptions::new()).unwrap();
+/// assert_eq!(bank.simulate(3, 0), 3 * 229);
+/// assert_eq!(bank.simulate(3, 1), 3 * -187);
+/// assert!(bank.report().adders < bank.report().separate_adders);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CoefficientBank {
+    module_name: String,
+    naming: Naming,
+    input_width: usize,
+    frac_bits: usize,
+    coefficients: Vec<MultiplierSpec>,
+    values: Vec<i128>,
+    output_width: usize,
+    groups: Vec<Group>,
+}
+
+/// Build a coefficient bank from coefficients that multiply the same input.
+///
+/// # Arguments
+///
+/// * `coeffs` - The selectable coefficients; `sel = k` selects `coeffs[k]`
+/// * `module_name` - Name for the generated Verilog module
+/// * `options` - Validation and naming; the other options don't apply to a bank
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers_with()`](super::generate_csd_multipliers_with),
+/// plus `CsdMultiplierError::ProductTooWide` if the products need more than
+/// 125 bits.
+pub fn coefficient_bank(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<CoefficientBank, CsdMultiplierError> {
+    let coefficients = check_specs(coeffs, options.validation)?;
+    let input_width = coefficients[0].input_width;
+    let (digits, frac_bits) = align_coefficients(&coefficients);
+    check_product_width(input_width + digits[0].len())?;
+    let values: Vec<i128> = digits.iter().map(|d| csd_value(d)).collect();
+    let output_width = values
+        .iter()
+        .map(|&v| {
+            let (lo, hi) = product_range(v, input_width);
+            signed_width(lo, hi)
+        })
+        .max()
+        .unwrap_or(1);
+
+    // Group powers by their digit vector, up to sign
+    let len = digits[0].len();
+    let mut groups: Vec<Group> = Vec::new();
+    let mut index: HashMap<Vec<i8>, usize> = HashMap::new();
+    for i in 0..len {
+        let vector: Vec<i8> = digits
+            .iter()
+            .map(|d| match d.as_bytes()[i] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => 0,
+            })
+            .collect();
+        let Some(&sign) = vector.iter().find(|&&d| d != 0) else {
+            continue;
+        };
+        let key: Vec<i8> = vector.iter().map(|&d| d * sign).collect();
+        let op = if sign > 0 { TermOp::Add } else { TermOp::Sub };
+        let power = len - 1 - i;
+        match index.get(&key) {
+            Some(&j) => groups[j].terms.push((op, power)),
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push(Group {
+                    terms: vec![(op, power)],
+                    digits: key,
+                    width: 0,
+                    mux_width: None,
+                });
+            }
+        }
+    }
+
+    for group in &mut groups {
+        // Keep the leading (highest-power) term positive
+        if group.terms[0].0 == TermOp::Sub {
+            for term in &mut group.terms {
+                term.0 = term.0.negate();
+            }
+            for d in &mut group.digits {
+                *d = -*d;
+            }
+        }
+        let (lo, hi) = product_range(group.multiple(), input_width);
+        group.width = signed_width(lo, hi).min(output_width);
+        if group.digits.iter().any(|&d| d != group.digits[0]) {
+            let (lo, hi) = group.digits.iter().fold((0, 0), |(lo, hi), &d| {
+                let (a, b) = product_range(d as i128 * group.multiple(), input_width);
+                (lo.min(a), hi.max(b))
+            });
+            group.mux_width = Some(signed_width(lo, hi).min(output_width));
+        }
+    }
+
+    let csds: Vec<&str> = coefficients.iter().map(|spec| spec.csd.as_str()).collect();
+    Ok(CoefficientBank {
+        module_name: options.naming.module(module_name, &csds),
+        naming: options.naming.clone(),
+        input_width,
+        frac_bits,
+        coefficients,
+        values,
+        output_width,
+        groups,
+    })
+}
+
+impl CoefficientBank {
+    /// Name of the generated module.
+    pub fn module_name(&self) -> &str {
+        &self.module_name
+    }
+
+    /// Name of the input port (default `x`).
+    pub fn input_name(&self) -> &str {
+        self.naming.input()
+    }
+
+    /// Name of the output port (default `result`).
+    pub fn output_name(&self) -> &str {
+        self.naming.output("result")
+    }
+
+    /// Bit width of the input.
+    pub fn input_width(&self) -> usize {
+        self.input_width
+    }
+
+    /// Bit width of the `sel` input.
+    pub fn sel_width(&self) -> usize {
+        let k = self.coefficients.len();
+        (usize::BITS - (k - 1).leading_zeros()).max(1) as usize
+    }
+
+    /// Bit width of the output, wide enough for every coefficient.
+    pub fn output_width(&self) -> usize {
+        self.output_width
+    }
+
+    /// Fractional bits of the output.
+    pub fn frac_bits(&self) -> usize {
+        self.frac_bits
+    }
+
+    /// Selectable coefficients as integers scaled by $2^F$, in `sel` order.
+    pub fn coefficients(&self) -> &[i128] {
+        &self.values
+    }
+
+    /// Two-input adders/subtractors of the shared network.
+    pub fn adder_count(&self) -> usize {
+        let shared: usize = self.groups.iter().map(|g| g.terms.len() - 1).sum();
+        shared + self.groups.len().saturating_sub(1)
+    }
+
+    /// Operands that are multiplexed by `sel`.
+    pub fn mux_count(&self) -> usize {
+        self.groups.iter().filter(|g| g.mux_width.is_some()).count()
+    }
+
+    /// Width of the input shifted left by `power`.
+    fn shift_width(&self, power: usize) -> usize {
+        (self.input_width + power).min(self.output_width)
+    }
+
+    /// Simulate the generated hardware for input `x` and selector `sel`.
+    ///
+    /// Returns the output as an integer scaled by $2^F$. `x` is wrapped to the
+    /// input width and every wire wraps at its declared width.
+    pub fn simulate(&self, x: i128, sel: usize) -> i128 {
+        let k = if sel < self.values.len() { sel } else { 0 };
+        let x = wrap(x, self.input_width);
+        let shifted = |power: usize| wrap(x << power, self.shift_width(power));
+        let sum = self.groups.iter().fold(0i128, |acc, group| {
+            let g = match group.terms.as_slice() {
+                [(_, power)] => shifted(*power),
+                terms => {
+                    let sum = terms.iter().fold(0i128, |s, &(op, power)| match op {
+                        TermOp::Add => s + shifted(power),
+                        TermOp::Sub => s - shifted(power),
+                    });
+                    wrap(sum, group.width)
+                }
+            };
+            match group.mux_width {
+                Some(width) => acc + wrap(group.digits[k] as i128 * g, width),
+                None => acc + group.digits[0] as i128 * g,
+            }
+        });
+        wrap(sum, self.output_width)
+    }
+
+    /// Name of the signal holding group `j`'s sum.
+    fn group_signal(&self, j: usize) -> String {
+        match self.groups[j].terms.as_slice() {
+            [(_, power)] => self.naming.shift_wire(*power),
+            _ => self.naming.wire(&format!("g{}", j)),
+        }
+    }
+
+    /// Name of the mux output selecting group `j`'s digit.
+    fn mux_signal(&self, j: usize) -> String {
+        self.naming.wire(&format!("m{}", j))
+    }
+
+    /// Print the bank as a Verilog-2001 module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::bank::coefficient_bank;
+    /// use csd::csd_multiplier::{MultiplierOptions, MultiplierSpec};
+    ///
+    /// let coeffs = vec![
+    ///     MultiplierSpec::from_integer("h0", 5, 8),
+    ///     MultiplierSpec::from_integer("h1", 3, 8),
+    /// ];
+    /// let bank = coefficient_bank(&coeffs, "bank", &MultiplierOptions::new()).unwrap();
+    /// let v = bank.verilog();
+    /// assert!(v.contains("    input [0:0] sel,      // Coefficient select"));
+    /// // Only the sign of the x term differs between 5 = "+0+" and 3 = "+0-"
+    /// assert!(v.contains("    wire signed [8:0] m1 = (sel == 1'd1) ? -x_shift0 : x_shift0; // sel 0..1: + -"));
+    /// assert!(v.contains("    assign result = x_shift2 + m1;"));
+    /// ```
+    pub fn verilog(&self) -> String {
+        let mut v = String::new();
+        let k = self.coefficients.len();
+        let sel_width = self.sel_width();
+        let input = self.input_name();
+
+        // --- module header ---
+        writeln!(v, "module {} (", self.module_name).unwrap();
+        writeln!(
+            v,
+            "    input signed [{}:0] {},      // Input value",
+            self.input_width - 1,
+            input
+        )
+        .unwrap();
+        writeln!(
+            v,
+            "    input [{}:0] sel,      // Coefficient select",
+            sel_width - 1
+        )
+        .unwrap();
+        let comment = match self.frac_bits {
+            0 => "Result of multiplication".to_string(),
+            f => format!("Result of multiplication ({})", frac_bits_text(f)),
+        };
+        writeln!(
+            v,
+            "    output signed [{}:0] {} // {}",
+            self.output_width - 1,
+            self.output_name(),
+            comment
+        )
+        .unwrap();
+        writeln!(v, ");").unwrap();
+        writeln!(v).unwrap();
+        writeln!(v, "    // Coefficient bank").unwrap();
+        for (i, spec) in self.coefficients.iter().enumerate() {
+            writeln!(
+                v,
+                "    //   sel = {}: {} = {} ({})",
+                i,
+                spec.name,
+                spec.value(),
+                spec.csd
+            )
+            .unwrap();
+        }
+        if k < 1 << sel_width {
+            writeln!(v, "    //   other sel values select coefficient 0").unwrap();
+        }
+
+        // --- shifted inputs ---
+        let mut powers: Vec<usize> = self
+            .groups
+            .iter()
+            .flat_map(|g| g.terms.iter().map(|&(_, p)| p))
+            .collect();
+        powers.sort_unstable_by(|a, b| b.cmp(a));
+        if !powers.is_empty() {
+            writeln!(v).unwrap();
+            writeln!(v, "    // Create shifted versions of input").unwrap();
+        }
+        for &p in &powers {
+            writeln!(
+                v,
+                "    wire signed [{}:0] {} = {} <<< {};",
+                self.shift_width(p) - 1,
+                self.naming.shift_wire(p),
+                input,
+                p
+            )
+            .unwrap();
+        }
+
+        // --- shared sums ---
+        let mut first = true;
+        for (j, group) in self.groups.iter().enumerate() {
+            if group.terms.len() < 2 {
+                continue;
+            }
+            if first {
+                writeln!(v).unwrap();
+                writeln!(
+                    v,
+                    "    // Terms whose digits change together across the bank"
+                )
+                .unwrap();
+                first = false;
+            }
+            let mut sum = String::new();
+            for (i, &(op, p)) in group.terms.iter().enumerate() {
+                match (i, op) {
+                    (0, TermOp::Add) => {}
+                    (0, TermOp::Sub) => sum.push('-'),
+                    (_, TermOp::Add) => sum.push_str(" + "),
+                    (_, TermOp::Sub) => sum.push_str(" - "),
+                }
+                sum.push_str(&self.naming.shift_wire(p));
+            }
+            writeln!(
+                v,
+                "    wire signed [{}:0] {} = {};",
+                group.width - 1,
+                self.group_signal(j),
+                sum
+            )
+            .unwrap();
+        }
+
+        // --- muxes ---
+        let mut first = true;
+        for (j, group) in self.groups.iter().enumerate() {
+            let Some(width) = group.mux_width else {
+                continue;
+            };
+            if first {
+                writeln!(v).unwrap();
+                writeln!(v, "    // Select the sign of each varying term").unwrap();
+                first = false;
+            }
+            let signal = self.group_signal(j);
+            let value = |d: i8| match d {
+                1 => signal.clone(),
+                -1 => format!("-{}", signal),
+                _ => "0".to_string(),
+            };
+            // Coefficient 0's digit is the default, covering unused sel values
+            let mut expr = String::new();
+            for d in [1i8, -1, 0] {
+                if d == group.digits[0] {
+                    continue;
+                }
+                let conditions: Vec<String> = (0..k)
+                    .filter(|&i| group.digits[i] == d)
+                    .map(|i| format!("sel == {}'d{}", sel_width, i))
+                    .collect();
+                if conditions.is_empty() {
+                    continue;
+                }
+                write!(expr, "({}) ? {} : ", conditions.join(" || "), value(d)).unwrap();
+            }
+            expr.push_str(&value(group.digits[0]));
+            let digits: Vec<&str> = group
+                .digits
+                .iter()
+                .map(|&d| match d {
+                    1 => "+",
+                    -1 => "-",
+                    _ => "0",
+                })
+                .collect();
+            writeln!(
+                v,
+                "    wire signed [{}:0] {} = {}; // sel 0..{}: {}",
+                width - 1,
+                self.mux_signal(j),
+                expr,
+                k - 1,
+                digits.join(" ")
+            )
+            .unwrap();
+        }
+
+        // --- output ---
+        let mut sum = String::new();
+        for (j, group) in self.groups.iter().enumerate() {
+            let (negate, name) = match group.mux_width {
+                Some(_) => (false, self.mux_signal(j)),
+                None => (group.digits[0] < 0, self.group_signal(j)),
+            };
+            match (sum.is_empty(), negate) {
+                (true, false) => sum.push_str(&name),
+                (true, true) => write!(sum, "-{}", name).unwrap(),
+                (false, false) => write!(sum, " + {}", name).unwrap(),
+                (false, true) => write!(sum, " - {}", name).unwrap(),
+            }
+        }
+        if sum.is_empty() {
+            sum.push('0');
+        }
+        writeln!(v).unwrap();
+        writeln!(v, "    // Sum of fixed and selected terms").unwrap();
+        writeln!(v, "    assign {} = {};", self.output_name(), sum).unwrap();
+        writeln!(v, "endmodule").unwrap();
+        v
+    }
+
+    /// Area of the bank compared with separate multipliers and an output mux.
+    pub fn report(&self) -> BankReport {
+        let k = self.coefficients.len();
+        let shared: usize = self
+            .groups
+            .iter()
+            .map(|g| (g.terms.len() - 1) * g.width)
+            .sum();
+        let separate: Vec<_> = self
+            .coefficients
+            .iter()
+            .map(|spec| {
+                lower_csd_multiplier(
+                    &spec.csd,
+                    spec.input_width,
+                    spec.max_power,
+                    &MultiplierOptions::new(),
+                )
+            })
+            .collect();
+        BankReport {
+            module_name: self.module_name.clone(),
+            coefficients: k,
+            adders: self.adder_count(),
+            muxes: self.mux_count(),
+            full_adders: shared + self.groups.len().saturating_sub(1) * self.output_width,
+            mux_cells: self
+                .groups
+                .iter()
+                .filter_map(|g| g.mux_width)
+                .map(|w| (k - 1) * w)
+                .sum(),
+            separate_adders: separate.iter().map(|n| n.adder_count()).sum(),
+            separate_full_adders: separate.iter().map(full_adder_cells).sum(),
+            separate_mux_cells: (k - 1) * self.output_width,
+        }
+    }
+}
+
+/// Area of a [`CoefficientBank`] versus separate multipliers.
+///
+/// Full-adder cells follow the model of [`CostReport`](super::report::CostReport);
+/// a $k$-input mux of $W$ bits counts as $(k - 1) \cdot W$ two-input mux
+/// cells. Negating a selected term is folded into the adder that consumes it,
+/// as synthesis does for add/subtract operands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankReport {
+    /// Module name
+    pub module_name: String,
+    /// Number of selectable coefficients
+    pub coefficients: usize,
+    /// Two-input adders/subtractors of the bank
+    pub adders: usize,
+    /// Operands multiplexed by `sel`
+    pub muxes: usize,
+    /// Estimated full-adder cells of the bank
+    pub full_adders: usize,
+    /// Estimated two-input mux cells of the bank
+    pub mux_cells: usize,
+    /// Adders of one multiplier per coefficient
+    pub separate_adders: usize,
+    /// Full-adder cells of one multiplier per coefficient
+    pub separate_full_adders: usize,
+    /// Mux cells of the output mux selecting between the separate products
+    pub separate_mux_cells: usize,
+}
+
+impl BankReport {
+    /// Print the report as a JSON object.
+    pub fn to_json(&self) -> String {
+        let mut j = String::new();
+        writeln!(j, "{{").unwrap();
+        writeln!(j, "  \"module\": {},", json_string(&self.module_name)).unwrap();
+        writeln!(j, "  \"coefficients\": {},", self.coefficients).unwrap();
+        writeln!(j, "  \"adders\": {},", self.adders).unwrap();
+        writeln!(j, "  \"muxes\": {},", self.muxes).unwrap();
+        writeln!(j, "  \"full_adders\": {},", self.full_adders).unwrap();
+        writeln!(j, "  \"mux_cells\": {},", self.mux_cells).unwrap();
+        writeln!(j, "  \"separate_adders\": {},", self.separate_adders).unwrap();
+        writeln!(
+            j,
+            "  \"separate_full_adders\": {},",
+            self.separate_full_adders
+        )
+        .unwrap();
+        writeln!(j, "  \"separate_mux_cells\": {}", self.separate_mux_cells).unwrap();
+        writeln!(j, "}}").unwrap();
+        j
+    }
+}
+
+impl fmt::Display for BankReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Coefficient bank {} ({} coefficients)",
+            self.module_name, self.coefficients
+        )?;
+        writeln!(
+            f,
+            "  adders: {} (separate: {})",
+            self.adders, self.separate_adders
+        )?;
+        writeln!(
+            f,
+            "  full-adder cells: {} (separate: {})",
+            self.full_adders, self.separate_full_adders
+        )?;
+        writeln!(
+            f,
+            "  mux cells: {} in {} muxes (separate: {})",
+            self.mux_cells, self.muxes, self.separate_mux_cells
+        )
+    }
+}
+
+/// Generate a coefficient bank's Verilog together with its [`BankReport`],
+/// with the default [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`coefficient_bank()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::bank::generate_coefficient_bank;
+/// use csd::csd_multiplier::MultiplierSpec;
+///
+/// let coeffs = vec![
+///     MultiplierSpec::from_integer("h0", 229, 8),
+///     MultiplierSpec::from_integer("h1", 197, 8),
+///     MultiplierSpec::from_integer("h2", -187, 8),
+/// ];
+/// let (v, report) = generate_coefficient_bank(&coeffs, "bank").unwrap();
+/// assert!(v.contains("module bank ("));
+/// println!("{}", report);
+/// ```
+pub fn generate_coefficient_bank(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<(String, BankReport), CsdMultiplierError> {
+    let bank = coefficient_bank(coeffs, module_name, &MultiplierOptions::new())?;
+    Ok((bank.verilog(), bank.report()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bank_of(values: &[i64], width: usize) -> CoefficientBank {
+        let coeffs: Vec<MultiplierSpec> = values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| MultiplierSpec::from_integer(&format!("h{}", i), v, width))
+            .collect();
+        coefficient_bank(&coeffs, "bank", &MultiplierOptions::new()).unwrap()
+    }
+
+    #[test]
+    fn test_bank_exhaustive() {
+        let values = [229, 197, -187, 0, 64];
+        let bank = bank_of(&values, 8);
+        assert_eq!(bank.sel_width(), 3);
+        for (k, &c) in values.iter().enumerate() {
+            for x in -128..128 {
+                assert_eq!(bank.simulate(x, k), c as i128 * x, "sel {} x {}", k, x);
+            }
+        }
+        // Unused sel values select coefficient 0
+        assert_eq!(bank.simulate(5, 7), 5 * 229);
+    }
+
+    #[test]
+    fn test_bank_verilog_golden() {
+        let bank = bank_of(&[5, 3], 8);
+        let expected = r###"module bank (
+    input signed [7:0] x,      // Input value
+    input [0:0] sel,      // Coefficient select
+    output signed [10:0] result // Result of multiplication
+);
+
+    // Coefficient bank
+    //   sel = 0: h0 = 5 (+0+)
+    //   sel = 1: h1 = 3 (+0-)
+
+    // Create shifted versions of input
+    wire signed [9:0] x_shift2 = x <<< 2;
+    wire signed [7:0] x_shift0 = x <<< 0;
+
+    // Select the sign of each varying term
+    wire signed [8:0] m1 = (sel == 1'd1) ? -x_shift0 : x_shift0; // sel 0..1: + -
+
+    // Sum of fixed and selected terms
+    assign result = x_shift2 + m1;
+endmodule
+"###;
+        assert_eq!(bank.verilog(), expected);
+    }
+
+    #[test]
+    fn test_bank_shares_groups() {
+        // 0b1010 and -0b1010 share "+0+0" up to sign; only one mux is needed
+        let bank = bank_of(&[10, -10], 8);
+        assert_eq!(bank.mux_count(), 1);
+        assert_eq!(bank.adder_count(), 1);
+        let v = bank.verilog();
+        assert!(v.contains("wire signed [11:0] g"));
+        let report = bank.report();
+        assert_eq!(report.separate_adders, 2);
+        assert!(report.to_json().contains("\"muxes\": 1,"));
+    }
+
+    #[test]
+    fn test_bank_fractional_and_errors() {
+        let coeffs = vec![
+            MultiplierSpec::from_f64("h0", 0.75, 2, 8).unwrap(),
+            MultiplierSpec::from_integer("h1", 1, 8),
+        ];
+        let bank = coefficient_bank(&coeffs, "bank", &MultiplierOptions::new()).unwrap();
+        assert_eq!(bank.frac_bits(), 2);
+        assert_eq!(bank.coefficients(), &[3, 4]);
+        assert_eq!(bank.simulate(-7, 0), -21);
+        assert_eq!(bank.simulate(-7, 1), -28);
+        assert_eq!(
+            coefficient_bank(&[], "bank", &MultiplierOptions::new()).unwrap_err(),
+            CsdMultiplierError::EmptyCoefficients
+        );
+    }
+
+    #[test]
+    fn test_bank_naming() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("h0", 10, 8),
+            MultiplierSpec::from_integer("h1", -10, 8),
+            MultiplierSpec::from_integer("h2", 3, 8),
+        ];
+        let naming = Naming::new()
+            .input_name("a")
+            .output_name("p")
+            .wire_prefix("u0")
+            .coefficient_suffix(true);
+        let options = MultiplierOptions::new().naming(naming);
+        let bank = coefficient_bank(&coeffs, "bank", &options).unwrap();
+        assert_eq!(bank.module_name(), "bank_10_m10_3");
+        let v = bank.verilog();
+        assert!(v.contains("input signed [7:0] a,"), "{}", v);
+        assert!(v.contains("input [1:0] sel,"), "{}", v);
+        for name in [
+            "u0_a_shift3 = a <<< 3;",
+            "wire signed [11:0] u0_g",
+            "u0_m",
+            "assign p = ",
+        ] {
+            assert!(v.contains(name), "{} missing:\n{}", name, v);
+        }
+        for name in ["x_shift", "result", " g0", " m0", " x,"] {
+            assert!(!v.contains(name), "{} left:\n{}", name, v);
+        }
+        assert_eq!(bank.simulate(-7, 1), 70);
+    }
+}
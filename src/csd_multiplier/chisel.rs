@@ -0,0 +1,292 @@
+//! Chisel backend.
+//!
+//! Prints a [`Netlist`] as a Chisel 3 `Module` (Scala source) with an `io`
+//! bundle of `SInt` ports. Chisel's `+%`/`-%` keep the width of their
+//! operands, so every operand is first brought to the width of the signal it
+//! is assigned to — sign-extended with `pad`, or narrowed by a bit slice — and
+//! the sums then wrap exactly like the Verilog output. The module therefore
+//! shares the Verilog's LCSRe, cross-CSE and folding decisions bit for bit.
+//!
+//! Chisel treats `val`s starting with an underscore as temporaries, so
+//! internal wires such as `_pat` are renamed with a `w` prefix (`w_pat`).
+
+use std::fmt::Write;
+
+use super::netlist::{Netlist, Source, Term, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, CsdMultiplierError, MultiplierOptions,
+    MultiplierSpec, TermOp,
+};
+
+/// Map a netlist signal name to a Chisel `val` name.
+fn chisel_identifier(name: &str) -> String {
+    if name.starts_with('_') {
+        format!("w{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Scala expression for a signal: ports live in the `io` bundle.
+fn chisel_signal(netlist: &Netlist, source: Source) -> String {
+    match source {
+        Source::Input(i) => format!("io.{}", netlist.inputs[i].name),
+        Source::Wire(i) => chisel_identifier(&netlist.wires[i].name),
+    }
+}
+
+/// Convert `expr` (an `SInt` of `from` bits) to `to` bits, keeping the low bits.
+fn fit(expr: &str, from: usize, to: usize) -> String {
+    match from.cmp(&to) {
+        std::cmp::Ordering::Equal => expr.to_string(),
+        std::cmp::Ordering::Less => format!("{}.pad({})", expr, to),
+        std::cmp::Ordering::Greater => format!("{}({}, 0).asSInt", expr, to - 1),
+    }
+}
+
+/// Render `signal` (of `from` bits) shifted by `shift` at `width` bits.
+fn chisel_shifted(signal: &str, from: usize, shift: isize, width: usize) -> String {
+    match shift {
+        0 => fit(signal, from, width),
+        s if s > 0 => fit(&format!("({} << {})", signal, s), from + s as usize, width),
+        s => {
+            let s = (-s) as usize;
+            if from > s + width {
+                // The low `width` bits of the shifted value are a plain slice
+                format!("{}({}, {}).asSInt", signal, s + width - 1, s)
+            } else {
+                fit(
+                    &format!("({} >> {})", signal, s),
+                    from.saturating_sub(s).max(1),
+                    width,
+                )
+            }
+        }
+    }
+}
+
+/// `3.S(4.W)`, `(-3).S(4.W)`.
+fn chisel_literal(value: i128, width: usize) -> String {
+    if value < 0 {
+        format!("({}).S({}.W)", value, width)
+    } else {
+        format!("{}.S({}.W)", value, width)
+    }
+}
+
+/// Render a signed sum of terms at `width` bits.
+fn chisel_sum(netlist: &Netlist, terms: &[Term], width: usize) -> String {
+    if terms.is_empty() {
+        return chisel_literal(0, width);
+    }
+    let mut expr = String::new();
+    for (i, term) in terms.iter().enumerate() {
+        let operand = chisel_shifted(
+            &chisel_signal(netlist, term.source),
+            netlist.source_width(term.source),
+            term.shift,
+            width,
+        );
+        match (i, term.op) {
+            (0, TermOp::Add) => {}
+            (0, TermOp::Sub) => write!(expr, "{} -% ", chisel_literal(0, width)).unwrap(),
+            (_, TermOp::Add) => expr.push_str(" +% "),
+            (_, TermOp::Sub) => expr.push_str(" -% "),
+        }
+        expr.push_str(&operand);
+    }
+    expr
+}
+
+/// Print a [`Netlist`] as a Chisel 3 `Module`.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::chisel::emit_chisel;
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+///
+/// let netlist = csd_multiplier_netlist("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+/// let scala = emit_chisel(&netlist);
+/// assert!(scala.contains("class csd_multiplier extends Module {"));
+/// assert!(scala.contains("  val w_pat = Wire(SInt(15.W))\n"));
+/// assert!(scala.contains("  io.result := w_pat +% (w_pat >> 4).pad(15)\n"));
+/// ```
+pub fn emit_chisel(netlist: &Netlist) -> String {
+    let mut scala = String::new();
+    writeln!(scala, "import chisel3._").unwrap();
+    writeln!(scala).unwrap();
+    writeln!(scala, "class {} extends Module {{", netlist.module_name).unwrap();
+    writeln!(scala, "  val io = IO(new Bundle {{").unwrap();
+    let ports = netlist
+        .inputs
+        .iter()
+        .map(|p| ("Input", p))
+        .chain(netlist.outputs.iter().map(|o| ("Output", &o.port)));
+    for (dir, port) in ports {
+        write!(
+            scala,
+            "    val {} = {}(SInt({}.W))",
+            port.name, dir, port.width
+        )
+        .unwrap();
+        match &port.comment {
+            Some(c) => writeln!(scala, " // {}", c).unwrap(),
+            None => writeln!(scala).unwrap(),
+        }
+    }
+    writeln!(scala, "  }})").unwrap();
+
+    for wire in &netlist.wires {
+        if let Some(comment) = &wire.comment {
+            writeln!(scala).unwrap();
+            writeln!(scala, "  // {}", comment).unwrap();
+        }
+        let name = chisel_identifier(&wire.name);
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => chisel_shifted(
+                &chisel_signal(netlist, Source::Input(*input)),
+                netlist.inputs[*input].width,
+                *power as isize,
+                wire.width,
+            ),
+            WireExpr::Sum(terms) => chisel_sum(netlist, terms, wire.width),
+            WireExpr::Constant(value) => chisel_literal(*value, wire.width),
+        };
+        writeln!(scala, "  val {} = Wire(SInt({}.W))", name, wire.width).unwrap();
+        writeln!(scala, "  {} := {}", name, expr).unwrap();
+    }
+    for output in &netlist.outputs {
+        if let Some(comment) = &output.comment {
+            writeln!(scala).unwrap();
+            writeln!(scala, "  // {}", comment).unwrap();
+        }
+        writeln!(
+            scala,
+            "  io.{} := {}",
+            output.port.name,
+            chisel_sum(netlist, &output.terms, output.port.width)
+        )
+        .unwrap();
+    }
+    writeln!(scala, "}}").unwrap();
+    scala
+}
+
+/// Generate a Chisel CSD multiplier module, the counterpart of
+/// [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::chisel::generate_csd_multiplier_chisel;
+///
+/// let scala = generate_csd_multiplier_chisel("+0-", 8, 2).unwrap();
+/// assert!(scala.contains("  io.result := x_shift2 -% x_shift0.pad(10)\n"));
+/// ```
+pub fn generate_csd_multiplier_chisel(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_chisel_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_chisel()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_chisel_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_chisel(&netlist))
+}
+
+/// Generate a Chisel multi-coefficient module with cross-CSE, the counterpart of
+/// [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_chisel(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_chisel_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_chisel()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_chisel_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_chisel(&netlist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chisel_golden() {
+        let scala = generate_csd_multiplier_chisel("+0-", 8, 2).unwrap();
+        let expected = r###"import chisel3._
+
+class csd_multiplier extends Module {
+  val io = IO(new Bundle {
+    val x = Input(SInt(8.W)) // Input value
+    val result = Output(SInt(10.W)) // Result of multiplication
+  })
+
+  // Create shifted versions of input
+  val x_shift2 = Wire(SInt(10.W))
+  x_shift2 := (io.x << 2)
+  val x_shift0 = Wire(SInt(8.W))
+  x_shift0 := io.x
+
+  // CSD implementation
+  io.result := x_shift2 -% x_shift0.pad(10)
+}
+"###;
+        assert_eq!(scala, expected);
+    }
+
+    #[test]
+    fn test_chisel_operands() {
+        assert_eq!(chisel_shifted("a", 8, 0, 8), "a");
+        assert_eq!(chisel_shifted("a", 8, 0, 12), "a.pad(12)");
+        assert_eq!(chisel_shifted("a", 12, 0, 8), "a(7, 0).asSInt");
+        assert_eq!(chisel_shifted("a", 8, 3, 10), "(a << 3)(9, 0).asSInt");
+        assert_eq!(chisel_shifted("a", 12, -3, 6), "a(8, 3).asSInt");
+        assert_eq!(chisel_shifted("a", 12, -3, 9), "(a >> 3)");
+        assert_eq!(chisel_shifted("a", 12, -3, 12), "(a >> 3).pad(12)");
+        assert_eq!(chisel_literal(-2, 3), "(-2).S(3.W)");
+    }
+
+    #[test]
+    fn test_chisel_multi_and_negation() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -229, 8),
+        ];
+        let scala = generate_csd_multipliers_chisel(&coeffs, "taps").unwrap();
+        assert!(scala.contains("class taps extends Module {"));
+        assert!(scala.contains("    val y1 = Output(SInt(16.W))"));
+        assert!(scala.contains("  io.y1 := 0.S(16.W) -% w_y0_prod\n"));
+    }
+}
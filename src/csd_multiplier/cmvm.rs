@@ -0,0 +1,546 @@
+//! Constant matrix-vector multiplication (CMVM).
+//!
+//! Transforms such as the DCT or color-space conversion compute
+//!
+//! $$ y_r = \sum_{c} a_{r,c} \cdot x_c $$
+//!
+//! for a constant matrix $A$. [`cmvm_netlist()`] lowers every row to one sum
+//! of shifted inputs and then repeatedly extracts the most frequent pair of
+//! terms
+//!
+//! $$ s = (u \ll i) \pm (v \ll j) $$
+//!
+//! where $u$ and $v$ may be different inputs (or earlier sub-expressions),
+//! replacing every occurrence — in any row, at any relative shift — by
+//! `s << base`. Each extraction with $n$ occurrences saves $n - 1$ adders; it
+//! stops when no pair occurs twice. The result is an ordinary [`Netlist`]
+//! with inputs `x0..xk` and one output per row, so every backend, the
+//! simulator and the cost reports apply unchanged.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use super::netlist::{Netlist, Output, Port, Source, Term, Wire, WireExpr};
+use super::{
+    check_coefficient, frac_bits_text, print_verilog, split_binary_point, CsdMultiplierError,
+    MultiplierOptions, TermOp,
+};
+
+/// Operand of a row sum during extraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Operand {
+    /// Input `x_c`
+    Input(usize),
+    /// Extracted sub-expression
+    Shared(usize),
+}
+
+/// One signed, left-shifted operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RowTerm {
+    op: TermOp,
+    operand: Operand,
+    shift: usize,
+}
+
+/// A pair of terms up to a common shift and sign:
+/// `(first, first shift, second, second shift, same sign)`.
+type PairKey = (Operand, usize, Operand, usize, bool);
+
+/// Key of the pair `a`, `b` and the common shift of its occurrence.
+fn pair_key(a: &RowTerm, b: &RowTerm) -> (PairKey, usize) {
+    let (a, b) = if (a.operand, a.shift) <= (b.operand, b.shift) {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let base = a.shift.min(b.shift);
+    (
+        (
+            a.operand,
+            a.shift - base,
+            b.operand,
+            b.shift - base,
+            a.op == b.op,
+        ),
+        base,
+    )
+}
+
+/// Disjoint occurrences of every pair in `row`, greedily in term order.
+fn row_pairs(row: &[RowTerm]) -> BTreeMap<PairKey, Vec<(usize, usize)>> {
+    let mut pairs: BTreeMap<PairKey, Vec<(usize, usize)>> = BTreeMap::new();
+    for i in 0..row.len() {
+        for j in i + 1..row.len() {
+            let (key, _) = pair_key(&row[i], &row[j]);
+            let occurrences = pairs.entry(key).or_default();
+            if occurrences
+                .iter()
+                .all(|&(a, b)| a != i && a != j && b != i && b != j)
+            {
+                occurrences.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+/// Extract the most frequent pair of terms from `rows` until none occurs
+/// twice, replacing every occurrence by a shifted sub-expression term.
+///
+/// Returns the sub-expressions in creation order; each may use earlier ones.
+fn extract_shared(rows: &mut [Vec<RowTerm>]) -> Vec<PairKey> {
+    let mut shared: Vec<PairKey> = Vec::new();
+    loop {
+        let mut counts: BTreeMap<PairKey, usize> = BTreeMap::new();
+        for row in rows.iter() {
+            for (key, occurrences) in row_pairs(row) {
+                *counts.entry(key).or_default() += occurrences.len();
+            }
+        }
+        // Most occurrences first; ties go to the smallest key
+        let Some((key, _)) = counts.into_iter().filter(|&(_, n)| n >= 2).fold(
+            None,
+            |best: Option<(PairKey, usize)>, (key, n)| match best {
+                Some((_, m)) if m >= n => best,
+                _ => Some((key, n)),
+            },
+        ) else {
+            break;
+        };
+        let id = shared.len();
+        shared.push(key);
+        for row in rows.iter_mut() {
+            let Some(occurrences) = row_pairs(row).remove(&key) else {
+                continue;
+            };
+            let mut removed = BTreeSet::new();
+            let mut added = Vec::new();
+            for (i, j) in occurrences {
+                let (_, base) = pair_key(&row[i], &row[j]);
+                // The sub-expression's first operand keeps its sign
+                let first = if (row[i].operand, row[i].shift - base) == (key.0, key.1) {
+                    row[i]
+                } else {
+                    row[j]
+                };
+                added.push(RowTerm {
+                    op: first.op,
+                    operand: Operand::Shared(id),
+                    shift: base,
+                });
+                removed.insert(i);
+                removed.insert(j);
+            }
+            let mut kept: Vec<RowTerm> = row
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !removed.contains(i))
+                .map(|(_, t)| *t)
+                .collect();
+            kept.extend(added);
+            kept.sort_by(|a, b| b.shift.cmp(&a.shift).then(a.operand.cmp(&b.operand)));
+            *row = kept;
+        }
+    }
+    shared
+}
+
+/// Build the shift-add [`Netlist`] computing `matrix` times the input vector.
+///
+/// Row $r$ drives output `y{r}` and column $c$ multiplies input `x{c}`; the
+/// names follow [`Naming`](super::Naming)'s input and output names. Entries are
+/// CSD strings whose integer digits define their own `max_power`, optionally
+/// with fractional digits; every output carries the largest number of
+/// fractional digits $F$ of the matrix. Of the options, the validation mode,
+/// names and [`MultiplierOptions::share_patterns()`] (which enables the
+/// cross-input extraction) apply.
+///
+/// # Arguments
+///
+/// * `matrix` - Coefficient rows, all of the same length
+/// * `input_width` - Bit width of every input `x{c}`
+/// * `module_name` - Name for the generated module
+/// * `options` - Validation, naming and sharing options
+///
+/// # Errors
+///
+/// Returns `CsdMultiplierError::EmptyCoefficients` if the matrix has no rows
+/// or no columns, `CsdMultiplierError::RaggedMatrix` if a row's length differs
+/// from the first row's, `CsdMultiplierError::ZeroInputWidth` if
+/// `input_width` is 0, and `CsdMultiplierError::InvalidSpec` (indexed row-major,
+/// named like `y1*x2`) wrapping the error of an invalid entry.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::cmvm::cmvm_netlist;
+/// use csd::csd_multiplier::simulator::simulate;
+/// use csd::csd_multiplier::MultiplierOptions;
+///
+/// // [5 3; 5 -3] = [+0+ +0-; +0+ -0+]
+/// let matrix = vec![vec!["+0+", "+0-"], vec!["+0+", "-0+"]];
+/// let netlist = cmvm_netlist(&matrix, 8, "butterfly", &MultiplierOptions::new()).unwrap();
+/// assert_eq!(netlist.inputs.len(), 2);
+/// assert_eq!(simulate(&netlist, &[7, -2]), vec![5 * 7 + 3 * -2, 5 * 7 - 3 * -2]);
+/// ```
+pub fn cmvm_netlist(
+    matrix: &[Vec<&str>],
+    input_width: usize,
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<Netlist, CsdMultiplierError> {
+    let columns = matrix.first().map_or(0, |row| row.len());
+    if columns == 0 {
+        return Err(CsdMultiplierError::EmptyCoefficients);
+    }
+    if input_width == 0 {
+        return Err(CsdMultiplierError::ZeroInputWidth);
+    }
+    let naming = &options.naming;
+    let output_name = |r: usize| format!("{}{}", naming.output("y"), r);
+    let input_name = |c: usize| format!("{}{}", naming.input(), c);
+
+    // Validate and align every entry on a common power axis
+    let mut entries: Vec<Vec<(String, usize)>> = Vec::with_capacity(matrix.len());
+    for (r, row) in matrix.iter().enumerate() {
+        if row.len() != columns {
+            return Err(CsdMultiplierError::RaggedMatrix {
+                row: r,
+                expected: columns,
+                actual: row.len(),
+            });
+        }
+        let mut checked = Vec::with_capacity(columns);
+        for (c, csd) in row.iter().enumerate() {
+            let (digits, _) = split_binary_point(csd);
+            let integer_digits = csd.find('.').unwrap_or(digits.len()).max(1);
+            checked.push(
+                check_coefficient(csd, input_width, integer_digits - 1, options.validation)
+                    .map_err(|e| {
+                        e.in_spec(
+                            r * columns + c,
+                            &format!("{}*{}", output_name(r), input_name(c)),
+                        )
+                    })?,
+            );
+        }
+        entries.push(checked);
+    }
+    let top = entries.iter().flatten().map(|(_, m)| *m).max().unwrap_or(0);
+    let frac_bits = entries
+        .iter()
+        .flatten()
+        .map(|(csd, _)| split_binary_point(csd).1)
+        .max()
+        .unwrap_or(0);
+
+    // Row sums of shifted inputs, highest power first
+    let mut rows: Vec<Vec<RowTerm>> = entries
+        .iter()
+        .map(|row| {
+            let mut terms = Vec::new();
+            for (c, (csd, _)) in row.iter().enumerate() {
+                let (digits, f) = split_binary_point(csd);
+                let lowest = frac_bits - f;
+                for (i, digit) in digits.bytes().enumerate() {
+                    let op = match digit {
+                        b'+' => TermOp::Add,
+                        b'-' => TermOp::Sub,
+                        _ => continue,
+                    };
+                    terms.push(RowTerm {
+                        op,
+                        operand: Operand::Input(c),
+                        shift: lowest + digits.len() - 1 - i,
+                    });
+                }
+            }
+            terms.sort_by(|a, b| b.shift.cmp(&a.shift).then(a.operand.cmp(&b.operand)));
+            terms
+        })
+        .collect();
+
+    // Greedy pairwise extraction of common sub-expressions
+    let shared = if options.share_patterns {
+        extract_shared(&mut rows)
+    } else {
+        Vec::new()
+    };
+
+    // --- netlist ---
+    // Generous widths, tightened from the exact value ranges at the end
+    let nnz: usize = rows.iter().map(|r| r.len()).sum::<usize>() + 2 * shared.len();
+    let width = input_width + top + frac_bits + (usize::BITS - nnz.leading_zeros()) as usize + 1;
+    let mut netlist = Netlist {
+        module_name: naming.module(module_name, &[]),
+        inputs: (0..columns)
+            .map(|c| Port {
+                name: input_name(c),
+                width: input_width,
+                frac_bits: 0,
+                comment: Some(format!("Input vector element {}", c)),
+            })
+            .collect(),
+        wires: Vec::new(),
+        outputs: Vec::new(),
+    };
+
+    // Shifted inputs, by input and then descending power
+    let mut shifts: BTreeSet<(usize, std::cmp::Reverse<usize>)> = BTreeSet::new();
+    let mut note = |operand: Operand, shift: usize| {
+        if let Operand::Input(c) = operand {
+            shifts.insert((c, std::cmp::Reverse(shift)));
+        }
+    };
+    for &(a, sa, b, sb, _) in &shared {
+        note(a, sa);
+        note(b, sb);
+    }
+    for t in rows.iter().flatten() {
+        note(t.operand, t.shift);
+    }
+    let mut shift_wires: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for (i, &(c, std::cmp::Reverse(power))) in shifts.iter().enumerate() {
+        netlist.wires.push(Wire {
+            name: naming.wire(&format!("{}_shift{}", input_name(c), power)),
+            width,
+            expr: WireExpr::Shift { input: c, power },
+            comment: (i == 0).then(|| "Create shifted versions of inputs".to_string()),
+        });
+        shift_wires.insert((c, power), netlist.wires.len() - 1);
+    }
+
+    let mut shared_wires: Vec<usize> = Vec::with_capacity(shared.len());
+    let term = |op: TermOp, operand: Operand, shift: usize, shared_wires: &[usize]| match operand {
+        Operand::Input(c) => Term {
+            op,
+            source: Source::Wire(shift_wires[&(c, shift)]),
+            shift: 0,
+        },
+        Operand::Shared(k) => Term {
+            op,
+            source: Source::Wire(shared_wires[k]),
+            shift: shift as isize,
+        },
+    };
+    for (k, &(a, sa, b, sb, same)) in shared.iter().enumerate() {
+        let second = if same { TermOp::Add } else { TermOp::Sub };
+        let mut terms = vec![
+            term(TermOp::Add, a, sa, &shared_wires),
+            term(second, b, sb, &shared_wires),
+        ];
+        // Lead with the higher-weight operand, as the other generators do
+        if sb > sa && same {
+            terms.swap(0, 1);
+        }
+        netlist.wires.push(Wire {
+            name: naming.wire(&format!("_cse_{}", k)),
+            width,
+            expr: WireExpr::Sum(terms),
+            comment: (k == 0).then(|| "Cross-input common sub-expressions".to_string()),
+        });
+        shared_wires.push(netlist.wires.len() - 1);
+    }
+
+    for (r, row) in rows.iter().enumerate() {
+        let mut terms: Vec<Term> = row
+            .iter()
+            .map(|t| term(t.op, t.operand, t.shift, &shared_wires))
+            .collect();
+        // Avoid a leading negation when any term is added
+        if let Some(first_add) = terms.iter().position(|t| t.op == TermOp::Add) {
+            let t = terms.remove(first_add);
+            terms.insert(0, t);
+        }
+        let mut comment = format!("{} =", output_name(r));
+        let mut any = false;
+        for (c, csd) in matrix[r].iter().enumerate() {
+            if csd.bytes().any(|d| d == b'+' || d == b'-') {
+                write!(comment, " {} * {},", csd, input_name(c)).unwrap();
+                any = true;
+            }
+        }
+        if any {
+            comment.pop();
+        } else {
+            comment.push_str(" 0");
+        }
+        let port_comment = match frac_bits {
+            0 => format!("Row {} of the product", r),
+            f => format!("Row {} of the product ({})", r, frac_bits_text(f)),
+        };
+        netlist.outputs.push(Output {
+            port: Port {
+                name: output_name(r),
+                width,
+                frac_bits,
+                comment: Some(port_comment),
+            },
+            terms,
+            comment: Some(comment),
+        });
+    }
+
+    netlist.minimize_widths();
+    Ok(netlist)
+}
+
+/// Generate a Verilog module multiplying the input vector by a constant matrix.
+///
+/// # Errors
+///
+/// Same as [`cmvm_netlist()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::cmvm::generate_cmvm;
+///
+/// let matrix = vec![vec!["+0+", "+0-"], vec!["+0+", "-0+"]];
+/// let v = generate_cmvm(&matrix, 8, "butterfly").unwrap();
+/// assert!(v.contains("module butterfly ("));
+/// assert!(v.contains("    input signed [7:0] x1,"));
+/// assert!(v.contains("    output signed [10:0] y1"));
+/// ```
+pub fn generate_cmvm(
+    matrix: &[Vec<&str>],
+    input_width: usize,
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_cmvm_with(matrix, input_width, module_name, &MultiplierOptions::new())
+}
+
+/// Generate a CMVM module with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`cmvm_netlist()`].
+pub fn generate_cmvm_with(
+    matrix: &[Vec<&str>],
+    input_width: usize,
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = cmvm_netlist(matrix, input_width, module_name, options)?;
+    Ok(print_verilog(&netlist, &[], options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd::to_csd_i;
+    use crate::csd_multiplier::simulator::simulate;
+
+    fn check_exact(matrix: &[Vec<i64>], netlist: &Netlist, input_width: usize) {
+        let lo = -(1i128 << (input_width - 1));
+        let hi = (1i128 << (input_width - 1)) - 1;
+        let columns = matrix[0].len();
+        for step in 0..200i128 {
+            let x: Vec<i128> = (0..columns as i128)
+                .map(|c| lo + (step * 37 + c * 101) % (hi - lo + 1))
+                .collect();
+            let y: Vec<i128> = matrix
+                .iter()
+                .map(|row| row.iter().zip(&x).map(|(&a, &v)| a as i128 * v).sum())
+                .collect();
+            assert_eq!(simulate(netlist, &x), y, "x = {:?}", x);
+        }
+        let extremes: Vec<i128> = (0..columns)
+            .map(|c| if c % 2 == 0 { lo } else { hi })
+            .collect();
+        let y: Vec<i128> = matrix
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&extremes)
+                    .map(|(&a, &v)| a as i128 * v)
+                    .sum()
+            })
+            .collect();
+        assert_eq!(simulate(netlist, &extremes), y);
+    }
+
+    fn csd_matrix(matrix: &[Vec<i64>]) -> Vec<Vec<String>> {
+        matrix
+            .iter()
+            .map(|row| row.iter().map(|&v| to_csd_i(v as i32)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_cmvm_exact_and_shared() {
+        // Integer 4-point DCT-like matrix
+        let matrix = vec![
+            vec![64, 64, 64, 64],
+            vec![83, 36, -36, -83],
+            vec![64, -64, -64, 64],
+            vec![36, -83, 83, -36],
+        ];
+        let strings = csd_matrix(&matrix);
+        let rows: Vec<Vec<&str>> = strings
+            .iter()
+            .map(|r| r.iter().map(|s| s.as_str()).collect())
+            .collect();
+        let shared = cmvm_netlist(&rows, 9, "dct4", &MultiplierOptions::new()).unwrap();
+        let flat = cmvm_netlist(
+            &rows,
+            9,
+            "dct4",
+            &MultiplierOptions::new().share_patterns(false),
+        )
+        .unwrap();
+        check_exact(&matrix, &shared, 9);
+        check_exact(&matrix, &flat, 9);
+        assert!(shared.adder_count() < flat.adder_count());
+        assert!(shared.wires.iter().any(|w| w.name == "_cse_0"));
+    }
+
+    #[test]
+    fn test_cmvm_cross_input_pair() {
+        // x0 + x1 appears in both rows
+        let matrix = vec![vec!["+", "+", "+"], vec!["+", "+", "-"]];
+        let netlist = cmvm_netlist(&matrix, 8, "m", &MultiplierOptions::new()).unwrap();
+        assert_eq!(netlist.adder_count(), 3);
+        let v = print_verilog(&netlist, &[], &MultiplierOptions::new());
+        assert!(v.contains("    wire signed [8:0] _cse_0 = x0_shift0 + x1_shift0;"));
+        assert!(v.contains("    assign y1 = _cse_0 - x2_shift0;"));
+    }
+
+    #[test]
+    fn test_cmvm_fractional_and_zero_rows() {
+        let matrix = vec![vec!["0.+", "+"], vec!["0", "0"]];
+        let netlist = cmvm_netlist(&matrix, 8, "m", &MultiplierOptions::new()).unwrap();
+        assert_eq!(netlist.outputs[0].port.frac_bits, 1);
+        // 0.5 * 3 + 1 * -4 = -2.5, scaled by 2
+        assert_eq!(simulate(&netlist, &[3, -4]), vec![-5, 0]);
+        let v = print_verilog(&netlist, &[], &MultiplierOptions::new());
+        assert!(v.contains("    assign y1 = 0;"));
+    }
+
+    #[test]
+    fn test_cmvm_errors() {
+        let options = MultiplierOptions::new();
+        assert_eq!(
+            cmvm_netlist(&[], 8, "m", &options).unwrap_err(),
+            CsdMultiplierError::EmptyCoefficients
+        );
+        assert_eq!(
+            cmvm_netlist(&[vec!["+"], vec!["+", "-"]], 8, "m", &options).unwrap_err(),
+            CsdMultiplierError::RaggedMatrix {
+                row: 1,
+                expected: 1,
+                actual: 2
+            }
+        );
+        let err = cmvm_netlist(&[vec!["+", "+x"]], 8, "m", &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Coefficient 1 (y0*x1): Invalid character 'x' at position 1 in CSD string"
+        );
+        assert_eq!(
+            cmvm_netlist(&[vec!["+"]], 0, "m", &options).unwrap_err(),
+            CsdMultiplierError::ZeroInputWidth
+        );
+    }
+}
@@ -0,0 +1,745 @@
+//! Complex constant multiplier generator.
+//!
+//! Multiplies a complex input $a + jb$ by a constant $c + jd$, such as an FFT
+//! twiddle factor:
+//!
+//! $$ (a + jb)(c + jd) = (ac - bd) + j(ad + bc) $$
+//!
+//! * [`ComplexForm::FourMultiplier`] — $a$ and $b$ each feed one
+//!   multiple-constant-multiplication block computing both $ac$ and $ad$
+//!   (resp. $bc$ and $bd$) with the cross-CSE of
+//!   [`generate_csd_multipliers()`](super::generate_csd_multipliers), so the
+//!   two products of an input share sub-expressions; for $|c| = |d|$ the second
+//!   product costs no adders at all. Two adders combine the four products.
+//! * [`ComplexForm::Gauss`] — three constant multipliers
+//!
+//!   $$ k_1 = c\,(a + b), \quad k_2 = (d - c)\,a, \quad k_3 = (c + d)\,b $$
+//!
+//!   give $ac - bd = k_1 - k_3$ and $ad + bc = k_1 + k_2$, trading one
+//!   multiplier for a pre-adder.
+//!
+//! With constant coefficients neither form always wins, so compare
+//! [`ComplexMultiplier::adder_count()`] for the twiddle at hand. Both outputs
+//! carry $F$ fractional bits and are sized so they never overflow.
+//!
+//! Ports and wires are named through [`ComplexOptions::naming()`].
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::netlist::{signed_width, Netlist};
+use super::simulator::{simulate, wrap};
+use super::{
+    check_product_width, csd_multipliers_netlist, csd_value, emit_verilog, frac_bits_text,
+    product_range, quantize_coefficient, CsdMultiplierError, MultiplierOptions, MultiplierSpec,
+    Naming, QuantizedCoefficient,
+};
+
+/// Structure of the generated complex multiplier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ComplexForm {
+    /// Four real products, sharing sub-expressions per input
+    #[default]
+    FourMultiplier,
+    /// Three real products and a pre-adder (Gauss's trick)
+    Gauss,
+}
+
+/// Options for the complex multiplier generator.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::complex::{ComplexForm, ComplexOptions};
+///
+/// let options = ComplexOptions::new()
+///     .coefficient_frac_bits(10)
+///     .form(ComplexForm::Gauss)
+///     .module_name("twiddle");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComplexOptions {
+    naming: Naming,
+    coefficient_frac_bits: usize,
+    form: ComplexForm,
+}
+
+impl Default for ComplexOptions {
+    fn default() -> Self {
+        Self {
+            naming: Naming::default(),
+            coefficient_frac_bits: 12,
+            form: ComplexForm::FourMultiplier,
+        }
+    }
+}
+
+impl ComplexOptions {
+    /// Create default options: four-multiplier form, 12 fractional coefficient bits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the name of the top module (default `csd_complex`).
+    ///
+    /// Multiplier modules are named after it (`{name}_a`, `{name}_k1`, ...).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Verilog module name
+    pub fn module_name(mut self, name: &str) -> Self {
+        self.naming = self.naming.module_name(name);
+        self
+    }
+
+    /// Set the module, port and signal names.
+    ///
+    /// The input ports default to `a` and `b` and the outputs to `re` and
+    /// `im`; an input name `x` gives `x_re` and `x_im`, and likewise for the
+    /// output name. The wire prefix applies to every internal wire and
+    /// instance, and the coefficient suffix appends $c$ and $d$ to the module
+    /// name. Replaces any earlier [`Self::module_name()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - Names to use instead of the defaults
+    pub fn naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Set the fractional word length $c$ and $d$ are quantized to.
+    ///
+    /// # Arguments
+    ///
+    /// * `frac_bits` - Fractional bits $F$ of the coefficients and of the outputs
+    pub fn coefficient_frac_bits(mut self, frac_bits: usize) -> Self {
+        self.coefficient_frac_bits = frac_bits;
+        self
+    }
+
+    /// Set the multiplier structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `form` - [`ComplexForm::FourMultiplier`] (default) or [`ComplexForm::Gauss`]
+    pub fn form(mut self, form: ComplexForm) -> Self {
+        self.form = form;
+        self
+    }
+}
+
+/// Signal feeding a multiplier module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    /// Real part `a`
+    Re,
+    /// Imaginary part `b`
+    Im,
+    /// Pre-added `a + b`
+    Sum,
+}
+
+/// A real product: output `output` of multiplier module `module`.
+#[derive(Debug, Clone)]
+struct Product {
+    name: &'static str,
+    input: Operand,
+    module: usize,
+    output: usize,
+}
+
+/// A generated complex constant multiplier.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::complex::{complex_multiplier, ComplexOptions};
+///
+/// let options = ComplexOptions::new().coefficient_frac_bits(4);
+/// // 0.75 - 0.5j, scaled by 2^4
+/// let m = complex_multiplier(0.75, -0.5, 8, &options).unwrap();
+/// assert_eq!(m.coefficients(), (12, -8));
+/// // (3 + 2j)(0.75 - 0.5j) = 3.25 + 0j
+/// assert_eq!(m.simulate(3, 2), (52, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComplexMultiplier {
+    module_name: String,
+    naming: Naming,
+    input_width: usize,
+    frac_bits: usize,
+    form: ComplexForm,
+    c: QuantizedCoefficient,
+    d: QuantizedCoefficient,
+    output_width: usize,
+    modules: Vec<Netlist>,
+    products: Vec<Product>,
+    /// Products summed into `re` and `im`, with whether they are subtracted
+    re_terms: Vec<(bool, &'static str)>,
+    im_terms: Vec<(bool, &'static str)>,
+}
+
+/// Coefficient spec named `name` for `value`, scaled by $2^F$.
+fn scaled_spec(
+    name: &str,
+    value: i128,
+    frac_bits: usize,
+    input_width: usize,
+) -> Result<MultiplierSpec, CsdMultiplierError> {
+    let q = quantize_coefficient(value as f64 / 2f64.powi(frac_bits as i32), frac_bits)?;
+    Ok(MultiplierSpec {
+        name: name.to_string(),
+        csd: q.csd,
+        input_width,
+        max_power: q.max_power,
+    })
+}
+
+/// Quantize $c + jd$ and build the multiplier datapath.
+///
+/// # Arguments
+///
+/// * `c` - Real part of the constant
+/// * `d` - Imaginary part of the constant
+/// * `input_width` - Bit width of the input parts `a` and `b`
+/// * `options` - Quantization, structure and naming options
+///
+/// # Errors
+///
+/// Returns `CsdMultiplierError::ZeroInputWidth` if `input_width` is 0,
+/// `CsdMultiplierError::CoefficientOutOfRange` if `c` or `d` cannot be quantized
+/// and `CsdMultiplierError::ProductTooWide` if the products need more than 125 bits.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::complex::{complex_multiplier, ComplexForm, ComplexOptions};
+///
+/// let h = std::f64::consts::FRAC_1_SQRT_2;
+/// let four = complex_multiplier(h, -h, 10, &ComplexOptions::new()).unwrap();
+/// let gauss = complex_multiplier(h, -h, 10, &ComplexOptions::new().form(ComplexForm::Gauss))
+///     .unwrap();
+/// assert_eq!(four.simulate(-512, 511), gauss.simulate(-512, 511));
+/// ```
+pub fn complex_multiplier(
+    c: f64,
+    d: f64,
+    input_width: usize,
+    options: &ComplexOptions,
+) -> Result<ComplexMultiplier, CsdMultiplierError> {
+    if input_width == 0 {
+        return Err(CsdMultiplierError::ZeroInputWidth);
+    }
+    let frac_bits = options.coefficient_frac_bits;
+    let qc = quantize_coefficient(c, frac_bits)?;
+    let qd = quantize_coefficient(d, frac_bits)?;
+    check_product_width(input_width + 2 + qc.csd.len().max(qd.csd.len()))?;
+    let (cv, dv) = (csd_value(&qc.csd), csd_value(&qd.csd));
+    let name = &options.naming.module("csd_complex", &[&qc.csd, &qd.csd]);
+    let multiplier_options =
+        MultiplierOptions::new().naming(Naming::new().wire_prefix(&options.naming.wire_prefix));
+
+    let mut modules: Vec<Netlist> = Vec::new();
+    let mut products: Vec<Product> = Vec::new();
+    // One module per input, computing its nonzero products
+    let mut add_module = |suffix: &str,
+                          input: Operand,
+                          width: usize,
+                          factors: &[(&'static str, i128)]|
+     -> Result<(), CsdMultiplierError> {
+        let mut specs = Vec::new();
+        let mut names = Vec::new();
+        for &(product, value) in factors {
+            if value != 0 {
+                specs.push(scaled_spec(product, value, frac_bits, width)?);
+                names.push(product);
+            }
+        }
+        if specs.is_empty() {
+            return Ok(());
+        }
+        let module =
+            csd_multipliers_netlist(&specs, &format!("{}_{}", name, suffix), &multiplier_options)?;
+        for (output, product) in names.into_iter().enumerate() {
+            products.push(Product {
+                name: product,
+                input,
+                module: modules.len(),
+                output,
+            });
+        }
+        modules.push(module);
+        Ok(())
+    };
+
+    let (re_terms, im_terms) = match options.form {
+        ComplexForm::FourMultiplier => {
+            add_module("a", Operand::Re, input_width, &[("ac", cv), ("ad", dv)])?;
+            add_module("b", Operand::Im, input_width, &[("bc", cv), ("bd", dv)])?;
+            (
+                vec![(false, "ac"), (true, "bd")],
+                vec![(false, "ad"), (false, "bc")],
+            )
+        }
+        ComplexForm::Gauss => {
+            add_module("k1", Operand::Sum, input_width + 1, &[("k1", cv)])?;
+            add_module("k2", Operand::Re, input_width, &[("k2", dv - cv)])?;
+            add_module("k3", Operand::Im, input_width, &[("k3", cv + dv)])?;
+            (
+                vec![(false, "k1"), (true, "k3")],
+                vec![(false, "k1"), (false, "k2")],
+            )
+        }
+    };
+    let present = |terms: Vec<(bool, &'static str)>| -> Vec<(bool, &'static str)> {
+        terms
+            .into_iter()
+            .filter(|(_, p)| products.iter().any(|q| q.name == *p))
+            .collect()
+    };
+    let (re_terms, im_terms) = (present(re_terms), present(im_terms));
+
+    // Exact output ranges: ac - bd and ad + bc over all inputs
+    let (c_lo, c_hi) = product_range(cv, input_width);
+    let (d_lo, d_hi) = product_range(dv, input_width);
+    let output_width =
+        signed_width(c_lo - d_hi, c_hi - d_lo).max(signed_width(c_lo + d_lo, c_hi + d_hi));
+
+    Ok(ComplexMultiplier {
+        module_name: name.clone(),
+        naming: options.naming.clone(),
+        input_width,
+        frac_bits,
+        form: options.form,
+        c: qc,
+        d: qd,
+        output_width,
+        modules,
+        products,
+        re_terms,
+        im_terms,
+    })
+}
+
+/// Port names `{name}_re` and `{name}_im`, or the defaults when unnamed.
+fn part_names(name: Option<&str>, re: &str, im: &str) -> (String, String) {
+    match name {
+        Some(name) => (format!("{}_re", name), format!("{}_im", name)),
+        None => (re.to_string(), im.to_string()),
+    }
+}
+
+/// Verilog text of a signed sum of named signals, `0` when empty.
+fn sum_text(terms: &[(bool, String)]) -> String {
+    let mut text = String::new();
+    for (i, (negate, name)) in terms.iter().enumerate() {
+        match (i, negate) {
+            (0, false) => text.push_str(name),
+            (0, true) => write!(text, "-{}", name).unwrap(),
+            (_, false) => write!(text, " + {}", name).unwrap(),
+            (_, true) => write!(text, " - {}", name).unwrap(),
+        }
+    }
+    if text.is_empty() {
+        text.push('0');
+    }
+    text
+}
+
+impl ComplexMultiplier {
+    /// Name of the top module.
+    pub fn module_name(&self) -> &str {
+        &self.module_name
+    }
+
+    /// Names of the real and imaginary input ports (default `a` and `b`).
+    pub fn input_names(&self) -> (String, String) {
+        part_names(self.naming.input_name.as_deref(), "a", "b")
+    }
+
+    /// Names of the real and imaginary output ports (default `re` and `im`).
+    pub fn output_names(&self) -> (String, String) {
+        part_names(self.naming.output_name.as_deref(), "re", "im")
+    }
+
+    /// Bit width of the input parts.
+    pub fn input_width(&self) -> usize {
+        self.input_width
+    }
+
+    /// Bit width of the output parts, sized so they can never overflow.
+    pub fn output_width(&self) -> usize {
+        self.output_width
+    }
+
+    /// Fractional bits of the coefficients and of the outputs.
+    pub fn frac_bits(&self) -> usize {
+        self.frac_bits
+    }
+
+    /// Structure of the multiplier.
+    pub fn form(&self) -> ComplexForm {
+        self.form
+    }
+
+    /// Quantized coefficients $(c, d)$ as integers scaled by $2^F$.
+    pub fn coefficients(&self) -> (i128, i128) {
+        (csd_value(&self.c.csd), csd_value(&self.d.csd))
+    }
+
+    /// Quantized real and imaginary parts of the constant.
+    pub fn quantized(&self) -> (&QuantizedCoefficient, &QuantizedCoefficient) {
+        (&self.c, &self.d)
+    }
+
+    /// Multiplier modules instantiated by the top module.
+    pub fn multipliers(&self) -> &[Netlist] {
+        &self.modules
+    }
+
+    /// Total adders and subtractors, including the pre-adder and output adders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::complex::{complex_multiplier, ComplexForm, ComplexOptions};
+    ///
+    /// let options = ComplexOptions::new().coefficient_frac_bits(8);
+    /// let four = complex_multiplier(0.6, 0.8, 8, &options).unwrap();
+    /// let gauss = complex_multiplier(0.6, 0.8, 8, &options.form(ComplexForm::Gauss)).unwrap();
+    /// println!("four: {} adders, Gauss: {} adders", four.adder_count(), gauss.adder_count());
+    /// ```
+    pub fn adder_count(&self) -> usize {
+        let multiplier_adders: usize = self.modules.iter().map(|m| m.adder_count()).sum();
+        let pre_adder = self.products.iter().any(|p| p.input == Operand::Sum) as usize;
+        multiplier_adders
+            + pre_adder
+            + self.re_terms.len().saturating_sub(1)
+            + self.im_terms.len().saturating_sub(1)
+    }
+
+    /// Simulate the generated hardware for the input $a + jb$.
+    ///
+    /// Returns `(re, im)` as integers scaled by $2^F$. The inputs are wrapped
+    /// to the input width and every product wraps at its declared width.
+    pub fn simulate(&self, a: i128, b: i128) -> (i128, i128) {
+        let a = wrap(a, self.input_width);
+        let b = wrap(b, self.input_width);
+        let mut outputs: HashMap<usize, Vec<i128>> = HashMap::new();
+        let mut value = |name: &str| -> i128 {
+            let p = self.products.iter().find(|p| p.name == name).unwrap();
+            let outs = outputs.entry(p.module).or_insert_with(|| {
+                let x = match p.input {
+                    Operand::Re => a,
+                    Operand::Im => b,
+                    Operand::Sum => a + b,
+                };
+                simulate(&self.modules[p.module], &[x])
+            });
+            outs[p.output]
+        };
+        let mut sum = |terms: &[(bool, &'static str)]| {
+            let total = terms.iter().fold(0i128, |acc, &(negate, name)| {
+                let v = value(name);
+                if negate {
+                    acc - v
+                } else {
+                    acc + v
+                }
+            });
+            wrap(total, self.output_width)
+        };
+        let re = sum(&self.re_terms);
+        let im = sum(&self.im_terms);
+        (re, im)
+    }
+
+    /// Print the multiplier modules followed by the top module as Verilog-2001.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::complex::{complex_multiplier, ComplexForm, ComplexOptions};
+    ///
+    /// let options = ComplexOptions::new().coefficient_frac_bits(4).form(ComplexForm::Gauss);
+    /// let v = complex_multiplier(0.75, -0.5, 8, &options).unwrap().verilog();
+    /// assert!(v.contains("module csd_complex_k1 ("));
+    /// assert!(v.contains("    wire signed [8:0] s = a + b;"));
+    /// assert!(v.contains("    assign re = k1 - k3;"));
+    /// assert!(v.contains("    assign im = k1 + k2;"));
+    /// ```
+    pub fn verilog(&self) -> String {
+        let mut verilog = String::new();
+        for netlist in &self.modules {
+            verilog.push_str(&emit_verilog(netlist));
+        }
+
+        // --- module header ---
+        let w = self.input_width;
+        let (a, b) = self.input_names();
+        let (re, im) = self.output_names();
+        let wire = |name: &str| self.naming.wire(name);
+        let fraction = match self.frac_bits {
+            0 => String::new(),
+            f => format!(" ({})", frac_bits_text(f)),
+        };
+        writeln!(verilog).unwrap();
+        writeln!(verilog, "module {} (", self.module_name).unwrap();
+        writeln!(
+            verilog,
+            "    input signed [{}:0] {},      // Real part of the input",
+            w - 1,
+            a
+        )
+        .unwrap();
+        writeln!(
+            verilog,
+            "    input signed [{}:0] {},      // Imaginary part of the input",
+            w - 1,
+            b
+        )
+        .unwrap();
+        writeln!(
+            verilog,
+            "    output signed [{}:0] {},    // Real part of the product{}",
+            self.output_width - 1,
+            re,
+            fraction
+        )
+        .unwrap();
+        writeln!(
+            verilog,
+            "    output signed [{}:0] {}     // Imaginary part of the product{}",
+            self.output_width - 1,
+            im,
+            fraction
+        )
+        .unwrap();
+        writeln!(verilog, ");").unwrap();
+        writeln!(verilog).unwrap();
+        writeln!(
+            verilog,
+            "    // (a + jb) * (c + jd), c = {} ({}), d = {} ({})",
+            self.c.value, self.c.csd, self.d.value, self.d.csd
+        )
+        .unwrap();
+
+        if self.products.iter().any(|p| p.input == Operand::Sum) {
+            writeln!(verilog).unwrap();
+            writeln!(
+                verilog,
+                "    // Gauss's trick: k1 = c(a + b), k2 = (d - c)a, k3 = (c + d)b"
+            )
+            .unwrap();
+            writeln!(
+                verilog,
+                "    wire signed [{}:0] {} = {} + {};",
+                w,
+                wire("s"),
+                a,
+                b
+            )
+            .unwrap();
+        }
+
+        if !self.modules.is_empty() {
+            writeln!(verilog).unwrap();
+            let heading = match self.form {
+                ComplexForm::FourMultiplier => "Products of each input, sharing sub-expressions",
+                ComplexForm::Gauss => "Real products",
+            };
+            writeln!(verilog, "    // {}", heading).unwrap();
+            for p in &self.products {
+                let port = &self.modules[p.module].outputs[p.output].port;
+                writeln!(
+                    verilog,
+                    "    wire signed [{}:0] {};",
+                    port.width - 1,
+                    wire(p.name)
+                )
+                .unwrap();
+            }
+            for (m, module) in self.modules.iter().enumerate() {
+                let input = self.products.iter().find(|p| p.module == m).unwrap().input;
+                let signal = match input {
+                    Operand::Re => a.clone(),
+                    Operand::Im => b.clone(),
+                    Operand::Sum => wire("s"),
+                };
+                let suffix = &module.module_name[self.module_name.len() + 1..];
+                let instance = wire(&format!("mult_{}", suffix));
+                writeln!(verilog, "    {} {} (", module.module_name, instance).unwrap();
+                writeln!(verilog, "        .{}({}),", module.inputs[0].name, signal).unwrap();
+                let outputs: Vec<&Product> =
+                    self.products.iter().filter(|p| p.module == m).collect();
+                for (i, p) in outputs.iter().enumerate() {
+                    let sep = if i + 1 == outputs.len() { "" } else { "," };
+                    let port = &module.outputs[p.output].port.name;
+                    writeln!(verilog, "        .{}({}){}", port, wire(p.name), sep).unwrap();
+                }
+                writeln!(verilog, "    );").unwrap();
+            }
+        }
+
+        writeln!(verilog).unwrap();
+        let terms = |terms: &[(bool, &str)]| -> Vec<(bool, String)> {
+            terms.iter().map(|&(negate, p)| (negate, wire(p))).collect()
+        };
+        writeln!(
+            verilog,
+            "    assign {} = {};",
+            re,
+            sum_text(&terms(&self.re_terms))
+        )
+        .unwrap();
+        writeln!(
+            verilog,
+            "    assign {} = {};",
+            im,
+            sum_text(&terms(&self.im_terms))
+        )
+        .unwrap();
+        writeln!(verilog, "endmodule").unwrap();
+        verilog
+    }
+}
+
+/// Generate Verilog for a complex constant multiplier.
+///
+/// Shorthand for [`complex_multiplier()`] followed by [`ComplexMultiplier::verilog()`].
+///
+/// # Errors
+///
+/// Same as [`complex_multiplier()`].
+pub fn generate_complex_multiplier(
+    c: f64,
+    d: f64,
+    input_width: usize,
+    options: &ComplexOptions,
+) -> Result<String, CsdMultiplierError> {
+    Ok(complex_multiplier(c, d, input_width, options)?.verilog())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_exact(m: &ComplexMultiplier) {
+        let (c, d) = m.coefficients();
+        let lo = -(1i128 << (m.input_width() - 1));
+        let hi = (1i128 << (m.input_width() - 1)) - 1;
+        for a in (lo..=hi).step_by(3).chain([hi]) {
+            for b in (lo..=hi).step_by(5).chain([lo, hi]) {
+                assert_eq!(
+                    m.simulate(a, b),
+                    (a * c - b * d, a * d + b * c),
+                    "a {} b {}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_exact_both_forms() {
+        let twiddles = [
+            (
+                std::f64::consts::FRAC_1_SQRT_2,
+                -std::f64::consts::FRAC_1_SQRT_2,
+            ),
+            (0.9238795325112867, -0.3826834323650898),
+            (0.0, -1.0),
+            (1.0, 0.0),
+            (-0.6, 0.8),
+        ];
+        for &(c, d) in &twiddles {
+            for form in [ComplexForm::FourMultiplier, ComplexForm::Gauss] {
+                let options = ComplexOptions::new().coefficient_frac_bits(10).form(form);
+                check_exact(&complex_multiplier(c, d, 8, &options).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_shares_equal_magnitudes() {
+        // |c| = |d|: each input's second product is folded from the first
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let options = ComplexOptions::new().coefficient_frac_bits(8);
+        let m = complex_multiplier(h, -h, 8, &options).unwrap();
+        let c_adders = m.multipliers()[0].adder_count();
+        assert_eq!(m.adder_count(), 2 * c_adders + 2);
+        let v = m.verilog();
+        assert!(v.contains("module csd_complex_a ("));
+        assert!(v.contains(
+            "    csd_complex_b mult_b (\n        .x(b),\n        .bc(bc),\n        .bd(bd)\n    );"
+        ));
+        assert!(v.contains("    assign re = ac - bd;"));
+        assert!(v.contains("    assign im = ad + bc;"));
+    }
+
+    #[test]
+    fn test_complex_zero_products() {
+        // c + jd = 1: the imaginary products vanish
+        let options = ComplexOptions::new().coefficient_frac_bits(2);
+        let m = complex_multiplier(1.0, 0.0, 8, &options).unwrap();
+        let v = m.verilog();
+        assert!(v.contains("    assign re = ac;"));
+        assert!(v.contains("    assign im = bc;"));
+        assert_eq!(m.simulate(-3, 5), (-12, 20));
+        let gauss = complex_multiplier(1.0, 0.0, 8, &options.form(ComplexForm::Gauss)).unwrap();
+        assert_eq!(gauss.simulate(-3, 5), (-12, 20));
+        assert_eq!(
+            complex_multiplier(1.0, 0.0, 0, &ComplexOptions::new()).unwrap_err(),
+            CsdMultiplierError::ZeroInputWidth
+        );
+    }
+
+    #[test]
+    fn test_complex_naming() {
+        let naming = Naming::new()
+            .module_name("twiddle")
+            .input_name("z")
+            .output_name("p")
+            .wire_prefix("u0")
+            .coefficient_suffix(true);
+        for form in [ComplexForm::FourMultiplier, ComplexForm::Gauss] {
+            let options = ComplexOptions::new()
+                .coefficient_frac_bits(4)
+                .form(form)
+                .naming(naming.clone());
+            let m = complex_multiplier(0.75, -0.5, 8, &options).unwrap();
+            assert_eq!(m.module_name(), "twiddle_0p75_m0p5");
+            assert_eq!(m.input_names(), ("z_re".to_string(), "z_im".to_string()));
+            let v = m.verilog();
+            for name in [
+                "input signed [7:0] z_re,",
+                "output signed",
+                "p_im ",
+                "assign p_re = u0_",
+            ] {
+                assert!(v.contains(name), "{} missing:\n{}", name, v);
+            }
+            assert!(
+                v.contains("twiddle_0p75_m0p5_") && v.contains(" u0_mult_"),
+                "{}",
+                v
+            );
+            let top = &v[v.rfind("module ").unwrap()..];
+            for name in [
+                "(a)",
+                "(b)",
+                " s ",
+                "assign re",
+                "assign im",
+                " mult_",
+                "(ac)",
+                "(k1)",
+            ] {
+                assert!(!top.contains(name), "{} left:\n{}", name, top);
+            }
+            check_exact(&m);
+        }
+    }
+}
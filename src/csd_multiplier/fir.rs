@@ -0,0 +1,1048 @@
+//! FIR filter generator.
+//!
+//! Quantizes real tap values $h_k$ to CSD coefficients with $F$ fractional bits
+//! and builds a clocked filter
+//!
+//! $$ y[n] = \sum_{k=0}^{N-1} h_k \cdot x[n-k] $$
+//!
+//! from the shift-add multipliers of the parent module plus a delay/accumulate
+//! chain:
+//!
+//! * [`FirForm::Transposed`] — one multiple-constant-multiplication (MCM) block
+//!   computes every distinct $h_k \cdot x[n]$ with cross-CSE, and a register
+//!   chain $z_k \leftarrow z_{k+1} + h_k x$ accumulates the products. Taps that
+//!   are equal or negated (as in linear-phase filters) share one product.
+//! * [`FirForm::Direct`] — a tapped delay line feeds one multiplier per tap and
+//!   an adder sums the products. For symmetric (or antisymmetric) taps the
+//!   mirrored samples are pre-added (or pre-subtracted) first, halving the
+//!   number of multipliers.
+//!
+//! Both forms have the same cycle behavior: `y` is the output for the current
+//! sample `x`, and each rising clock edge shifts `x` into the filter state.
+//! Every register and output is sized from its exact value range, so the
+//! filter never overflows. [`FirFilter::simulate()`] models the generated
+//! hardware bit-accurately and
+//! [`emit_fir_testbench()`](super::testbench::emit_fir_testbench) writes a
+//! matching self-checking testbench.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::netlist::{signed_width, Netlist};
+use super::simulator::{simulate, wrap};
+use super::{
+    check_product_width, csd_multiplier_netlist, csd_multipliers_netlist, csd_value, emit_verilog,
+    quantize_coefficient, AdderStructure, CsdMultiplierError, MultiplierOptions, MultiplierSpec,
+    Naming, QuantizedCoefficient,
+};
+
+/// Filter structure of the generated FIR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FirForm {
+    /// Tapped delay line, multipliers and a final adder
+    Direct,
+    /// Multiplier block on the input followed by a register/adder chain
+    #[default]
+    Transposed,
+}
+
+/// Symmetry of the quantized impulse response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirSymmetry {
+    /// No symmetry
+    None,
+    /// $h_k = h_{N-1-k}$ (linear phase, types I and II)
+    Symmetric,
+    /// $h_k = -h_{N-1-k}$ (linear phase, types III and IV)
+    Antisymmetric,
+}
+
+/// Options for the FIR filter generator.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::fir::{FirForm, FirOptions};
+///
+/// let options = FirOptions::new()
+///     .coefficient_frac_bits(10)
+///     .form(FirForm::Direct)
+///     .module_name("lowpass");
+/// ```
+#[derive(Debug, Clone)]
+pub struct FirOptions {
+    naming: Naming,
+    coefficient_frac_bits: usize,
+    form: FirForm,
+    exploit_symmetry: bool,
+    adder_structure: AdderStructure,
+}
+
+impl Default for FirOptions {
+    fn default() -> Self {
+        Self {
+            naming: Naming::default(),
+            coefficient_frac_bits: 12,
+            form: FirForm::Transposed,
+            exploit_symmetry: true,
+            adder_structure: AdderStructure::Chain,
+        }
+    }
+}
+
+impl FirOptions {
+    /// Create default options: transposed form, 12 fractional coefficient bits, symmetry on.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the name of the filter module (default `csd_fir`).
+    ///
+    /// Multiplier modules are named after it (`{name}_mcm`, `{name}_c0`, ...).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Verilog module name
+    pub fn module_name(mut self, name: &str) -> Self {
+        self.naming = self.naming.module_name(name);
+        self
+    }
+
+    /// Set the module, port and signal names.
+    ///
+    /// The input and output ports default to `x` and `y`; the wire prefix
+    /// applies to every internal register and wire, and the coefficient
+    /// suffix appends all taps to the module name. Replaces any earlier
+    /// [`Self::module_name()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - Names to use instead of the defaults
+    pub fn naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Set the fractional word length the taps are quantized to.
+    ///
+    /// # Arguments
+    ///
+    /// * `frac_bits` - Fractional bits $F$ of every coefficient and of the output
+    pub fn coefficient_frac_bits(mut self, frac_bits: usize) -> Self {
+        self.coefficient_frac_bits = frac_bits;
+        self
+    }
+
+    /// Set the filter structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `form` - [`FirForm::Transposed`] (default) or [`FirForm::Direct`]
+    pub fn form(mut self, form: FirForm) -> Self {
+        self.form = form;
+        self
+    }
+
+    /// Enable or disable sharing between symmetric taps.
+    ///
+    /// # Arguments
+    ///
+    /// * `exploit` - Share products of equal/negated taps (transposed) or
+    ///   pre-add mirrored samples (direct); `false` builds one multiplier per tap
+    ///   and turns off [`MultiplierOptions::share_patterns()`]
+    pub fn exploit_symmetry(mut self, exploit: bool) -> Self {
+        self.exploit_symmetry = exploit;
+        self
+    }
+
+    /// Set how partial products are summed inside the multipliers.
+    ///
+    /// # Arguments
+    ///
+    /// * `structure` - [`AdderStructure::Chain`] or [`AdderStructure::Tree`]
+    pub fn adder_structure(mut self, structure: AdderStructure) -> Self {
+        self.adder_structure = structure;
+        self
+    }
+}
+
+/// Tap of a transposed filter: MCM output index and whether it is subtracted.
+type SharedProduct = (usize, bool);
+
+/// Multiplier instance of a direct-form filter.
+#[derive(Debug, Clone)]
+struct DirectProduct {
+    /// Delay line taps feeding the multiplier (two when pre-added)
+    taps: Vec<usize>,
+    /// Pre-subtract the mirrored sample instead of adding it
+    subtract: bool,
+    /// Index of the multiplier module
+    module: usize,
+}
+
+/// Datapath of a generated filter.
+#[derive(Debug, Clone)]
+enum Structure {
+    Transposed {
+        /// Multiplier block, `None` when every tap is zero
+        mcm: Option<Netlist>,
+        /// Product realizing each tap, `None` for zero taps
+        taps: Vec<Option<SharedProduct>>,
+        /// Width of accumulator `z_k` (index 0 unused), `None` if always zero
+        chain: Vec<Option<usize>>,
+    },
+    Direct {
+        /// One module per distinct (coefficient, input width)
+        modules: Vec<Netlist>,
+        products: Vec<DirectProduct>,
+    },
+}
+
+/// A generated FIR filter.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::fir::{fir_filter, FirOptions, FirSymmetry};
+///
+/// let filter = fir_filter(&[0.25, 0.5, 0.25], 8, &FirOptions::new()).unwrap();
+/// assert_eq!(filter.symmetry(), FirSymmetry::Symmetric);
+/// assert_eq!(filter.coefficients(), vec![1024, 2048, 1024]);
+/// // Impulse response, scaled by 2^12
+/// assert_eq!(filter.simulate(&[1, 0, 0, 0]), vec![1024, 2048, 1024, 0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FirFilter {
+    module_name: String,
+    naming: Naming,
+    input_width: usize,
+    frac_bits: usize,
+    taps: Vec<QuantizedCoefficient>,
+    symmetry: FirSymmetry,
+    output_width: usize,
+    structure: Structure,
+}
+
+/// Detect the symmetry of integer taps.
+fn detect_symmetry(values: &[i128]) -> FirSymmetry {
+    let n = values.len();
+    if (0..n).all(|k| values[k] == values[n - 1 - k]) {
+        FirSymmetry::Symmetric
+    } else if (0..n).all(|k| values[k] == -values[n - 1 - k]) {
+        FirSymmetry::Antisymmetric
+    } else {
+        FirSymmetry::None
+    }
+}
+
+/// Sum of value ranges.
+fn add_range(a: (i128, i128), b: (i128, i128)) -> (i128, i128) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+/// Verilog text of a signed sum of named signals, `0` when empty.
+fn sum_text(terms: &[(bool, String)]) -> String {
+    let mut text = String::new();
+    for (i, (negate, name)) in terms.iter().enumerate() {
+        match (i, negate) {
+            (0, false) => text.push_str(name),
+            (0, true) => write!(text, "-{}", name).unwrap(),
+            (_, false) => write!(text, " + {}", name).unwrap(),
+            (_, true) => write!(text, " - {}", name).unwrap(),
+        }
+    }
+    if text.is_empty() {
+        text.push('0');
+    }
+    text
+}
+
+/// Quantize `taps` and build the filter datapath.
+///
+/// # Arguments
+///
+/// * `taps` - Impulse response $h_0, \ldots, h_{N-1}$
+/// * `input_width` - Bit width of the input sample x
+/// * `options` - Quantization and structure options
+///
+/// # Errors
+///
+/// Returns `CsdMultiplierError::EmptyCoefficients` if `taps` is empty,
+/// `CsdMultiplierError::ZeroInputWidth` if `input_width` is 0,
+/// `CsdMultiplierError::CoefficientOutOfRange` if a tap cannot be quantized
+/// and `CsdMultiplierError::ProductTooWide` if the output needs more than 125 bits.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::fir::{fir_filter, FirForm, FirOptions};
+///
+/// let taps = [-0.0625, 0.3, 0.6, 0.3, -0.0625];
+/// let transposed = fir_filter(&taps, 10, &FirOptions::new()).unwrap();
+/// let direct = fir_filter(&taps, 10, &FirOptions::new().form(FirForm::Direct)).unwrap();
+/// let x = [100, -512, 511, 3, 0, 0, 0, 0];
+/// assert_eq!(transposed.simulate(&x), direct.simulate(&x));
+/// ```
+pub fn fir_filter(
+    taps: &[f64],
+    input_width: usize,
+    options: &FirOptions,
+) -> Result<FirFilter, CsdMultiplierError> {
+    if taps.is_empty() {
+        return Err(CsdMultiplierError::EmptyCoefficients);
+    }
+    if input_width == 0 {
+        return Err(CsdMultiplierError::ZeroInputWidth);
+    }
+    let frac_bits = options.coefficient_frac_bits;
+    let quantized = taps
+        .iter()
+        .map(|&h| quantize_coefficient(h, frac_bits))
+        .collect::<Result<Vec<_>, _>>()?;
+    let digits = quantized.iter().map(|q| q.csd.len()).max().unwrap_or(0);
+    let sum_bits = (usize::BITS - taps.len().leading_zeros()) as usize;
+    check_product_width(input_width + digits + sum_bits + 1)?;
+    let values: Vec<i128> = quantized.iter().map(|q| csd_value(&q.csd)).collect();
+    let symmetry = detect_symmetry(&values);
+    let multiplier_options = MultiplierOptions::new()
+        .adder_structure(options.adder_structure)
+        .share_patterns(options.exploit_symmetry)
+        .naming(Naming::new().wire_prefix(&options.naming.wire_prefix));
+    let n = taps.len();
+    let csds: Vec<&str> = quantized.iter().map(|q| q.csd.as_str()).collect();
+    let name = &options.naming.module("csd_fir", &csds);
+
+    let (structure, y_range) = match options.form {
+        FirForm::Transposed => {
+            // Distinct products; with sharing, a tap reuses the product of an
+            // equal or negated earlier tap
+            let mut specs: Vec<MultiplierSpec> = Vec::new();
+            let mut spec_values: Vec<i128> = Vec::new();
+            let mut tap_products = Vec::with_capacity(n);
+            for (q, &c) in quantized.iter().zip(&values) {
+                if c == 0 {
+                    tap_products.push(None);
+                    continue;
+                }
+                let shared = if options.exploit_symmetry {
+                    spec_values.iter().enumerate().find_map(|(j, &v)| {
+                        if v == c {
+                            Some((j, false))
+                        } else if v == -c {
+                            Some((j, true))
+                        } else {
+                            None
+                        }
+                    })
+                } else {
+                    None
+                };
+                let product = shared.unwrap_or_else(|| {
+                    specs.push(MultiplierSpec {
+                        name: format!("p{}", specs.len()),
+                        csd: q.csd.clone(),
+                        input_width,
+                        max_power: q.max_power,
+                    });
+                    spec_values.push(c);
+                    (specs.len() - 1, false)
+                });
+                tap_products.push(Some(product));
+            }
+            let mcm = if specs.is_empty() {
+                None
+            } else {
+                Some(csd_multipliers_netlist(
+                    &specs,
+                    &format!("{}_mcm", name),
+                    &multiplier_options,
+                )?)
+            };
+            let product_ranges = mcm.as_ref().map_or(Vec::new(), |m| m.output_ranges());
+            let tap_range = |k: usize| match tap_products[k] {
+                None => (0, 0),
+                Some((j, false)) => product_ranges[j],
+                Some((j, true)) => (-product_ranges[j].1, -product_ranges[j].0),
+            };
+            // z_k holds the sum of taps k..N-1 applied to past samples
+            let mut chain = vec![None; n];
+            let mut range = (0, 0);
+            for k in (1..n).rev() {
+                range = add_range(range, tap_range(k));
+                if range != (0, 0) {
+                    chain[k] = Some(signed_width(range.0, range.1));
+                }
+            }
+            let y_range = add_range(range, tap_range(0));
+            (
+                Structure::Transposed {
+                    mcm,
+                    taps: tap_products,
+                    chain,
+                },
+                y_range,
+            )
+        }
+        FirForm::Direct => {
+            let groups: Vec<(Vec<usize>, bool)> = match symmetry {
+                FirSymmetry::None => (0..n).map(|k| (vec![k], false)).collect(),
+                _ if !options.exploit_symmetry => (0..n).map(|k| (vec![k], false)).collect(),
+                s => (0..(n + 1) / 2)
+                    .map(|k| {
+                        if k == n - 1 - k {
+                            (vec![k], false)
+                        } else {
+                            (vec![k, n - 1 - k], s == FirSymmetry::Antisymmetric)
+                        }
+                    })
+                    .collect(),
+            };
+            let mut modules: Vec<Netlist> = Vec::new();
+            let mut module_of: HashMap<(String, usize), usize> = HashMap::new();
+            let mut products = Vec::new();
+            let mut y_range = (0, 0);
+            for (group, subtract) in groups {
+                let q = &quantized[group[0]];
+                if values[group[0]] == 0 {
+                    continue;
+                }
+                // Pre-adding two samples needs one more input bit
+                let width = input_width + group.len() - 1;
+                let key = (q.csd.clone(), width);
+                let module = match module_of.get(&key) {
+                    Some(&m) => m,
+                    None => {
+                        let mut netlist = csd_multiplier_netlist(
+                            &q.csd,
+                            width,
+                            q.max_power,
+                            &multiplier_options,
+                        )?;
+                        netlist.module_name = format!("{}_c{}", name, modules.len());
+                        modules.push(netlist);
+                        module_of.insert(key, modules.len() - 1);
+                        modules.len() - 1
+                    }
+                };
+                y_range = add_range(y_range, modules[module].output_ranges()[0]);
+                products.push(DirectProduct {
+                    taps: group,
+                    subtract,
+                    module,
+                });
+            }
+            (Structure::Direct { modules, products }, y_range)
+        }
+    };
+
+    Ok(FirFilter {
+        module_name: name.clone(),
+        naming: options.naming.clone(),
+        input_width,
+        frac_bits,
+        taps: quantized,
+        symmetry,
+        output_width: signed_width(y_range.0, y_range.1),
+        structure,
+    })
+}
+
+impl FirFilter {
+    /// Name of the filter module.
+    pub fn module_name(&self) -> &str {
+        &self.module_name
+    }
+
+    /// Name of the input port (default `x`).
+    pub fn input_name(&self) -> &str {
+        self.naming.input()
+    }
+
+    /// Name of the output port (default `y`).
+    pub fn output_name(&self) -> &str {
+        self.naming.output("y")
+    }
+
+    /// Name of internal signal `{base}{index}`, with the wire prefix applied.
+    fn signal(&self, base: &str, index: usize) -> String {
+        self.naming.wire(&format!("{}{}", base, index))
+    }
+
+    /// Name of delay line tap `k` (the input itself for `k == 0`).
+    fn delay(&self, k: usize) -> String {
+        if k == 0 {
+            self.input_name().to_string()
+        } else {
+            self.signal("d", k)
+        }
+    }
+
+    /// Bit width of the input sample `x`.
+    pub fn input_width(&self) -> usize {
+        self.input_width
+    }
+
+    /// Bit width of the output `y`, sized so it can never overflow.
+    pub fn output_width(&self) -> usize {
+        self.output_width
+    }
+
+    /// Fractional bits of the coefficients and of the output `y`.
+    pub fn frac_bits(&self) -> usize {
+        self.frac_bits
+    }
+
+    /// Quantized taps $h_0, \ldots, h_{N-1}$.
+    pub fn taps(&self) -> &[QuantizedCoefficient] {
+        &self.taps
+    }
+
+    /// Quantized taps as integers scaled by $2^F$.
+    pub fn coefficients(&self) -> Vec<i128> {
+        self.taps.iter().map(|q| csd_value(&q.csd)).collect()
+    }
+
+    /// Symmetry of the quantized taps.
+    pub fn symmetry(&self) -> FirSymmetry {
+        self.symmetry
+    }
+
+    /// Multiplier modules instantiated by the filter.
+    pub fn multipliers(&self) -> Vec<&Netlist> {
+        match &self.structure {
+            Structure::Transposed { mcm, .. } => mcm.iter().collect(),
+            Structure::Direct { modules, .. } => modules.iter().collect(),
+        }
+    }
+
+    /// Total adders and subtractors, counting each multiplier instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::fir::{fir_filter, FirForm, FirOptions};
+    ///
+    /// let taps = [0.1, -0.3, 0.7, -0.3, 0.1];
+    /// let options = FirOptions::new().form(FirForm::Direct);
+    /// let shared = fir_filter(&taps, 8, &options).unwrap();
+    /// let unshared = fir_filter(&taps, 8, &options.exploit_symmetry(false)).unwrap();
+    /// assert!(shared.adder_count() < unshared.adder_count());
+    /// ```
+    pub fn adder_count(&self) -> usize {
+        match &self.structure {
+            Structure::Transposed { mcm, taps, chain } => {
+                let mcm_adders = mcm.as_ref().map_or(0, |m| m.adder_count());
+                // Each register (and y) adds its product to the next register
+                let chain_adders = (0..taps.len())
+                    .filter(|&k| taps[k].is_some() && matches!(chain.get(k + 1), Some(Some(_))))
+                    .count();
+                mcm_adders + chain_adders
+            }
+            Structure::Direct { modules, products } => {
+                let pre_adders = products.iter().filter(|p| p.taps.len() == 2).count();
+                let multiplier_adders: usize = products
+                    .iter()
+                    .map(|p| modules[p.module].adder_count())
+                    .sum();
+                pre_adders + multiplier_adders + products.len().saturating_sub(1)
+            }
+        }
+    }
+
+    /// Simulate the generated hardware on a sequence of input samples.
+    ///
+    /// Starts from the reset state and returns `y` for every sample, as an
+    /// integer scaled by $2^F$. Samples are wrapped to the input width, and
+    /// every register and product wraps at its declared width.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Input samples $x[0], x[1], \ldots$
+    pub fn simulate(&self, samples: &[i128]) -> Vec<i128> {
+        let n = self.taps.len();
+        let mut outputs = Vec::with_capacity(samples.len());
+        match &self.structure {
+            Structure::Transposed { mcm, taps, chain } => {
+                let mut z = vec![0i128; n + 1];
+                for &sample in samples {
+                    let products = mcm.as_ref().map_or(Vec::new(), |m| simulate(m, &[sample]));
+                    let tap = |k: usize| match taps[k] {
+                        None => 0,
+                        Some((j, false)) => products[j],
+                        Some((j, true)) => -products[j],
+                    };
+                    outputs.push(wrap(z[1] + tap(0), self.output_width));
+                    // Ascending k reads z[k + 1] before it is updated
+                    for k in 1..n {
+                        z[k] = match chain[k] {
+                            Some(width) => wrap(z[k + 1] + tap(k), width),
+                            None => 0,
+                        };
+                    }
+                }
+            }
+            Structure::Direct { modules, products } => {
+                let mut delay = vec![0i128; n];
+                for &sample in samples {
+                    delay.rotate_right(1);
+                    delay[0] = wrap(sample, self.input_width);
+                    let y = products.iter().fold(0i128, |acc, p| {
+                        let module = &modules[p.module];
+                        let input = match (p.taps.as_slice(), p.subtract) {
+                            ([a, b], false) => delay[*a] + delay[*b],
+                            ([a, b], true) => delay[*a] - delay[*b],
+                            (taps, _) => delay[taps[0]],
+                        };
+                        acc + simulate(module, &[input])[0]
+                    });
+                    outputs.push(wrap(y, self.output_width));
+                }
+            }
+        }
+        outputs
+    }
+
+    /// Tap comment such as `h[1] = h[3] = 0.25`.
+    fn tap_comment(&self, taps: &[usize]) -> String {
+        let mut comment = String::new();
+        for &k in taps {
+            write!(comment, "h[{}] = ", k).unwrap();
+        }
+        write!(comment, "{}", self.taps[taps[0]].value).unwrap();
+        comment
+    }
+
+    /// Print the multiplier modules followed by the filter module as Verilog-2001.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::fir::{fir_filter, FirOptions};
+    ///
+    /// let filter = fir_filter(&[0.25, 0.5, 0.25], 8, &FirOptions::new()).unwrap();
+    /// let v = filter.verilog();
+    /// assert!(v.contains("module csd_fir_mcm ("));
+    /// assert!(v.contains("            z1 <= z2 + p1; // h[1] = 0.5"));
+    /// assert!(v.contains("    assign y = z1 + p0;"));
+    /// ```
+    pub fn verilog(&self) -> String {
+        let mut verilog = String::new();
+        for netlist in self.multipliers() {
+            verilog.push_str(&emit_verilog(netlist));
+        }
+
+        // --- module header ---
+        let w = self.input_width;
+        writeln!(verilog).unwrap();
+        writeln!(verilog, "module {} (", self.module_name).unwrap();
+        writeln!(verilog, "    input clk,").unwrap();
+        writeln!(
+            verilog,
+            "    input rst,      // Synchronous reset, active high"
+        )
+        .unwrap();
+        writeln!(
+            verilog,
+            "    input signed [{}:0] {},      // Input sample",
+            w - 1,
+            self.input_name()
+        )
+        .unwrap();
+        writeln!(
+            verilog,
+            "    output signed [{}:0] {} // Filter output ({})",
+            self.output_width - 1,
+            self.output_name(),
+            super::frac_bits_text(self.frac_bits)
+        )
+        .unwrap();
+        writeln!(verilog, ");").unwrap();
+
+        let instance =
+            |verilog: &mut String, netlist: &Netlist, label: &str, ports: &[(&str, String)]| {
+                writeln!(verilog, "    {} {} (", netlist.module_name, label).unwrap();
+                for (i, (port, signal)) in ports.iter().enumerate() {
+                    let sep = if i + 1 == ports.len() { "" } else { "," };
+                    writeln!(verilog, "        .{}({}){}", port, signal, sep).unwrap();
+                }
+                writeln!(verilog, "    );").unwrap();
+            };
+
+        let y_terms = match &self.structure {
+            Structure::Transposed { mcm, taps, chain } => {
+                if let Some(mcm) = mcm {
+                    writeln!(verilog).unwrap();
+                    writeln!(
+                        verilog,
+                        "    // Multiplier block: one product per distinct coefficient"
+                    )
+                    .unwrap();
+                    for (j, output) in mcm.outputs.iter().enumerate() {
+                        writeln!(
+                            verilog,
+                            "    wire signed [{}:0] {};",
+                            output.port.width - 1,
+                            self.signal("p", j)
+                        )
+                        .unwrap();
+                    }
+                    let mut ports =
+                        vec![(mcm.inputs[0].name.as_str(), self.input_name().to_string())];
+                    ports.extend(
+                        mcm.outputs
+                            .iter()
+                            .enumerate()
+                            .map(|(j, o)| (o.port.name.as_str(), self.signal("p", j))),
+                    );
+                    instance(&mut verilog, mcm, &self.naming.wire("mcm"), &ports);
+                }
+                let product = |k: usize| taps[k].map(|(j, negate)| (negate, self.signal("p", j)));
+                let terms = |k: usize| {
+                    let mut terms = Vec::new();
+                    if matches!(chain.get(k + 1), Some(Some(_))) {
+                        terms.push((false, self.signal("z", k + 1)));
+                    }
+                    terms.extend(product(k));
+                    terms
+                };
+                let registers: Vec<(usize, usize)> = chain
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(k, z)| z.map(|width| (k, width)))
+                    .collect();
+                if !registers.is_empty() {
+                    writeln!(verilog).unwrap();
+                    writeln!(verilog, "    // Transposed-form accumulator chain").unwrap();
+                    for &(k, width) in &registers {
+                        writeln!(
+                            verilog,
+                            "    reg signed [{}:0] {};",
+                            width - 1,
+                            self.signal("z", k)
+                        )
+                        .unwrap();
+                    }
+                    writeln!(verilog, "    always @(posedge clk) begin").unwrap();
+                    writeln!(verilog, "        if (rst) begin").unwrap();
+                    for &(k, _) in &registers {
+                        writeln!(verilog, "            {} <= 0;", self.signal("z", k)).unwrap();
+                    }
+                    writeln!(verilog, "        end else begin").unwrap();
+                    for &(k, _) in &registers {
+                        write!(
+                            verilog,
+                            "            {} <= {};",
+                            self.signal("z", k),
+                            sum_text(&terms(k))
+                        )
+                        .unwrap();
+                        if taps[k].is_some() {
+                            write!(verilog, " // {}", self.tap_comment(&[k])).unwrap();
+                        }
+                        writeln!(verilog).unwrap();
+                    }
+                    writeln!(verilog, "        end").unwrap();
+                    writeln!(verilog, "    end").unwrap();
+                }
+                terms(0)
+            }
+            Structure::Direct { modules, products } => {
+                let depth = products
+                    .iter()
+                    .flat_map(|p| p.taps.iter().copied())
+                    .max()
+                    .unwrap_or(0);
+                if depth > 0 {
+                    writeln!(verilog).unwrap();
+                    writeln!(verilog, "    // Tapped delay line").unwrap();
+                    for k in 1..=depth {
+                        writeln!(verilog, "    reg signed [{}:0] {};", w - 1, self.delay(k))
+                            .unwrap();
+                    }
+                    writeln!(verilog, "    always @(posedge clk) begin").unwrap();
+                    writeln!(verilog, "        if (rst) begin").unwrap();
+                    for k in 1..=depth {
+                        writeln!(verilog, "            {} <= 0;", self.delay(k)).unwrap();
+                    }
+                    writeln!(verilog, "        end else begin").unwrap();
+                    for k in 1..=depth {
+                        writeln!(
+                            verilog,
+                            "            {} <= {};",
+                            self.delay(k),
+                            self.delay(k - 1)
+                        )
+                        .unwrap();
+                    }
+                    writeln!(verilog, "        end").unwrap();
+                    writeln!(verilog, "    end").unwrap();
+                }
+                let pairs: Vec<(usize, &DirectProduct)> = products
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| p.taps.len() == 2)
+                    .collect();
+                if !pairs.is_empty() {
+                    writeln!(verilog).unwrap();
+                    let kind = if self.symmetry == FirSymmetry::Antisymmetric {
+                        "Antisymmetric pre-subtractors"
+                    } else {
+                        "Symmetric pre-adders"
+                    };
+                    writeln!(verilog, "    // {}", kind).unwrap();
+                    for (i, p) in &pairs {
+                        let op = if p.subtract { '-' } else { '+' };
+                        writeln!(
+                            verilog,
+                            "    wire signed [{}:0] {} = {} {} {};",
+                            w,
+                            self.signal("s", *i),
+                            self.delay(p.taps[0]),
+                            op,
+                            self.delay(p.taps[1])
+                        )
+                        .unwrap();
+                    }
+                }
+                if !products.is_empty() {
+                    writeln!(verilog).unwrap();
+                    writeln!(verilog, "    // Coefficient multipliers").unwrap();
+                    for (i, p) in products.iter().enumerate() {
+                        let module = &modules[p.module];
+                        writeln!(
+                            verilog,
+                            "    wire signed [{}:0] {}; // {}",
+                            module.outputs[0].port.width - 1,
+                            self.signal("p", i),
+                            self.tap_comment(&p.taps)
+                        )
+                        .unwrap();
+                    }
+                    for (i, p) in products.iter().enumerate() {
+                        let module = &modules[p.module];
+                        let input = if p.taps.len() == 2 {
+                            self.signal("s", i)
+                        } else {
+                            self.delay(p.taps[0])
+                        };
+                        let ports = [
+                            (module.inputs[0].name.as_str(), input),
+                            (module.outputs[0].port.name.as_str(), self.signal("p", i)),
+                        ];
+                        instance(&mut verilog, module, &self.signal("m", i), &ports);
+                    }
+                }
+                (0..products.len())
+                    .map(|i| (false, self.signal("p", i)))
+                    .collect()
+            }
+        };
+
+        writeln!(verilog).unwrap();
+        writeln!(
+            verilog,
+            "    assign {} = {};",
+            self.output_name(),
+            sum_text(&y_terms)
+        )
+        .unwrap();
+        writeln!(verilog, "endmodule").unwrap();
+        verilog
+    }
+}
+
+/// Generate Verilog for a FIR filter with CSD coefficients.
+///
+/// Shorthand for [`fir_filter()`] followed by [`FirFilter::verilog()`].
+///
+/// # Errors
+///
+/// Same as [`fir_filter()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::fir::{generate_fir_filter, FirForm, FirOptions};
+///
+/// let options = FirOptions::new().form(FirForm::Direct).coefficient_frac_bits(8);
+/// let v = generate_fir_filter(&[0.25, 0.5, 0.25], 8, &options).unwrap();
+/// assert!(v.contains("    wire signed [8:0] s0 = x + d2;"));
+/// ```
+pub fn generate_fir_filter(
+    taps: &[f64],
+    input_width: usize,
+    options: &FirOptions,
+) -> Result<String, CsdMultiplierError> {
+    Ok(fir_filter(taps, input_width, options)?.verilog())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exact output of the quantized filter.
+    fn convolve(coefficients: &[i128], samples: &[i128]) -> Vec<i128> {
+        (0..samples.len())
+            .map(|n| {
+                (0..coefficients.len())
+                    .filter(|&k| k <= n)
+                    .map(|k| coefficients[k] * samples[n - k])
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn samples(width: usize) -> Vec<i128> {
+        let min = -(1i128 << (width - 1));
+        let max = (1i128 << (width - 1)) - 1;
+        let mut x = vec![min, 0, 0, 0, 0, 0, 0];
+        x.extend([max; 7]);
+        x.extend([min; 7]);
+        x.extend((0..50).map(|i| (i * 37 % 255) - 127));
+        x
+    }
+
+    #[test]
+    fn test_detect_symmetry() {
+        assert_eq!(detect_symmetry(&[1, 2, 1]), FirSymmetry::Symmetric);
+        assert_eq!(detect_symmetry(&[1, 2, 2, 1]), FirSymmetry::Symmetric);
+        assert_eq!(detect_symmetry(&[1, 0, -1]), FirSymmetry::Antisymmetric);
+        assert_eq!(detect_symmetry(&[1, 2, 3]), FirSymmetry::None);
+        assert_eq!(detect_symmetry(&[5]), FirSymmetry::Symmetric);
+    }
+
+    #[test]
+    fn test_transposed_shares_mirrored_products() {
+        let taps = [0.1, -0.3, 0.7, -0.3, 0.1];
+        let options = FirOptions::new().coefficient_frac_bits(6);
+        let filter = fir_filter(&taps, 8, &options).unwrap();
+        assert_eq!(filter.symmetry(), FirSymmetry::Symmetric);
+        assert_eq!(filter.multipliers()[0].outputs.len(), 3);
+        let v = filter.verilog();
+        assert!(v.contains("            z3 <= z4 + p1; // h[3] = -0.296875\n"));
+        assert!(v.contains("            z4 <= p0; // h[4] = 0.09375\n"));
+        assert!(v.contains("    output signed [14:0] y // Filter output (6 fractional bits)\n"));
+
+        let unshared = fir_filter(&taps, 8, &options.exploit_symmetry(false)).unwrap();
+        assert_eq!(unshared.multipliers()[0].outputs.len(), 5);
+        assert_eq!((filter.adder_count(), unshared.adder_count()), (8, 13));
+    }
+
+    #[test]
+    fn test_antisymmetric_transposed_subtracts() {
+        let filter = fir_filter(&[0.5, 0.25, -0.25, -0.5], 8, &FirOptions::new()).unwrap();
+        assert_eq!(filter.symmetry(), FirSymmetry::Antisymmetric);
+        assert_eq!(filter.multipliers()[0].outputs.len(), 2);
+        let v = filter.verilog();
+        assert!(v.contains("            z2 <= z3 - p1; // h[2] = -0.25\n"));
+        assert!(v.contains("            z3 <= -p0; // h[3] = -0.5\n"));
+    }
+
+    #[test]
+    fn test_direct_pre_adders() {
+        let options = FirOptions::new()
+            .form(FirForm::Direct)
+            .coefficient_frac_bits(6);
+        let filter = fir_filter(&[0.1, -0.3, 0.7, -0.3, 0.1], 8, &options).unwrap();
+        assert_eq!(filter.multipliers().len(), 3);
+        let v = filter.verilog();
+        assert!(v.contains("    // Symmetric pre-adders\n    wire signed [8:0] s0 = x + d4;\n"));
+        assert!(v.contains("    wire signed [11:0] p0; // h[0] = h[4] = 0.09375\n"));
+        assert!(v.contains("    csd_fir_c2 m2 (\n        .x(d2),\n        .result(p2)\n    );\n"));
+        assert!(v.contains("    assign y = p0 + p1 + p2;\n"));
+
+        let anti = fir_filter(&[0.5, 0.0, -0.5], 8, &options).unwrap();
+        let v = anti.verilog();
+        assert!(v.contains("    wire signed [8:0] s0 = x - d2;\n"));
+        assert_eq!(anti.multipliers().len(), 1);
+    }
+
+    #[test]
+    fn test_simulate_matches_convolution() {
+        let taps = [-0.02, 0.13, -0.3, 0.0, 0.9, 0.0, -0.3, 0.13, -0.02];
+        let x = samples(10);
+        for form in [FirForm::Transposed, FirForm::Direct] {
+            for exploit in [true, false] {
+                let options = FirOptions::new()
+                    .form(form)
+                    .exploit_symmetry(exploit)
+                    .coefficient_frac_bits(9);
+                let filter = fir_filter(&taps, 10, &options).unwrap();
+                assert_eq!(
+                    filter.simulate(&x),
+                    convolve(&filter.coefficients(), &x),
+                    "{:?}, symmetry {}",
+                    form,
+                    exploit
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_asymmetric_and_degenerate_filters() {
+        let x = samples(8);
+        for form in [FirForm::Transposed, FirForm::Direct] {
+            let options = FirOptions::new().form(form).coefficient_frac_bits(5);
+            for taps in [&[0.75][..], &[0.0, 0.0][..], &[0.3, -0.6, 0.1, 0.0][..]] {
+                let filter = fir_filter(taps, 8, &options).unwrap();
+                assert_eq!(filter.simulate(&x), convolve(&filter.coefficients(), &x));
+            }
+        }
+        let zero = fir_filter(&[0.0, 0.0], 8, &FirOptions::new()).unwrap();
+        assert!(zero.multipliers().is_empty());
+        assert!(zero.verilog().contains("    assign y = 0;\n"));
+    }
+
+    #[test]
+    fn test_fir_naming() {
+        let naming = Naming::new()
+            .module_name("lp")
+            .input_name("din")
+            .output_name("dout")
+            .wire_prefix("f");
+        for form in [FirForm::Transposed, FirForm::Direct] {
+            let options = FirOptions::new().form(form).naming(naming.clone());
+            let filter = fir_filter(&[0.25, 0.5, 0.25], 8, &options).unwrap();
+            assert_eq!(filter.input_name(), "din");
+            assert_eq!(filter.output_name(), "dout");
+            let v = filter.verilog();
+            assert!(v.contains("module lp (\n"));
+            assert!(v.contains("    input signed [7:0] din,      // Input sample\n"));
+            assert!(v.contains("    assign dout = "));
+            assert!(!v.contains(" y ") && !v.contains("(x)"));
+            match form {
+                FirForm::Transposed => {
+                    assert!(v.contains("    lp_mcm f_mcm (\n        .x(din),\n        .p0(f_p0),"));
+                    assert!(v.contains("            f_z1 <= f_z2 + f_p1; // h[1] = 0.5\n"));
+                }
+                FirForm::Direct => {
+                    assert!(v.contains("            f_d1 <= din;\n            f_d2 <= f_d1;\n"));
+                    assert!(v.contains("    wire signed [8:0] f_s0 = din + f_d2;\n"));
+                    assert!(
+                        v.contains("    lp_c0 f_m0 (\n        .x(f_s0),\n        .result(f_p0)\n")
+                    );
+                }
+            }
+        }
+        let suffixed = FirOptions::new().naming(Naming::new().coefficient_suffix(true));
+        let filter = fir_filter(&[0.25, -0.5], 8, &suffixed).unwrap();
+        assert_eq!(filter.module_name(), "csd_fir_0p25_m0p5");
+    }
+
+    #[test]
+    fn test_fir_errors() {
+        assert_eq!(
+            fir_filter(&[], 8, &FirOptions::new()).unwrap_err(),
+            CsdMultiplierError::EmptyCoefficients
+        );
+        assert_eq!(
+            fir_filter(&[f64::NAN], 8, &FirOptions::new()).unwrap_err(),
+            CsdMultiplierError::CoefficientOutOfRange
+        );
+        assert_eq!(
+            fir_filter(&[0.5], 0, &FirOptions::new()).unwrap_err(),
+            CsdMultiplierError::ZeroInputWidth
+        );
+    }
+}
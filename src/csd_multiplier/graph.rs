@@ -0,0 +1,503 @@
+//! Graphviz DOT and Mermaid export of the shift-add network.
+//!
+//! Every adder — a sum wire or an output — is drawn as a node labelled with its
+//! name and the multiple of the input it computes (its fundamental); every
+//! operand is an edge labelled with its sign and shift. Shifted copies of the
+//! input are plain wiring, so they are folded into the edge shift instead of
+//! being drawn. Wires feeding more than one operand (the LCSRe `_pat`, cross-CSE
+//! `_cse_0` and folded `_prod` wires) are highlighted as shared hardware.
+
+use std::fmt::Write;
+
+use super::netlist::{Netlist, Source, Term, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, CsdMultiplierError, MultiplierOptions,
+    MultiplierSpec, TermOp,
+};
+
+// ---------------------------------------------------------------------------
+// Graph model shared by both formats
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Input,
+    Adder,
+    Constant,
+    Output,
+}
+
+#[derive(Debug)]
+struct Node {
+    id: String,
+    name: String,
+    detail: String,
+    shape: Shape,
+    shared: bool,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: String,
+    to: String,
+    op: TermOp,
+    shift: isize,
+}
+
+/// `229·x`, `-x`, `3·x0 - 5·x1`, or `?` if the multiple is not exact.
+fn multiple_text(netlist: &Netlist, multiples: Option<&[i128]>, frac_bits: usize) -> String {
+    let Some(multiples) = multiples else {
+        return "?".to_string();
+    };
+    let mut text = String::new();
+    for (port, &c) in netlist.inputs.iter().zip(multiples) {
+        if c == 0 {
+            continue;
+        }
+        let sign = match (text.is_empty(), c < 0) {
+            (true, false) => "",
+            (true, true) => "-",
+            (false, false) => " + ",
+            (false, true) => " - ",
+        };
+        let magnitude = c.unsigned_abs();
+        let factor = if frac_bits > 0 {
+            format!("{}·", magnitude as f64 / 2f64.powi(frac_bits as i32))
+        } else if magnitude == 1 {
+            String::new()
+        } else {
+            format!("{}·", magnitude)
+        };
+        write!(text, "{}{}{}", sign, factor, port.name).unwrap();
+    }
+    if text.is_empty() {
+        text.push('0');
+    }
+    text
+}
+
+/// Node id and shift of a term, looking through shifted copies of an input.
+fn edge_source(netlist: &Netlist, term: &Term) -> (String, isize) {
+    match term.source {
+        Source::Input(i) => (format!("i{}", i), term.shift),
+        Source::Wire(i) => match netlist.wires[i].expr {
+            WireExpr::Shift { input, power } => {
+                (format!("i{}", input), power as isize + term.shift)
+            }
+            _ => (format!("w{}", i), term.shift),
+        },
+    }
+}
+
+/// Nodes and edges of the adder network.
+fn graph(netlist: &Netlist) -> (Vec<Node>, Vec<Edge>) {
+    let fanout = netlist.wire_fanout();
+    let mut nodes: Vec<Node> = netlist
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, port)| Node {
+            id: format!("i{}", i),
+            name: port.name.clone(),
+            detail: format!("{} bits", port.width),
+            shape: Shape::Input,
+            shared: false,
+        })
+        .collect();
+    let mut edges = Vec::new();
+    let connect = |terms: &[Term], to: &str, edges: &mut Vec<Edge>| {
+        for term in terms {
+            let (from, shift) = edge_source(netlist, term);
+            edges.push(Edge {
+                from,
+                to: to.to_string(),
+                op: term.op,
+                shift,
+            });
+        }
+    };
+
+    let wire_multiples = netlist.wire_multiples();
+    for (i, wire) in netlist.wires.iter().enumerate() {
+        let id = format!("w{}", i);
+        let (detail, shape) = match &wire.expr {
+            WireExpr::Shift { .. } => continue,
+            WireExpr::Constant(k) => (format!("= {}", k), Shape::Constant),
+            WireExpr::Sum(terms) => {
+                connect(terms, &id, &mut edges);
+                let multiple = multiple_text(netlist, wire_multiples[i].as_deref(), 0);
+                (format!("= {}", multiple), Shape::Adder)
+            }
+        };
+        nodes.push(Node {
+            id,
+            name: wire.name.clone(),
+            detail,
+            shape,
+            shared: fanout[i] > 1,
+        });
+    }
+
+    let output_multiples = netlist.output_multiples();
+    for (i, output) in netlist.outputs.iter().enumerate() {
+        let id = format!("o{}", i);
+        connect(&output.terms, &id, &mut edges);
+        let multiple = multiple_text(
+            netlist,
+            output_multiples[i].as_deref(),
+            output.port.frac_bits,
+        );
+        nodes.push(Node {
+            id,
+            name: output.port.name.clone(),
+            detail: format!("= {}", multiple),
+            shape: Shape::Output,
+            shared: false,
+        });
+    }
+    (nodes, edges)
+}
+
+/// `+`, `- << 5`, `+ >>> 4`.
+fn edge_label(edge: &Edge) -> String {
+    let sign = match edge.op {
+        TermOp::Add => "+",
+        TermOp::Sub => "-",
+    };
+    match edge.shift {
+        0 => sign.to_string(),
+        s if s > 0 => format!("{} << {}", sign, s),
+        s => format!("{} >>> {}", sign, -s),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Printers
+// ---------------------------------------------------------------------------
+
+/// Print a [`Netlist`] as a Graphviz DOT digraph.
+///
+/// Inputs are drawn as `invhouse`, adders as boxes, constants as ellipses and
+/// outputs as `house` nodes; shared wires are filled light blue.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::graph::emit_dot;
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+///
+/// let netlist = csd_multiplier_netlist("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+/// let dot = emit_dot(&netlist);
+/// assert!(dot.contains("w4 [label=\"_pat\\n= 96·x\", shape=box, style=filled, fillcolor=lightblue];"));
+/// assert!(dot.contains("w4 -> o0 [label=\"+ >>> 4\"];"));
+/// ```
+pub fn emit_dot(netlist: &Netlist) -> String {
+    let (nodes, edges) = graph(netlist);
+    let mut d = String::new();
+    writeln!(d, "digraph {} {{", netlist.module_name).unwrap();
+    writeln!(d, "    rankdir=LR;").unwrap();
+    writeln!(d, "    node [fontname=\"monospace\"];").unwrap();
+    writeln!(d).unwrap();
+    for node in &nodes {
+        let shape = match node.shape {
+            Shape::Input => "invhouse",
+            Shape::Adder => "box",
+            Shape::Constant => "ellipse",
+            Shape::Output => "house",
+        };
+        let style = if node.shared {
+            ", style=filled, fillcolor=lightblue"
+        } else {
+            ""
+        };
+        writeln!(
+            d,
+            "    {} [label=\"{}\\n{}\", shape={}{}];",
+            node.id, node.name, node.detail, shape, style
+        )
+        .unwrap();
+    }
+    if !edges.is_empty() {
+        writeln!(d).unwrap();
+    }
+    for edge in &edges {
+        writeln!(
+            d,
+            "    {} -> {} [label=\"{}\"];",
+            edge.from,
+            edge.to,
+            edge_label(edge)
+        )
+        .unwrap();
+    }
+    writeln!(d, "}}").unwrap();
+    d
+}
+
+/// Print a [`Netlist`] as a Mermaid flowchart.
+///
+/// Node shapes follow [`emit_dot()`]; shared wires get the `shared` class.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::graph::emit_mermaid;
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+///
+/// let netlist = csd_multiplier_netlist("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+/// let m = emit_mermaid(&netlist);
+/// assert!(m.starts_with("flowchart LR\n"));
+/// assert!(m.contains("    w4[\"_pat<br/>= 96·x\"]:::shared\n"));
+/// ```
+pub fn emit_mermaid(netlist: &Netlist) -> String {
+    let (nodes, edges) = graph(netlist);
+    let mut m = String::new();
+    writeln!(m, "flowchart LR").unwrap();
+    for node in &nodes {
+        let label = format!("\"{}<br/>{}\"", node.name, node.detail);
+        let shaped = match node.shape {
+            Shape::Input => format!("[/{}/]", label),
+            Shape::Adder => format!("[{}]", label),
+            Shape::Constant => format!("([{}])", label),
+            Shape::Output => format!("[[{}]]", label),
+        };
+        let class = if node.shared { ":::shared" } else { "" };
+        writeln!(m, "    {}{}{}", node.id, shaped, class).unwrap();
+    }
+    for edge in &edges {
+        // `<` and `>` would be read as HTML in a Mermaid label
+        let label = edge_label(edge).replace('<', "#lt;").replace('>', "#gt;");
+        writeln!(m, "    {} -->|\"{}\"| {}", edge.from, label, edge.to).unwrap();
+    }
+    if nodes.iter().any(|n| n.shared) {
+        writeln!(
+            m,
+            "    classDef shared fill:#add8e6,stroke:#1f4e79,stroke-width:2px"
+        )
+        .unwrap();
+    }
+    m
+}
+
+// ---------------------------------------------------------------------------
+// Convenience generators
+// ---------------------------------------------------------------------------
+
+/// Generate a DOT diagram of a single-coefficient multiplier, the counterpart of
+/// [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_dot(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_dot_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_dot()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_dot_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_dot(&netlist))
+}
+
+/// Generate a DOT diagram of a multi-coefficient block with cross-CSE, the
+/// counterpart of [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::graph::generate_csd_multipliers_dot;
+/// use csd::csd_multiplier::MultiplierSpec;
+///
+/// let coeffs = vec![
+///     MultiplierSpec::from_integer("y0", 229, 8),
+///     MultiplierSpec::from_integer("y1", -187, 8),
+/// ];
+/// let dot = generate_csd_multipliers_dot(&coeffs, "taps").unwrap();
+/// assert!(dot.starts_with("digraph taps {"));
+/// assert!(dot.contains("fillcolor=lightblue"));
+/// ```
+pub fn generate_csd_multipliers_dot(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_dot_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_dot()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_dot_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_dot(&netlist))
+}
+
+/// Generate a Mermaid diagram of a single-coefficient multiplier.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_mermaid(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_mermaid_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_mermaid()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_mermaid_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_mermaid(&netlist))
+}
+
+/// Generate a Mermaid diagram of a multi-coefficient block with cross-CSE.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_mermaid(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_mermaid_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_mermaid()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_mermaid_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_mermaid(&netlist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::OutputRounding;
+
+    #[test]
+    fn test_dot_golden() {
+        let dot = generate_csd_multiplier_dot("+0-0+0-0", 8, 7).unwrap();
+        let expected = r###"digraph csd_multiplier {
+    rankdir=LR;
+    node [fontname="monospace"];
+
+    i0 [label="x\n8 bits", shape=invhouse];
+    w4 [label="_pat\n= 96·x", shape=box, style=filled, fillcolor=lightblue];
+    o0 [label="result\n= 102·x", shape=house];
+
+    i0 -> w4 [label="+ << 7"];
+    i0 -> w4 [label="- << 5"];
+    w4 -> o0 [label="+"];
+    w4 -> o0 [label="+ >>> 4"];
+}
+"###;
+        assert_eq!(dot, expected);
+    }
+
+    #[test]
+    fn test_mermaid_golden() {
+        let m = generate_csd_multiplier_mermaid("+0-", 8, 2).unwrap();
+        let expected = r###"flowchart LR
+    i0[/"x<br/>8 bits"/]
+    o0[["result<br/>= 3·x"]]
+    i0 -->|"+ #lt;#lt; 2"| o0
+    i0 -->|"-"| o0
+"###;
+        assert_eq!(m, expected);
+    }
+
+    #[test]
+    fn test_multi_shared_wires() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -187, 8),
+            MultiplierSpec::from_integer("y2", -458, 8),
+        ];
+        let netlist = csd_multipliers_netlist(&coeffs, "taps", &MultiplierOptions::new()).unwrap();
+        let (nodes, edges) = graph(&netlist);
+        let shared: Vec<&str> = nodes
+            .iter()
+            .filter(|n| n.shared)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(shared, vec!["_cse_0", "_y0_prod"]);
+        let outputs: Vec<&str> = nodes
+            .iter()
+            .filter(|n| n.shape == Shape::Output)
+            .map(|n| n.detail.as_str())
+            .collect();
+        assert_eq!(outputs, vec!["= 229·x", "= -187·x", "= -458·x"]);
+        // Every operand is drawn once
+        let operands: usize = netlist.outputs.iter().map(|o| o.terms.len()).sum::<usize>()
+            + netlist
+                .wires
+                .iter()
+                .map(|w| match &w.expr {
+                    WireExpr::Sum(terms) => terms.len(),
+                    _ => 0,
+                })
+                .sum::<usize>();
+        assert_eq!(edges.len(), operands);
+        let m = emit_mermaid(&netlist);
+        assert!(m.contains("classDef shared"));
+    }
+
+    #[test]
+    fn test_fractional_and_rounded_labels() {
+        let dot = generate_csd_multiplier_dot("+00-.0+", 6, 3).unwrap();
+        assert!(dot.contains("o0 [label=\"result\\n= 7.25·x\", shape=house];"));
+        let options = MultiplierOptions::new()
+            .output_frac_bits(0)
+            .rounding(OutputRounding::Nearest);
+        let netlist = csd_multiplier_netlist("+00-.0+", 6, 3, &options).unwrap();
+        let dot = emit_dot(&netlist);
+        assert!(dot.contains("w3 [label=\"_round\\n= 2\", shape=ellipse];"));
+        assert!(dot.contains("= ?\", shape=house"));
+    }
+
+    #[test]
+    fn test_zero_coefficient() {
+        let dot = generate_csd_multiplier_dot("000", 8, 2).unwrap();
+        assert!(dot.contains("o0 [label=\"result\\n= 0\", shape=house];"));
+        assert!(!dot.contains("->"));
+    }
+}
@@ -0,0 +1,306 @@
+//! HLS C++ backend.
+//!
+//! Prints a [`Netlist`] as a C++ function for Vitis HLS with `ap_int<W>`
+//! variables of exactly the Verilog wire widths and `#pragma HLS INLINE`, so
+//! the constant multiplier dissolves into the caller's datapath. The
+//! function shares the Verilog's LCSRe, cross-CSE and folding decisions.
+//!
+//! `ap_int` arithmetic grows with its operands and assignment wraps to the
+//! destination width, as a Verilog `assign` does. A left shift keeps the width
+//! of its operand, so shifted operands are first cast to the destination
+//! width. Outputs with fractional bits are `ap_fixed<W, I>` and receive the
+//! raw product bits through `.range()`. Wires no operand reads are left out,
+//! as in the [`model`](super::model) output.
+//!
+//! A single-output function returns its result; with several outputs the
+//! results are written through references.
+
+use std::fmt::Write;
+
+use super::model::used_wires;
+use super::netlist::{Netlist, Port, Source, Term, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, CsdMultiplierError, MultiplierOptions,
+    MultiplierSpec, TermOp,
+};
+
+/// `ap_int<W>`, or `ap_fixed<W, I>` for a port with fractional bits.
+fn hls_type(port: &Port) -> String {
+    if port.frac_bits == 0 {
+        format!("ap_int<{}>", port.width)
+    } else {
+        format!(
+            "ap_fixed<{}, {}>",
+            port.width,
+            port.width as isize - port.frac_bits as isize
+        )
+    }
+}
+
+/// Render one term's operand at `width` bits, without its sign; a shift
+/// that is the whole expression needs no parentheses.
+fn hls_operand(netlist: &Netlist, term: &Term, width: usize, bare: bool) -> String {
+    let name = netlist.source_name(term.source);
+    let operand = match term.shift {
+        0 => return name.to_string(),
+        s if s > 0 => format!("ap_int<{}>({}) << {}", width, name, s),
+        s => format!("{} >> {}", name, -s),
+    };
+    if bare {
+        operand
+    } else {
+        format!("({})", operand)
+    }
+}
+
+/// Render a signed sum of terms assigned to a `width`-bit signal.
+fn hls_sum(netlist: &Netlist, terms: &[Term], width: usize) -> String {
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+    let mut expr = String::new();
+    for (i, term) in terms.iter().enumerate() {
+        match (i, term.op) {
+            (0, TermOp::Add) => {}
+            (0, TermOp::Sub) => expr.push('-'),
+            (_, TermOp::Add) => expr.push_str(" + "),
+            (_, TermOp::Sub) => expr.push_str(" - "),
+        }
+        let bare = terms.len() == 1 && term.op == TermOp::Add;
+        expr.push_str(&hls_operand(netlist, term, width, bare));
+    }
+    expr
+}
+
+/// Print a [`Netlist`] as an HLS C++ function.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::hls::emit_hls;
+/// use csd::csd_multiplier::CsdMultiplier;
+///
+/// let cpp = emit_hls(&CsdMultiplier::new("+0-0+0-0", 8, 7).unwrap().netlist());
+/// assert!(cpp.contains("ap_int<15> csd_multiplier(ap_int<8> x)\n{"));
+/// assert!(cpp.contains("    ap_int<15> _pat = x_shift7 - x_shift5;\n"));
+/// assert!(cpp.contains("    return _pat + (_pat >> 4);\n"));
+/// ```
+pub fn emit_hls(netlist: &Netlist) -> String {
+    let mut cpp = String::new();
+    writeln!(cpp, "#include <ap_int.h>").unwrap();
+    if netlist
+        .inputs
+        .iter()
+        .chain(netlist.outputs.iter().map(|o| &o.port))
+        .any(|p| p.frac_bits > 0)
+    {
+        writeln!(cpp, "#include <ap_fixed.h>").unwrap();
+    }
+    writeln!(cpp).unwrap();
+
+    // --- signature ---
+    let single = netlist.outputs.len() == 1;
+    let mut params: Vec<String> = netlist
+        .inputs
+        .iter()
+        .map(|p| format!("{} {}", hls_type(p), p.name))
+        .collect();
+    if !single {
+        params.extend(
+            netlist
+                .outputs
+                .iter()
+                .map(|o| format!("{} &{}", hls_type(&o.port), o.port.name)),
+        );
+    }
+    let ret = if single {
+        hls_type(&netlist.outputs[0].port)
+    } else {
+        "void".to_string()
+    };
+    writeln!(
+        cpp,
+        "{} {}({})",
+        ret,
+        netlist.module_name,
+        params.join(", ")
+    )
+    .unwrap();
+    writeln!(cpp, "{{").unwrap();
+    writeln!(cpp, "#pragma HLS INLINE").unwrap();
+
+    // --- body ---
+    for (i, comment) in used_wires(netlist) {
+        let wire = &netlist.wires[i];
+        if let Some(comment) = comment {
+            writeln!(cpp).unwrap();
+            writeln!(cpp, "    // {}", comment).unwrap();
+        }
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                let term = Term {
+                    op: TermOp::Add,
+                    source: Source::Input(*input),
+                    shift: *power as isize,
+                };
+                hls_sum(netlist, &[term], wire.width)
+            }
+            WireExpr::Sum(terms) => hls_sum(netlist, terms, wire.width),
+            WireExpr::Constant(value) => value.to_string(),
+        };
+        writeln!(cpp, "    ap_int<{}> {} = {};", wire.width, wire.name, expr).unwrap();
+    }
+    for output in &netlist.outputs {
+        if let Some(comment) = &output.comment {
+            writeln!(cpp).unwrap();
+            writeln!(cpp, "    // {}", comment).unwrap();
+        }
+        let port = &output.port;
+        let expr = hls_sum(netlist, &output.terms, port.width);
+        match (single, port.frac_bits) {
+            (true, 0) => writeln!(cpp, "    return {};", expr).unwrap(),
+            (true, _) => {
+                writeln!(cpp, "    {} {};", hls_type(port), port.name).unwrap();
+                writeln!(
+                    cpp,
+                    "    {}.range({}, 0) = ap_int<{}>({});",
+                    port.name,
+                    port.width - 1,
+                    port.width,
+                    expr
+                )
+                .unwrap();
+                writeln!(cpp, "    return {};", port.name).unwrap();
+            }
+            (false, 0) => writeln!(cpp, "    {} = {};", port.name, expr).unwrap(),
+            (false, _) => writeln!(
+                cpp,
+                "    {}.range({}, 0) = ap_int<{}>({});",
+                port.name,
+                port.width - 1,
+                port.width,
+                expr
+            )
+            .unwrap(),
+        }
+    }
+    writeln!(cpp, "}}").unwrap();
+    cpp
+}
+
+/// Generate an HLS C++ CSD multiplier, the counterpart of
+/// [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::hls::generate_csd_multiplier_hls;
+///
+/// let cpp = generate_csd_multiplier_hls("+0-", 8, 2).unwrap();
+/// assert!(cpp.contains("#pragma HLS INLINE\n"));
+/// assert!(cpp.contains("    return x_shift2 - x_shift0;\n"));
+/// ```
+pub fn generate_csd_multiplier_hls(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_hls_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_hls()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_hls_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_hls(&netlist))
+}
+
+/// Generate an HLS C++ multi-coefficient block with cross-CSE, the counterpart of
+/// [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_hls(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_hls_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_hls()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_hls_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_hls(&netlist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hls_golden() {
+        let cpp = generate_csd_multiplier_hls("+0-", 8, 2).unwrap();
+        let expected = r###"#include <ap_int.h>
+
+ap_int<10> csd_multiplier(ap_int<8> x)
+{
+#pragma HLS INLINE
+
+    // Create shifted versions of input
+    ap_int<10> x_shift2 = ap_int<10>(x) << 2;
+    ap_int<8> x_shift0 = x;
+
+    // CSD implementation
+    return x_shift2 - x_shift0;
+}
+"###;
+        assert_eq!(cpp, expected);
+    }
+
+    #[test]
+    fn test_hls_multi_output() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -229, 8),
+        ];
+        let cpp = generate_csd_multipliers_hls(&coeffs, "taps").unwrap();
+        assert!(cpp.contains("void taps(ap_int<8> x, ap_int<16> &y0, ap_int<16> &y1)\n"));
+        assert!(cpp.contains("    y1 = -_y0_prod;\n"));
+    }
+
+    #[test]
+    fn test_hls_fixed_point_output() {
+        let netlist = csd_multiplier_netlist("+0-.+", 8, 2, &MultiplierOptions::new()).unwrap();
+        let port = &netlist.outputs[0].port;
+        let cpp = emit_hls(&netlist);
+        assert!(cpp.contains("#include <ap_fixed.h>\n"));
+        let ty = format!("ap_fixed<{}, {}>", port.width, port.width - 1);
+        assert!(cpp.contains(&format!("{} csd_multiplier(ap_int<8> x)\n", ty)));
+        assert!(cpp.contains(&format!(
+            "    result.range({}, 0) = ap_int<{}>(",
+            port.width - 1,
+            port.width
+        )));
+    }
+}
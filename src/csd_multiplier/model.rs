@@ -0,0 +1,599 @@
+//! Bit-accurate C and Rust software models.
+//!
+//! Firmware and golden models must compute exactly what the hardware does.
+//! [`emit_c()`] and [`emit_rust()`] print a [`Netlist`] as a function that
+//! performs the same shifts and additions and wraps every intermediate value
+//! to the width of the wire it models, with the semantics of
+//! [`simulator::simulate()`](super::simulator::simulate): inputs are wrapped to
+//! their port width, sums wrap at the width of the signal they are assigned
+//! to, and `>>>` is an arithmetic right shift of the wrapped operand.
+//!
+//! Values are held in 64-bit integers, or 128-bit ones (`__int128` in C) when
+//! a signal is wider than 64 bits. Wires no operand reads (shifted copies
+//! that an LCSRe pattern made redundant) are left out.
+//!
+//! A single-output function returns its result; with several outputs the C
+//! function writes them through pointers and the Rust function returns them
+//! as an array in port order.
+
+use std::fmt::Write;
+
+use super::netlist::{Netlist, Source, Term, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, CsdMultiplierError, MultiplierOptions,
+    MultiplierSpec, TermOp,
+};
+
+/// Bits of the integer type needed to hold every signal of `netlist`.
+fn value_bits(netlist: &Netlist) -> usize {
+    let widest = netlist
+        .inputs
+        .iter()
+        .map(|p| p.width)
+        .chain(netlist.wires.iter().map(|w| w.width))
+        .chain(netlist.outputs.iter().map(|o| o.port.width))
+        .max()
+        .unwrap_or(1);
+    if widest <= 64 {
+        64
+    } else {
+        128
+    }
+}
+
+/// Wires that are actually read, with the comment to print before each.
+///
+/// The comment of an unread wire moves to the next wire that is printed.
+pub(super) fn used_wires(netlist: &Netlist) -> Vec<(usize, Option<&str>)> {
+    let fanout = netlist.wire_fanout();
+    let mut used = Vec::new();
+    let mut pending: Option<&str> = None;
+    for (i, wire) in netlist.wires.iter().enumerate() {
+        if let Some(comment) = &wire.comment {
+            pending = Some(comment);
+        }
+        if fanout[i] > 0 {
+            used.push((i, pending.take()));
+        }
+    }
+    used
+}
+
+// ---------------------------------------------------------------------------
+// C
+// ---------------------------------------------------------------------------
+
+/// C types and wrap helper for a value width.
+struct CTypes {
+    signed: &'static str,
+    unsigned: &'static str,
+    wrap: &'static str,
+}
+
+fn c_types(bits: usize) -> CTypes {
+    if bits == 64 {
+        CTypes {
+            signed: "int64_t",
+            unsigned: "uint64_t",
+            wrap: "csd_wrap",
+        }
+    } else {
+        CTypes {
+            signed: "__int128",
+            unsigned: "unsigned __int128",
+            wrap: "csd_wrap128",
+        }
+    }
+}
+
+/// Render one term as an unsigned operand (unsigned arithmetic wraps instead
+/// of overflowing).
+fn c_operand(netlist: &Netlist, term: &Term, types: &CTypes) -> String {
+    let name = netlist.source_name(term.source);
+    match term.shift {
+        0 => format!("({}){}", types.unsigned, name),
+        s if s > 0 => format!("(({}){} << {})", types.unsigned, name, s),
+        s => format!("({})({} >> {})", types.unsigned, name, -s),
+    }
+}
+
+/// Render a sum of terms wrapped to `width` bits.
+fn c_sum(netlist: &Netlist, terms: &[Term], width: usize, types: &CTypes) -> String {
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+    let mut expr = String::new();
+    for (i, term) in terms.iter().enumerate() {
+        match (i, term.op) {
+            (0, TermOp::Add) => {}
+            (0, TermOp::Sub) => expr.push('-'),
+            (_, TermOp::Add) => expr.push_str(" + "),
+            (_, TermOp::Sub) => expr.push_str(" - "),
+        }
+        expr.push_str(&c_operand(netlist, term, types));
+    }
+    format!("{}({}, {})", types.wrap, expr, width)
+}
+
+/// Print a [`Netlist`] as a C99 function.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::model::emit_c;
+/// use csd::csd_multiplier::CsdMultiplier;
+///
+/// let c = emit_c(&CsdMultiplier::new("+0-0+0-0", 8, 7).unwrap().netlist());
+/// assert!(c.contains("int64_t csd_multiplier(int64_t x)\n{"));
+/// assert!(c.contains("    int64_t _pat = csd_wrap((uint64_t)x_shift7 - (uint64_t)x_shift5, 15);"));
+/// assert!(c.contains("    return csd_wrap((uint64_t)_pat + (uint64_t)(_pat >> 4), 15);"));
+/// ```
+pub fn emit_c(netlist: &Netlist) -> String {
+    let types = c_types(value_bits(netlist));
+    let mut c = String::new();
+    writeln!(c, "#include <stdint.h>").unwrap();
+    writeln!(c).unwrap();
+    let guard = format!("{}_DEFINED", types.wrap.to_uppercase());
+    writeln!(c, "#ifndef {}", guard).unwrap();
+    writeln!(c, "#define {}", guard).unwrap();
+    writeln!(
+        c,
+        "/* Sign-extend the low w bits of v (two's-complement wrap-around) */"
+    )
+    .unwrap();
+    writeln!(
+        c,
+        "static inline {} {}({} v, unsigned w)",
+        types.signed, types.wrap, types.unsigned
+    )
+    .unwrap();
+    writeln!(c, "{{").unwrap();
+    writeln!(
+        c,
+        "    {} sign = ({})1 << (w - 1);",
+        types.unsigned, types.unsigned
+    )
+    .unwrap();
+    writeln!(
+        c,
+        "    return ({})(((v & ((sign << 1) - 1)) ^ sign) - sign);",
+        types.signed
+    )
+    .unwrap();
+    writeln!(c, "}}").unwrap();
+    writeln!(c, "#endif").unwrap();
+
+    // --- signature ---
+    writeln!(c).unwrap();
+    writeln!(
+        c,
+        "/* Bit-accurate model of module {} */",
+        netlist.module_name
+    )
+    .unwrap();
+    let mut params: Vec<String> = netlist
+        .inputs
+        .iter()
+        .map(|p| format!("{} {}", types.signed, p.name))
+        .collect();
+    let single = netlist.outputs.len() == 1;
+    if !single {
+        params.extend(
+            netlist
+                .outputs
+                .iter()
+                .map(|o| format!("{} *{}", types.signed, o.port.name)),
+        );
+    }
+    let ret = if single { types.signed } else { "void" };
+    writeln!(c, "{} {}({})", ret, netlist.module_name, params.join(", ")).unwrap();
+    writeln!(c, "{{").unwrap();
+
+    // --- body ---
+    for port in &netlist.inputs {
+        write!(
+            c,
+            "    {} = {}(({}){}, {});",
+            port.name, types.wrap, types.unsigned, port.name, port.width
+        )
+        .unwrap();
+        match &port.comment {
+            Some(comment) => writeln!(c, " /* {} */", comment).unwrap(),
+            None => writeln!(c).unwrap(),
+        }
+    }
+    for (i, comment) in used_wires(netlist) {
+        let wire = &netlist.wires[i];
+        if let Some(comment) = comment {
+            writeln!(c).unwrap();
+            writeln!(c, "    /* {} */", comment).unwrap();
+        }
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                let term = Term {
+                    op: TermOp::Add,
+                    source: Source::Input(*input),
+                    shift: *power as isize,
+                };
+                c_sum(netlist, &[term], wire.width, &types)
+            }
+            WireExpr::Sum(terms) => c_sum(netlist, terms, wire.width, &types),
+            WireExpr::Constant(value) => value.to_string(),
+        };
+        writeln!(c, "    {} {} = {};", types.signed, wire.name, expr).unwrap();
+    }
+    for output in &netlist.outputs {
+        if let Some(comment) = &output.comment {
+            writeln!(c).unwrap();
+            writeln!(c, "    /* {} */", comment).unwrap();
+        }
+        let expr = c_sum(netlist, &output.terms, output.port.width, &types);
+        if single {
+            writeln!(c, "    return {};", expr).unwrap();
+        } else {
+            writeln!(c, "    *{} = {};", output.port.name, expr).unwrap();
+        }
+    }
+    writeln!(c, "}}").unwrap();
+    c
+}
+
+// ---------------------------------------------------------------------------
+// Rust
+// ---------------------------------------------------------------------------
+
+/// Render one term as an operand; a method receiver needs parentheses.
+fn rust_operand(netlist: &Netlist, term: &Term, receiver: bool) -> String {
+    let name = netlist.source_name(term.source);
+    let operand = match term.shift {
+        0 => return name.to_string(),
+        s if s > 0 => format!("{} << {}", name, s),
+        s => format!("{} >> {}", name, -s),
+    };
+    if receiver {
+        format!("({})", operand)
+    } else {
+        operand
+    }
+}
+
+/// Render a sum of terms wrapped to `width` bits.
+fn rust_sum(netlist: &Netlist, terms: &[Term], width: usize, ty: &str) -> String {
+    if terms.is_empty() {
+        return format!("0{}", ty);
+    }
+    let mut expr = String::new();
+    for (i, term) in terms.iter().enumerate() {
+        let receiver = i == 0 && (terms.len() > 1 || term.op == TermOp::Sub);
+        let operand = rust_operand(netlist, term, receiver);
+        match (i, term.op) {
+            (0, TermOp::Add) => expr.push_str(&operand),
+            (0, TermOp::Sub) => write!(expr, "{}.wrapping_neg()", operand).unwrap(),
+            (_, TermOp::Add) => write!(expr, ".wrapping_add({})", operand).unwrap(),
+            (_, TermOp::Sub) => write!(expr, ".wrapping_sub({})", operand).unwrap(),
+        }
+    }
+    format!("wrap({}, {})", expr, width)
+}
+
+/// Print a [`Netlist`] as a Rust function.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::model::emit_rust;
+/// use csd::csd_multiplier::CsdMultiplier;
+///
+/// let rs = emit_rust(&CsdMultiplier::new("+0-", 8, 2).unwrap().netlist());
+/// assert!(rs.contains("pub fn csd_multiplier(x: i64) -> i64 {"));
+/// assert!(rs.contains("    wrap(x_shift2.wrapping_sub(x_shift0), 10)\n"));
+/// ```
+pub fn emit_rust(netlist: &Netlist) -> String {
+    let bits = value_bits(netlist);
+    let ty = if bits == 64 { "i64" } else { "i128" };
+    let mut rs = String::new();
+    writeln!(
+        rs,
+        "/// Bit-accurate model of module `{}`.",
+        netlist.module_name
+    )
+    .unwrap();
+    let params: Vec<String> = netlist
+        .inputs
+        .iter()
+        .map(|p| format!("{}: {}", p.name, ty))
+        .collect();
+    let single = netlist.outputs.len() == 1;
+    let ret = if single {
+        ty.to_string()
+    } else {
+        format!("[{}; {}]", ty, netlist.outputs.len())
+    };
+    writeln!(
+        rs,
+        "pub fn {}({}) -> {} {{",
+        netlist.module_name,
+        params.join(", "),
+        ret
+    )
+    .unwrap();
+    writeln!(rs, "    // Sign-extend the low `w` bits of `v`").unwrap();
+    writeln!(rs, "    fn wrap(v: {}, w: u32) -> {} {{", ty, ty).unwrap();
+    writeln!(rs, "        (v << ({} - w)) >> ({} - w)", bits, bits).unwrap();
+    writeln!(rs, "    }}").unwrap();
+
+    for port in &netlist.inputs {
+        write!(
+            rs,
+            "    let {} = wrap({}, {});",
+            port.name, port.name, port.width
+        )
+        .unwrap();
+        match &port.comment {
+            Some(comment) => writeln!(rs, " // {}", comment).unwrap(),
+            None => writeln!(rs).unwrap(),
+        }
+    }
+    for (i, comment) in used_wires(netlist) {
+        let wire = &netlist.wires[i];
+        if let Some(comment) = comment {
+            writeln!(rs).unwrap();
+            writeln!(rs, "    // {}", comment).unwrap();
+        }
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                let term = Term {
+                    op: TermOp::Add,
+                    source: Source::Input(*input),
+                    shift: *power as isize,
+                };
+                rust_sum(netlist, &[term], wire.width, ty)
+            }
+            WireExpr::Sum(terms) => rust_sum(netlist, terms, wire.width, ty),
+            WireExpr::Constant(value) => format!("{}{}", value, ty),
+        };
+        writeln!(rs, "    let {} = {};", wire.name, expr).unwrap();
+    }
+    for output in &netlist.outputs {
+        if let Some(comment) = &output.comment {
+            writeln!(rs).unwrap();
+            writeln!(rs, "    // {}", comment).unwrap();
+        }
+        let expr = rust_sum(netlist, &output.terms, output.port.width, ty);
+        if single {
+            writeln!(rs, "    {}", expr).unwrap();
+        } else {
+            writeln!(rs, "    let {} = {};", output.port.name, expr).unwrap();
+        }
+    }
+    if !single {
+        let names: Vec<&str> = netlist
+            .outputs
+            .iter()
+            .map(|o| o.port.name.as_str())
+            .collect();
+        writeln!(rs).unwrap();
+        writeln!(rs, "    [{}]", names.join(", ")).unwrap();
+    }
+    writeln!(rs, "}}").unwrap();
+    rs
+}
+
+// ---------------------------------------------------------------------------
+// Convenience generators
+// ---------------------------------------------------------------------------
+
+/// Generate a C model of a single-coefficient multiplier.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_c(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_c_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_c()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_c_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_c(&netlist))
+}
+
+/// Generate a C model of a multi-coefficient block with cross-CSE.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_c(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_c_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_c()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_c_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_c(&netlist))
+}
+
+/// Generate a Rust model of a single-coefficient multiplier.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_rust(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_rust_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_rust()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_rust_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_rust(&netlist))
+}
+
+/// Generate a Rust model of a multi-coefficient block with cross-CSE.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::model::generate_csd_multipliers_rust;
+/// use csd::csd_multiplier::MultiplierSpec;
+///
+/// let coeffs = vec![
+///     MultiplierSpec::from_integer("y0", 229, 8),
+///     MultiplierSpec::from_integer("y1", -187, 8),
+/// ];
+/// let rs = generate_csd_multipliers_rust(&coeffs, "taps").unwrap();
+/// assert!(rs.contains("pub fn taps(x: i64) -> [i64; 2] {"));
+/// assert!(rs.ends_with("    [y0, y1]\n}\n"));
+/// ```
+pub fn generate_csd_multipliers_rust(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_rust_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_rust()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_rust_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_rust(&netlist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::CsdMultiplier;
+
+    #[test]
+    fn test_c_golden() {
+        let c = generate_csd_multiplier_c("+0-", 8, 2).unwrap();
+        let expected = r###"#include <stdint.h>
+
+#ifndef CSD_WRAP_DEFINED
+#define CSD_WRAP_DEFINED
+/* Sign-extend the low w bits of v (two's-complement wrap-around) */
+static inline int64_t csd_wrap(uint64_t v, unsigned w)
+{
+    uint64_t sign = (uint64_t)1 << (w - 1);
+    return (int64_t)(((v & ((sign << 1) - 1)) ^ sign) - sign);
+}
+#endif
+
+/* Bit-accurate model of module csd_multiplier */
+int64_t csd_multiplier(int64_t x)
+{
+    x = csd_wrap((uint64_t)x, 8); /* Input value */
+
+    /* Create shifted versions of input */
+    int64_t x_shift2 = csd_wrap(((uint64_t)x << 2), 10);
+    int64_t x_shift0 = csd_wrap((uint64_t)x, 8);
+
+    /* CSD implementation */
+    return csd_wrap((uint64_t)x_shift2 - (uint64_t)x_shift0, 10);
+}
+"###;
+        assert_eq!(c, expected);
+    }
+
+    #[test]
+    fn test_rust_golden() {
+        let rs = generate_csd_multiplier_rust("+0-0+0-0", 8, 7).unwrap();
+        let expected = r###"/// Bit-accurate model of module `csd_multiplier`.
+pub fn csd_multiplier(x: i64) -> i64 {
+    // Sign-extend the low `w` bits of `v`
+    fn wrap(v: i64, w: u32) -> i64 {
+        (v << (64 - w)) >> (64 - w)
+    }
+    let x = wrap(x, 8); // Input value
+
+    // Create shifted versions of input
+    let x_shift7 = wrap(x << 7, 15);
+    let x_shift5 = wrap(x << 5, 13);
+
+    // LCSRe: repeated pattern "+0-0"
+    let _pat = wrap(x_shift7.wrapping_sub(x_shift5), 15);
+
+    // CSD implementation (LCSRe optimized)
+    wrap(_pat.wrapping_add(_pat >> 4), 15)
+}
+"###;
+        assert_eq!(rs, expected);
+    }
+
+    #[test]
+    fn test_multi_output_c() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -229, 8),
+        ];
+        let c = generate_csd_multipliers_c(&coeffs, "taps").unwrap();
+        assert!(c.contains("void taps(int64_t x, int64_t *y0, int64_t *y1)\n"));
+        assert!(c.contains("    *y1 = csd_wrap(-(uint64_t)_y0_prod, 16);\n"));
+    }
+
+    #[test]
+    fn test_wide_signals_use_128_bits() {
+        let multiplier = CsdMultiplier::from_integer(229, 60).unwrap();
+        let netlist = multiplier.netlist();
+        let c = emit_c(&netlist);
+        assert!(c.contains("static inline __int128 csd_wrap128(unsigned __int128 v, unsigned w)"));
+        let rs = emit_rust(&netlist);
+        assert!(rs.contains("pub fn csd_multiplier(x: i128) -> i128 {"));
+        assert!(rs.contains("        (v << (128 - w)) >> (128 - w)\n"));
+    }
+
+    #[test]
+    fn test_zero_and_constant() {
+        let rs = generate_csd_multiplier_rust("000", 8, 2).unwrap();
+        assert!(rs.contains("    0i64\n"));
+        let c = generate_csd_multiplier_c("000", 8, 2).unwrap();
+        assert!(c.contains("    return 0;\n"));
+    }
+}
@@ -0,0 +1,538 @@
+//! Shift-add netlist representation.
+//!
+//! The generators in [`crate::csd_multiplier`] first lower CSD coefficients to a
+//! [`Netlist`] — shifted copies of the input, shared sub-expression wires and the
+//! output sums — and then print it in the requested hardware description language.
+//! Keeping the structure explicit lets every backend (and the cost metrics below)
+//! agree on exactly which adders are built.
+//!
+//! Every signal is an exact affine function of the inputs, so its value range —
+//! and therefore the smallest two's-complement width that holds it — is known
+//! exactly: [`Netlist::minimize_widths()`] sizes each wire and port from it.
+
+use super::TermOp;
+
+/// Where a [`Term`] takes its value from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// Input port, indexing [`Netlist::inputs`]
+    Input(usize),
+    /// Intermediate wire, indexing [`Netlist::wires`]
+    Wire(usize),
+}
+
+/// One signed operand of a sum: `±(source · 2^shift)`.
+///
+/// A negative `shift` is an arithmetic right shift (`>>>`), which the
+/// generators use where the shifted-out bits are known to be zero or are
+/// meant to be dropped (requantized outputs, truncated partial products).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Term {
+    /// Add or subtract this operand
+    pub op: TermOp,
+    /// Signal being added
+    pub source: Source,
+    /// Power-of-two scaling applied to the signal
+    pub shift: isize,
+}
+
+impl Term {
+    /// A term that adds `source` unshifted.
+    pub fn add(source: Source) -> Self {
+        Self {
+            op: TermOp::Add,
+            source,
+            shift: 0,
+        }
+    }
+}
+
+/// Expression driving an intermediate wire.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WireExpr {
+    /// Input port `input`, sign-extended to the wire width and shifted left by `power`
+    Shift {
+        /// Index into [`Netlist::inputs`]
+        input: usize,
+        /// Left shift amount
+        power: usize,
+    },
+    /// Signed sum of terms (an empty sum is the constant 0)
+    Sum(Vec<Term>),
+    /// Constant value, e.g. the rounding offset of a requantized output
+    Constant(i128),
+}
+
+/// A signed module port.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Port {
+    /// Port name
+    pub name: String,
+    /// Bit width
+    pub width: usize,
+    /// Number of fractional bits (the binary point sits this many bits from the LSB)
+    pub frac_bits: usize,
+    /// Trailing comment in the port list
+    pub comment: Option<String>,
+}
+
+/// A named intermediate signal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wire {
+    /// Wire name
+    pub name: String,
+    /// Bit width
+    pub width: usize,
+    /// Driving expression
+    pub expr: WireExpr,
+    /// Section comment printed before the declaration
+    pub comment: Option<String>,
+}
+
+/// An output port and the sum that drives it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Output {
+    /// The output port
+    pub port: Port,
+    /// Signed sum assigned to the port (an empty sum is the constant 0)
+    pub terms: Vec<Term>,
+    /// Section comment printed before the assignment
+    pub comment: Option<String>,
+}
+
+/// A complete constant-multiplier module as a shift-add network.
+///
+/// Wires are stored in declaration order: every wire only refers to inputs
+/// and to wires declared before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Netlist {
+    /// Module name
+    pub module_name: String,
+    /// Input ports
+    pub inputs: Vec<Port>,
+    /// Intermediate wires, in declaration order
+    pub wires: Vec<Wire>,
+    /// Output ports with their driving sums
+    pub outputs: Vec<Output>,
+}
+
+/// Smallest two's-complement width holding every value in `lo..=hi`.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::netlist::signed_width;
+///
+/// assert_eq!(signed_width(-384, 381), 10);
+/// assert_eq!(signed_width(-128, 127), 8);
+/// assert_eq!(signed_width(0, 128), 9);
+/// assert_eq!(signed_width(0, 0), 1);
+/// ```
+pub fn signed_width(lo: i128, hi: i128) -> usize {
+    let bits = |v: i128| {
+        // Magnitude bits of v (or of -v - 1 for negative v), plus the sign bit
+        let m = if v < 0 { !v } else { v };
+        (128 - m.leading_zeros()) as usize + 1
+    };
+    bits(lo).max(bits(hi))
+}
+
+/// Value of a signal: exact affine form `constant + Σ coeffs[i] · inputs[i]`
+/// when known, and its range (`None` when it does not fit an `i128`).
+#[derive(Debug, Clone)]
+struct Value {
+    affine: Option<(Vec<i128>, i128)>,
+    range: Option<(i128, i128)>,
+}
+
+/// `v · 2^s`, or `None` when it does not fit an `i128`.
+fn checked_shl(v: i128, s: u32) -> Option<i128> {
+    if s >= 128 {
+        return (v == 0).then_some(0);
+    }
+    let shifted = v.wrapping_shl(s);
+    (shifted >> s == v).then_some(shifted)
+}
+
+/// Range of an affine form over all input values.
+fn affine_range(coeffs: &[i128], constant: i128, inputs: &[Port]) -> Option<(i128, i128)> {
+    let (mut lo, mut hi) = (constant, constant);
+    for (&a, port) in coeffs.iter().zip(inputs) {
+        if a == 0 {
+            continue;
+        }
+        let max = checked_shl(1, port.width as u32 - 1)? - 1;
+        let (x, y) = (a.checked_mul(-max - 1)?, a.checked_mul(max)?);
+        lo = lo.checked_add(x.min(y))?;
+        hi = hi.checked_add(x.max(y))?;
+    }
+    Some((lo, hi))
+}
+
+impl Value {
+    fn from_affine(coeffs: Vec<i128>, constant: i128, inputs: &[Port]) -> Self {
+        Self {
+            range: affine_range(&coeffs, constant, inputs),
+            affine: Some((coeffs, constant)),
+        }
+    }
+
+    /// `±(self · 2^shift)`, with right shifts rounding toward −∞ like `>>>`.
+    fn term(&self, term: &Term, inputs: &[Port]) -> Self {
+        let mut range = self.range;
+        let mut affine = self.affine.clone();
+        if term.shift >= 0 {
+            let s = term.shift as u32;
+            range = range.and_then(|(lo, hi)| Some((checked_shl(lo, s)?, checked_shl(hi, s)?)));
+            affine = affine.and_then(|(c, k)| {
+                let c = c
+                    .iter()
+                    .map(|&a| checked_shl(a, s))
+                    .collect::<Option<_>>()?;
+                Some((c, checked_shl(k, s)?))
+            });
+        } else {
+            let s = (-term.shift) as u32;
+            let mask = if s >= 128 {
+                -1
+            } else {
+                ((1u128 << s) - 1) as i128
+            };
+            let s = s.min(127);
+            range = range.map(|(lo, hi)| (lo >> s, hi >> s));
+            affine = affine
+                .filter(|(c, k)| c.iter().chain([k]).all(|v| v & mask == 0))
+                .map(|(c, k)| (c.iter().map(|a| a >> s).collect(), k >> s));
+        }
+        if term.op == TermOp::Sub {
+            range = range.and_then(|(lo, hi)| Some((hi.checked_neg()?, lo.checked_neg()?)));
+            affine = affine.and_then(|(c, k)| {
+                let c = c.iter().map(|a| a.checked_neg()).collect::<Option<_>>()?;
+                Some((c, k.checked_neg()?))
+            });
+        }
+        match affine {
+            Some((c, k)) => Self::from_affine(c, k, inputs),
+            None => Self {
+                affine: None,
+                range,
+            },
+        }
+    }
+
+    /// Input multiples of a purely linear value.
+    fn multiples(self) -> Option<Vec<i128>> {
+        self.affine.filter(|(_, k)| *k == 0).map(|(c, _)| c)
+    }
+
+    fn sum(values: Vec<Self>, inputs: &[Port]) -> Self {
+        if values.iter().all(|v| v.affine.is_some()) {
+            let affine = values.into_iter().filter_map(|v| v.affine).try_fold(
+                (vec![0i128; inputs.len()], 0i128),
+                |(mut coeffs, constant), (c, k)| {
+                    for (sum, a) in coeffs.iter_mut().zip(c) {
+                        *sum = sum.checked_add(a)?;
+                    }
+                    Some((coeffs, constant.checked_add(k)?))
+                },
+            );
+            return match affine {
+                Some((coeffs, constant)) => Self::from_affine(coeffs, constant, inputs),
+                None => Self {
+                    affine: None,
+                    range: None,
+                },
+            };
+        }
+        let range = values.iter().try_fold((0i128, 0i128), |(lo, hi), v| {
+            let (a, b) = v.range?;
+            Some((lo.checked_add(a)?, hi.checked_add(b)?))
+        });
+        Self {
+            affine: None,
+            range,
+        }
+    }
+
+    /// The range, or the full `i128` range when it does not fit.
+    fn saturated_range(&self) -> (i128, i128) {
+        self.range.unwrap_or((i128::MIN, i128::MAX))
+    }
+}
+
+impl Netlist {
+    /// Name of the signal a [`Source`] refers to.
+    pub fn source_name(&self, source: Source) -> &str {
+        match source {
+            Source::Input(i) => &self.inputs[i].name,
+            Source::Wire(i) => &self.wires[i].name,
+        }
+    }
+
+    /// Bit width of the signal a [`Source`] refers to.
+    pub fn source_width(&self, source: Source) -> usize {
+        match source {
+            Source::Input(i) => self.inputs[i].width,
+            Source::Wire(i) => self.wires[i].width,
+        }
+    }
+
+    /// Exact values of every wire, then of every output.
+    fn values(&self) -> (Vec<Value>, Vec<Value>) {
+        let unit = |i: usize| {
+            let mut coeffs = vec![0; self.inputs.len()];
+            coeffs[i] = 1;
+            Value::from_affine(coeffs, 0, &self.inputs)
+        };
+        let inputs: Vec<Value> = (0..self.inputs.len()).map(unit).collect();
+        let mut wires: Vec<Value> = Vec::with_capacity(self.wires.len());
+        let sum = |terms: &[Term], wires: &[Value]| {
+            let values = terms
+                .iter()
+                .map(|t| {
+                    let v = match t.source {
+                        Source::Input(i) => &inputs[i],
+                        Source::Wire(i) => &wires[i],
+                    };
+                    v.term(t, &self.inputs)
+                })
+                .collect();
+            Value::sum(values, &self.inputs)
+        };
+        for wire in &self.wires {
+            let v = match &wire.expr {
+                WireExpr::Shift { input, power } => inputs[*input].term(
+                    &Term {
+                        op: TermOp::Add,
+                        source: Source::Input(*input),
+                        shift: *power as isize,
+                    },
+                    &self.inputs,
+                ),
+                WireExpr::Sum(terms) => sum(terms, &wires),
+                WireExpr::Constant(k) => {
+                    Value::from_affine(vec![0; self.inputs.len()], *k, &self.inputs)
+                }
+            };
+            wires.push(v);
+        }
+        let outputs = self.outputs.iter().map(|o| sum(&o.terms, &wires)).collect();
+        (wires, outputs)
+    }
+
+    /// Value range `(min, max)` of every wire, indexed like [`Netlist::wires`],
+    /// over all input values (assuming no signal wraps).
+    ///
+    /// A range that does not fit an `i128` is reported as `(i128::MIN, i128::MAX)`.
+    pub fn wire_ranges(&self) -> Vec<(i128, i128)> {
+        self.values().0.iter().map(Value::saturated_range).collect()
+    }
+
+    /// Value range `(min, max)` of every output, indexed like [`Netlist::outputs`]
+    /// (see [`Netlist::wire_ranges()`]).
+    pub fn output_ranges(&self) -> Vec<(i128, i128)> {
+        self.values().1.iter().map(Value::saturated_range).collect()
+    }
+
+    /// Multiple of every input computed by each wire, indexed like
+    /// [`Netlist::wires`]: `Some(c)` when the wire equals `Σ c[i] · inputs[i]`
+    /// exactly, `None` for constants and for values a right shift truncates.
+    pub fn wire_multiples(&self) -> Vec<Option<Vec<i128>>> {
+        self.values().0.into_iter().map(Value::multiples).collect()
+    }
+
+    /// Multiple of every input computed by each output, indexed like
+    /// [`Netlist::outputs`] (see [`Netlist::wire_multiples()`]).
+    pub fn output_multiples(&self) -> Vec<Option<Vec<i128>>> {
+        self.values().1.into_iter().map(Value::multiples).collect()
+    }
+
+    /// Shrink every wire and output port to the smallest width holding its range.
+    ///
+    /// All sums are then still exact: each operand holds its true value and
+    /// each result fits its destination, so no intermediate value ever wraps.
+    /// Signals whose range does not fit an `i128` (inputs and coefficients
+    /// well beyond 64 bits) keep the width they were built with.
+    pub fn minimize_widths(&mut self) {
+        let (wires, outputs) = self.values();
+        for (wire, v) in self.wires.iter_mut().zip(wires) {
+            if let Some((lo, hi)) = v.range {
+                wire.width = signed_width(lo, hi);
+            }
+        }
+        for (output, v) in self.outputs.iter_mut().zip(outputs) {
+            if let Some((lo, hi)) = v.range {
+                output.port.width = signed_width(lo, hi);
+            }
+        }
+    }
+
+    /// Total number of two-input adders/subtractors in the network.
+    ///
+    /// A sum of $k$ terms costs $k - 1$ adders; shifts are free wiring.
+    pub fn adder_count(&self) -> usize {
+        let wires: usize = self
+            .wires
+            .iter()
+            .map(|w| match &w.expr {
+                WireExpr::Shift { .. } | WireExpr::Constant(_) => 0,
+                WireExpr::Sum(terms) => terms.len().saturating_sub(1),
+            })
+            .sum();
+        let outputs: usize = self
+            .outputs
+            .iter()
+            .map(|o| o.terms.len().saturating_sub(1))
+            .sum();
+        wires + outputs
+    }
+
+    /// Adder depth (longest chain of adders from an input to an output).
+    ///
+    /// Each sum is assumed to be evaluated left to right, as a synthesis tool
+    /// reads `a + b + c`, so a flat sum of $k$ terms is $k - 1$ adders deep.
+    pub fn adder_depth(&self) -> usize {
+        self.output_depths().into_iter().max().unwrap_or(0)
+    }
+
+    /// Adder depth of every output, indexed like [`Netlist::outputs`].
+    pub fn output_depths(&self) -> Vec<usize> {
+        let depths = self.wire_depths();
+        self.outputs
+            .iter()
+            .map(|o| self.sum_depth(&o.terms, &depths))
+            .collect()
+    }
+
+    /// Number of operands reading each wire, indexed like [`Netlist::wires`].
+    ///
+    /// A sum wire with a fanout above one is shared hardware (an LCSRe or
+    /// cross-CSE sub-expression, or a product reused by folded coefficients).
+    pub fn wire_fanout(&self) -> Vec<usize> {
+        let mut fanout = vec![0; self.wires.len()];
+        let sums = self
+            .wires
+            .iter()
+            .filter_map(|w| match &w.expr {
+                WireExpr::Sum(terms) => Some(terms),
+                _ => None,
+            })
+            .chain(self.outputs.iter().map(|o| &o.terms));
+        for term in sums.flatten() {
+            if let Source::Wire(i) = term.source {
+                fanout[i] += 1;
+            }
+        }
+        fanout
+    }
+
+    /// Adder depth of every wire, indexed like [`Netlist::wires`].
+    pub fn wire_depths(&self) -> Vec<usize> {
+        let mut depths: Vec<usize> = Vec::with_capacity(self.wires.len());
+        for wire in &self.wires {
+            let d = match &wire.expr {
+                WireExpr::Shift { .. } | WireExpr::Constant(_) => 0,
+                WireExpr::Sum(terms) => self.sum_depth(terms, &depths),
+            };
+            depths.push(d);
+        }
+        depths
+    }
+
+    /// Adder depth of a term given the depths of the wires before it.
+    pub(crate) fn term_depth(term: &Term, depths: &[usize]) -> usize {
+        match term.source {
+            Source::Input(_) => 0,
+            Source::Wire(i) => depths[i],
+        }
+    }
+
+    /// Depth of a left-to-right chain: term `i` enters adder `max(i, 1)`.
+    fn sum_depth(&self, terms: &[Term], depths: &[usize]) -> usize {
+        let k = terms.len();
+        terms
+            .iter()
+            .enumerate()
+            .map(|(i, t)| Self::term_depth(t, depths) + k - i.max(1))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift_wire(name: &str, power: usize) -> Wire {
+        Wire {
+            name: name.to_string(),
+            width: 12,
+            expr: WireExpr::Shift { input: 0, power },
+            comment: None,
+        }
+    }
+
+    fn netlist(wires: Vec<Wire>, terms: Vec<Term>) -> Netlist {
+        Netlist {
+            module_name: "m".to_string(),
+            inputs: vec![Port {
+                name: "x".to_string(),
+                width: 8,
+                frac_bits: 0,
+                comment: None,
+            }],
+            wires,
+            outputs: vec![Output {
+                port: Port {
+                    name: "y".to_string(),
+                    width: 12,
+                    frac_bits: 0,
+                    comment: None,
+                },
+                terms,
+                comment: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_chain_cost() {
+        let wires = (0..4).map(|p| shift_wire(&format!("s{}", p), p)).collect();
+        let terms = (0..4).map(|i| Term::add(Source::Wire(i))).collect();
+        let n = netlist(wires, terms);
+        assert_eq!(n.adder_count(), 3);
+        assert_eq!(n.adder_depth(), 3);
+        assert_eq!(n.source_name(Source::Wire(2)), "s2");
+    }
+
+    #[test]
+    fn test_tree_cost() {
+        let mut wires: Vec<Wire> = (0..4).map(|p| shift_wire(&format!("s{}", p), p)).collect();
+        for (name, a, b) in [("t0", 0, 1), ("t1", 2, 3)] {
+            wires.push(Wire {
+                name: name.to_string(),
+                width: 12,
+                expr: WireExpr::Sum(vec![Term::add(Source::Wire(a)), Term::add(Source::Wire(b))]),
+                comment: None,
+            });
+        }
+        let n = netlist(
+            wires,
+            vec![Term::add(Source::Wire(4)), Term::add(Source::Wire(5))],
+        );
+        assert_eq!(n.adder_count(), 3);
+        assert_eq!(n.adder_depth(), 2);
+        assert_eq!(n.wire_depths(), vec![0, 0, 0, 0, 1, 1]);
+        assert_eq!(n.output_depths(), vec![2]);
+        assert_eq!(n.wire_fanout(), vec![1, 1, 1, 1, 1, 1]);
+        assert_eq!(n.wire_multiples()[5], Some(vec![12]));
+        assert_eq!(n.output_multiples(), vec![Some(vec![15])]);
+    }
+
+    #[test]
+    fn test_constant_zero_cost() {
+        let n = netlist(Vec::new(), Vec::new());
+        assert_eq!(n.adder_count(), 0);
+        assert_eq!(n.adder_depth(), 0);
+    }
+}
@@ -0,0 +1,341 @@
+//! Hardware cost reports.
+//!
+//! A [`CostReport`] summarizes a generated multiplier before synthesis: adder
+//! count and depth, how many adders LCSRe/cross-CSE sharing saved compared with
+//! building every coefficient as a flat CSD sum, the width of every wire and a
+//! ripple-carry area estimate. It prints as plain text or as JSON.
+//!
+//! The area model counts one full-adder cell per result bit of every two-input
+//! adder: a sum of $k$ terms into a $W$-bit signal costs $(k - 1) \cdot W$
+//! cells. On FPGAs with dedicated carry chains this is also roughly the LUT count.
+
+use std::fmt::{self, Write};
+
+use super::netlist::{Netlist, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, print_verilog, CsdMultiplierError,
+    MultiplierOptions, MultiplierSpec,
+};
+
+/// Cost of one wire or output port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalCost {
+    /// Signal name
+    pub name: String,
+    /// Bit width
+    pub width: usize,
+    /// Two-input adders driving the signal
+    pub adders: usize,
+    /// Adder depth from the input
+    pub depth: usize,
+    /// Operands reading the signal (always 0 for outputs)
+    pub fanout: usize,
+}
+
+/// Structured cost summary of a generated multiplier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostReport {
+    /// Module name
+    pub module_name: String,
+    /// Two-input adders/subtractors in the generated design
+    pub adders: usize,
+    /// Adder depth of the generated design
+    pub depth: usize,
+    /// Adders of the same coefficients built as flat CSD sums, without sharing
+    pub flat_adders: usize,
+    /// Adder depth of the flat CSD sums
+    pub flat_depth: usize,
+    /// Estimated full-adder cells (ripple-carry area)
+    pub full_adders: usize,
+    /// Every intermediate wire, in declaration order
+    pub wires: Vec<SignalCost>,
+    /// Every output port
+    pub outputs: Vec<SignalCost>,
+}
+
+impl CostReport {
+    /// Report on `netlist`, comparing against `flat`, the same coefficients
+    /// lowered without sub-expression sharing.
+    fn compare(netlist: &Netlist, flat: &Netlist) -> Self {
+        let depths = netlist.wire_depths();
+        let fanout = netlist.wire_fanout();
+        let wires: Vec<SignalCost> = netlist
+            .wires
+            .iter()
+            .enumerate()
+            .map(|(i, wire)| {
+                let terms = match &wire.expr {
+                    WireExpr::Sum(terms) => terms.len(),
+                    WireExpr::Shift { .. } | WireExpr::Constant(_) => 0,
+                };
+                SignalCost {
+                    name: wire.name.clone(),
+                    width: wire.width,
+                    adders: terms.saturating_sub(1),
+                    depth: depths[i],
+                    fanout: fanout[i],
+                }
+            })
+            .collect();
+        let outputs: Vec<SignalCost> = netlist
+            .outputs
+            .iter()
+            .zip(netlist.output_depths())
+            .map(|(output, depth)| SignalCost {
+                name: output.port.name.clone(),
+                width: output.port.width,
+                adders: output.terms.len().saturating_sub(1),
+                depth,
+                fanout: 0,
+            })
+            .collect();
+        let full_adders = wires
+            .iter()
+            .chain(&outputs)
+            .map(|s| s.adders * s.width)
+            .sum();
+        Self {
+            module_name: netlist.module_name.clone(),
+            adders: netlist.adder_count(),
+            depth: netlist.adder_depth(),
+            flat_adders: flat.adder_count(),
+            flat_depth: flat.adder_depth(),
+            full_adders,
+            wires,
+            outputs,
+        }
+    }
+
+    /// Adders saved by sharing, relative to flat CSD.
+    pub fn adders_saved(&self) -> usize {
+        self.flat_adders.saturating_sub(self.adders)
+    }
+
+    /// Print the report as a JSON object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csd::csd_multiplier::report::generate_csd_multiplier_with_report;
+    /// use csd::csd_multiplier::MultiplierOptions;
+    ///
+    /// let (_, report) =
+    ///     generate_csd_multiplier_with_report("+0-", 8, 2, &MultiplierOptions::new()).unwrap();
+    /// let json = report.to_json();
+    /// assert!(json.starts_with("{\n  \"module\": \"csd_multiplier\",\n  \"adders\": 1,"));
+    /// assert!(json.contains("{\"name\": \"result\", \"width\": 10, \"adders\": 1, \"depth\": 1, \"fanout\": 0}"));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let signals = |signals: &[SignalCost]| {
+            signals
+                .iter()
+                .map(|s| {
+                    format!(
+                        "    {{\"name\": {}, \"width\": {}, \"adders\": {}, \"depth\": {}, \"fanout\": {}}}",
+                        json_string(&s.name),
+                        s.width,
+                        s.adders,
+                        s.depth,
+                        s.fanout
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",\n")
+        };
+        let mut j = String::new();
+        writeln!(j, "{{").unwrap();
+        writeln!(j, "  \"module\": {},", json_string(&self.module_name)).unwrap();
+        writeln!(j, "  \"adders\": {},", self.adders).unwrap();
+        writeln!(j, "  \"depth\": {},", self.depth).unwrap();
+        writeln!(j, "  \"flat_adders\": {},", self.flat_adders).unwrap();
+        writeln!(j, "  \"flat_depth\": {},", self.flat_depth).unwrap();
+        writeln!(j, "  \"adders_saved\": {},", self.adders_saved()).unwrap();
+        writeln!(j, "  \"full_adders\": {},", self.full_adders).unwrap();
+        writeln!(j, "  \"wires\": [\n{}\n  ],", signals(&self.wires)).unwrap();
+        writeln!(j, "  \"outputs\": [\n{}\n  ]", signals(&self.outputs)).unwrap();
+        writeln!(j, "}}").unwrap();
+        j.replace("[\n\n  ]", "[]")
+    }
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Module {}", self.module_name)?;
+        writeln!(
+            f,
+            "  adders: {} (flat CSD: {}, saved: {})",
+            self.adders,
+            self.flat_adders,
+            self.adders_saved()
+        )?;
+        writeln!(f, "  depth: {} (flat CSD: {})", self.depth, self.flat_depth)?;
+        writeln!(f, "  full-adder cells: {}", self.full_adders)?;
+        for s in self.wires.iter().chain(&self.outputs) {
+            writeln!(
+                f,
+                "  {:<16} {:>3} bits  {} adders  depth {}  fanout {}",
+                s.name, s.width, s.adders, s.depth, s.fanout
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Estimated full-adder cells of `netlist`, as in [`CostReport::full_adders`].
+pub(super) fn full_adder_cells(netlist: &Netlist) -> usize {
+    CostReport::compare(netlist, netlist).full_adders
+}
+
+/// Quote and escape `s` as a JSON string.
+pub(super) fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Generate Verilog for a single multiplier together with its [`CostReport`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::report::generate_csd_multiplier_with_report;
+/// use csd::csd_multiplier::MultiplierOptions;
+///
+/// let (verilog, report) =
+///     generate_csd_multiplier_with_report("+0-0+0-0+0-0", 8, 11, &MultiplierOptions::new())
+///         .unwrap();
+/// assert!(verilog.contains("_pat"));
+/// assert_eq!((report.adders, report.flat_adders, report.adders_saved()), (3, 5, 2));
+/// ```
+pub fn generate_csd_multiplier_with_report(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<(String, CostReport), CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    let flat_options = options.clone().share_patterns(false);
+    let flat = csd_multiplier_netlist(csd_str, input_width, max_power, &flat_options)?;
+    let verilog = print_verilog(&netlist, &[csd_str], options);
+    Ok((verilog, CostReport::compare(&netlist, &flat)))
+}
+
+/// Generate Verilog for a multi-coefficient block together with its [`CostReport`].
+///
+/// The flat baseline builds every coefficient separately, so the savings
+/// include both cross-CSE and folded (duplicate or scaled) coefficients.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_with_report(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<(String, CostReport), CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    let flat_options = options.clone().share_patterns(false);
+    let flat = csd_multipliers_netlist(coeffs, module_name, &flat_options)?;
+    let csds: Vec<&str> = coeffs.iter().map(|spec| spec.csd.as_str()).collect();
+    let verilog = print_verilog(&netlist, &csds, options);
+    Ok((verilog, CostReport::compare(&netlist, &flat)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::{generate_csd_multiplier_with, AdderStructure};
+
+    #[test]
+    fn test_single_report() {
+        let options = MultiplierOptions::new();
+        let (verilog, report) =
+            generate_csd_multiplier_with_report("+00-00+0+", 8, 8, &options).unwrap();
+        assert_eq!(
+            verilog,
+            generate_csd_multiplier_with("+00-00+0+", 8, 8, &options).unwrap()
+        );
+        assert_eq!((report.adders, report.depth), (3, 3));
+        assert_eq!((report.flat_adders, report.adders_saved()), (3, 0));
+        // Three adders into the 16-bit result
+        assert_eq!(report.full_adders, 48);
+        let widths: Vec<(&str, usize)> = report
+            .wires
+            .iter()
+            .map(|w| (w.name.as_str(), w.width))
+            .collect();
+        assert_eq!(
+            widths,
+            vec![
+                ("x_shift8", 16),
+                ("x_shift5", 13),
+                ("x_shift2", 10),
+                ("x_shift0", 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tree_report() {
+        let options = MultiplierOptions::new().adder_structure(AdderStructure::Tree);
+        let (_, report) = generate_csd_multiplier_with_report("+00-00+0+", 8, 8, &options).unwrap();
+        assert_eq!((report.adders, report.depth, report.flat_depth), (3, 2, 2));
+        let t: Vec<&SignalCost> = report.wires.iter().filter(|w| w.adders > 0).collect();
+        assert_eq!(t.len(), 2);
+        assert!(t.iter().all(|w| w.depth == 1 && w.fanout == 1));
+    }
+
+    #[test]
+    fn test_multi_report() {
+        let coeffs = vec![
+            MultiplierSpec::from_integer("y0", 229, 8),
+            MultiplierSpec::from_integer("y1", -187, 8),
+            MultiplierSpec::from_integer("y2", 229, 8),
+        ];
+        let (_, report) =
+            generate_csd_multipliers_with_report(&coeffs, "taps", &MultiplierOptions::new())
+                .unwrap();
+        assert!(report.adders < report.flat_adders);
+        assert_eq!(report.flat_adders, 9);
+        assert_eq!(report.outputs.len(), 3);
+        assert!(report.wires.iter().any(|w| w.fanout > 1));
+        let text = report.to_string();
+        assert!(text.starts_with("Module taps\n"));
+        assert!(text.contains(&format!("saved: {}", report.adders_saved())));
+    }
+
+    #[test]
+    fn test_json() {
+        let (_, report) =
+            generate_csd_multiplier_with_report("000", 8, 2, &MultiplierOptions::new()).unwrap();
+        let expected = r###"{
+  "module": "csd_multiplier",
+  "adders": 0,
+  "depth": 0,
+  "flat_adders": 0,
+  "flat_depth": 0,
+  "adders_saved": 0,
+  "full_adders": 0,
+  "wires": [],
+  "outputs": [
+    {"name": "result", "width": 1, "adders": 0, "depth": 0, "fanout": 0}
+  ]
+}
+"###;
+        assert_eq!(report.to_json(), expected);
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\u000a\"");
+    }
+}
@@ -0,0 +1,390 @@
+//! Bit-accurate simulation of generated netlists.
+//!
+//! [`simulate()`] evaluates a [`Netlist`] with the semantics of the emitted
+//! Verilog: every signal is a two's-complement value of its declared width,
+//! sums wrap around at the width of the signal they are assigned to, `<<<`
+//! is a left shift and `>>>` an arithmetic (sign-preserving) right shift of
+//! the already-wrapped operand. The VHDL and SystemVerilog backends describe
+//! the same arithmetic, so one simulation covers all of them.
+//!
+//! [`verify_exhaustive()`] drives every value of the input width and compares
+//! each output with `x * coefficient`, which catches both structural errors
+//! and ports or wires too narrow for the product — without an HDL simulator.
+//! Values are held in `i128`, so signals must be narrower than 128 bits.
+//!
+//! Fractional coefficients are checked on their scaled integer value: with $F$
+//! fractional digits the exact product is $x \cdot C \cdot 2^F$, which
+//! [`verify_csd_multiplier()`] truncates or rounds exactly as requested by
+//! [`MultiplierOptions::output_frac_bits()`].
+
+use super::netlist::{Netlist, Source, Term, WireExpr};
+use super::{
+    aligned_values, csd_multiplier_netlist, csd_multipliers_netlist, csd_value, split_binary_point,
+    CsdMultiplierError, MultiplierOptions, MultiplierSpec, OutputRounding, TermOp,
+};
+
+/// Wrap `value` to a signed integer of `width` bits.
+pub(super) fn wrap(value: i128, width: usize) -> i128 {
+    if width >= 128 {
+        return value;
+    }
+    let unused = (128 - width) as u32;
+    value.wrapping_shl(unused).wrapping_shr(unused)
+}
+
+/// Value of one term given the values of inputs and earlier wires.
+fn term_value(term: &Term, inputs: &[i128], wires: &[i128]) -> i128 {
+    let v = match term.source {
+        Source::Input(i) => inputs[i],
+        Source::Wire(i) => wires[i],
+    };
+    let v = if term.shift >= 0 {
+        v.wrapping_shl(term.shift as u32)
+    } else {
+        v >> (-term.shift).min(127)
+    };
+    match term.op {
+        TermOp::Add => v,
+        TermOp::Sub => v.wrapping_neg(),
+    }
+}
+
+/// Wrapped value of a signed sum assigned to a `width`-bit signal.
+fn sum_value(terms: &[Term], width: usize, inputs: &[i128], wires: &[i128]) -> i128 {
+    let total = terms.iter().fold(0i128, |acc, t| {
+        acc.wrapping_add(term_value(t, inputs, wires))
+    });
+    wrap(total, width)
+}
+
+/// Evaluate every output of `netlist` for one set of input values.
+///
+/// Input values are first wrapped to their port widths, exactly as driving
+/// the port from a wider signal would.
+///
+/// # Panics
+///
+/// Panics if `inputs` does not have one value per input port.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+/// use csd::csd_multiplier::simulator::simulate;
+///
+/// let netlist = csd_multiplier_netlist("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+/// assert_eq!(simulate(&netlist, &[-5]), vec![-5 * 102]);
+/// ```
+pub fn simulate(netlist: &Netlist, inputs: &[i128]) -> Vec<i128> {
+    assert_eq!(
+        inputs.len(),
+        netlist.inputs.len(),
+        "one value per input port is required"
+    );
+    let inputs: Vec<i128> = inputs
+        .iter()
+        .zip(&netlist.inputs)
+        .map(|(&v, port)| wrap(v, port.width))
+        .collect();
+    let mut wires: Vec<i128> = Vec::with_capacity(netlist.wires.len());
+    for wire in &netlist.wires {
+        let v = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                wrap(inputs[*input].wrapping_shl(*power as u32), wire.width)
+            }
+            WireExpr::Sum(terms) => sum_value(terms, wire.width, &inputs, &wires),
+            WireExpr::Constant(value) => wrap(*value, wire.width),
+        };
+        wires.push(v);
+    }
+    netlist
+        .outputs
+        .iter()
+        .map(|o| sum_value(&o.terms, o.port.width, &inputs, &wires))
+        .collect()
+}
+
+/// A simulated output that differs from the exact product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Output port name
+    pub output: String,
+    /// Input value
+    pub input: i128,
+    /// Simulated output value
+    pub actual: i128,
+    /// Expected value: the exact product, requantized to the output format
+    pub expected: i128,
+}
+
+/// Result of an exhaustive verification run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Number of input values simulated
+    pub vectors: u128,
+    /// Number of (input, output) pairs that differed from the exact product
+    pub mismatches: u128,
+    /// The first mismatch found, if any
+    pub first_mismatch: Option<Mismatch>,
+}
+
+impl VerifyReport {
+    /// Whether every output matched the exact product for every input.
+    pub fn passed(&self) -> bool {
+        self.mismatches == 0
+    }
+}
+
+/// Simulate every value of the (single) input and compare output `k` with
+/// `x * coefficients[k]`.
+///
+/// The run takes $2^{W_{\text{in}}}$ simulations, so it is meant for the input
+/// widths of typical DSP datapaths (up to about 20 bits).
+///
+/// # Panics
+///
+/// Panics if the netlist does not have exactly one input or `coefficients`
+/// does not have one entry per output.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+/// use csd::csd_multiplier::simulator::verify_exhaustive;
+///
+/// let netlist = csd_multiplier_netlist("+00-00+0+", 8, 8, &MultiplierOptions::new()).unwrap();
+/// let report = verify_exhaustive(&netlist, &[229]);
+/// assert!(report.passed());
+/// assert_eq!(report.vectors, 256);
+/// ```
+pub fn verify_exhaustive(netlist: &Netlist, coefficients: &[i128]) -> VerifyReport {
+    assert_eq!(
+        coefficients.len(),
+        netlist.outputs.len(),
+        "one coefficient per output is required"
+    );
+    verify_against(netlist, |k, x| x * coefficients[k])
+}
+
+/// Exact product with `frac_bits` fractional bits, reduced to the output
+/// format requested in `options`.
+fn requantize(product: i128, frac_bits: usize, options: &MultiplierOptions) -> i128 {
+    let target = options.output_frac_bits.unwrap_or(frac_bits);
+    if target >= frac_bits {
+        return product << (target - frac_bits);
+    }
+    let drop = frac_bits - target;
+    match options.rounding {
+        OutputRounding::Truncate => product >> drop,
+        OutputRounding::Nearest => (product + (1 << (drop - 1))) >> drop,
+    }
+}
+
+/// Simulate every input value and compare output `k` with `expected(k, x)`.
+fn verify_against(netlist: &Netlist, expected: impl Fn(usize, i128) -> i128) -> VerifyReport {
+    assert_eq!(
+        netlist.inputs.len(),
+        1,
+        "exactly one input port is required"
+    );
+    let width = netlist.inputs[0].width;
+    let lo = -(1i128 << (width - 1));
+    let hi = 1i128 << (width - 1);
+    let mut report = VerifyReport {
+        vectors: 0,
+        mismatches: 0,
+        first_mismatch: None,
+    };
+    for x in lo..hi {
+        let values = simulate(netlist, &[x]);
+        for (k, (output, &actual)) in netlist.outputs.iter().zip(&values).enumerate() {
+            let expected = expected(k, x);
+            if actual != expected {
+                report.mismatches += 1;
+                report.first_mismatch.get_or_insert_with(|| Mismatch {
+                    output: output.port.name.clone(),
+                    input: x,
+                    actual,
+                    expected,
+                });
+            }
+        }
+        report.vectors += 1;
+    }
+    report
+}
+
+/// Build the netlist for one coefficient and verify it exhaustively.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::MultiplierOptions;
+/// use csd::csd_multiplier::simulator::verify_csd_multiplier;
+///
+/// let report = verify_csd_multiplier("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+/// assert!(report.passed());
+/// ```
+pub fn verify_csd_multiplier(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<VerifyReport, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    let (_, frac_bits) = split_binary_point(csd_str);
+    let coefficient = csd_value(csd_str);
+    Ok(verify_against(&netlist, |_, x| {
+        requantize(x * coefficient, frac_bits, options)
+    }))
+}
+
+/// Build the netlist for a coefficient set and verify it exhaustively.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn verify_csd_multipliers(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<VerifyReport, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    let (coefficients, frac_bits) = aligned_values(coeffs);
+    Ok(verify_against(&netlist, |k, x| {
+        requantize(x * coefficients[k], frac_bits, options)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::AdderStructure;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap(511, 10), 511);
+        assert_eq!(wrap(512, 10), -512);
+        assert_eq!(wrap(-513, 10), 511);
+        assert_eq!(wrap(-1, 1), -1);
+        assert_eq!(wrap(1, 1), -1);
+        assert_eq!(wrap(i128::MIN, 128), i128::MIN);
+    }
+
+    #[test]
+    fn test_right_shift_is_arithmetic() {
+        let netlist = csd_multiplier_netlist("-0+0-0+0", 6, 7, &MultiplierOptions::new()).unwrap();
+        assert_eq!(simulate(&netlist, &[7]), vec![7 * -102]);
+        assert_eq!(simulate(&netlist, &[-32]), vec![-32 * -102]);
+    }
+
+    #[test]
+    fn test_inputs_are_wrapped() {
+        let netlist = csd_multiplier_netlist("+", 4, 0, &MultiplierOptions::new()).unwrap();
+        assert_eq!(simulate(&netlist, &[9]), vec![-7]);
+    }
+
+    #[test]
+    fn test_verify_all_structures() {
+        for structure in [AdderStructure::Chain, AdderStructure::Tree] {
+            for share in [true, false] {
+                let options = MultiplierOptions::new()
+                    .adder_structure(structure)
+                    .share_patterns(share);
+                for csd in ["+0-0+0-0+0-0", "+00-00+00-00", "-0+0-0+0", "+0-0+00-0+0"] {
+                    let report = verify_csd_multiplier(csd, 6, csd.len() - 1, &options).unwrap();
+                    assert!(report.passed(), "{} {:?}", csd, report.first_mismatch);
+                    assert_eq!(report.vectors, 64);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_detects_overflow() {
+        // 5 > 2^2, so the legacy 8 + 2 bit output cannot hold 5 * -128
+        let legacy = MultiplierOptions::new().legacy_widths(true);
+        let report = verify_csd_multiplier("+0+", 8, 2, &legacy).unwrap();
+        assert!(!report.passed());
+        assert_eq!(
+            report.first_mismatch,
+            Some(Mismatch {
+                output: "result".to_string(),
+                input: -128,
+                actual: 384,
+                expected: -640,
+            })
+        );
+    }
+
+    #[test]
+    fn test_tight_widths_never_overflow() {
+        for csd in ["+0+", "-00", "+0+0+", "-0-0-0-"] {
+            let report =
+                verify_csd_multiplier(csd, 8, csd.len() - 1, &MultiplierOptions::new()).unwrap();
+            assert!(report.passed(), "{} {:?}", csd, report.first_mismatch);
+        }
+    }
+
+    #[test]
+    fn test_verify_multi_cse() {
+        let coeffs: Vec<MultiplierSpec> = ["+00-00+0+", "-0+00-00+", "+0-0+0-00"]
+            .iter()
+            .enumerate()
+            .map(|(i, csd)| MultiplierSpec {
+                name: format!("y{}", i),
+                csd: csd.to_string(),
+                input_width: 7,
+                max_power: 8,
+            })
+            .collect();
+        let report = verify_csd_multipliers(&coeffs, "taps", &MultiplierOptions::new()).unwrap();
+        assert!(report.passed(), "{:?}", report.first_mismatch);
+        assert_eq!(report.vectors, 128);
+    }
+
+    #[test]
+    fn test_verify_fractional_requantized() {
+        let csd = crate::csd::to_csd(-5.375, 3);
+        let frac_options = [
+            MultiplierOptions::new(),
+            MultiplierOptions::new().output_frac_bits(0),
+            MultiplierOptions::new().output_frac_bits(1),
+            MultiplierOptions::new()
+                .output_frac_bits(1)
+                .rounding(OutputRounding::Nearest),
+            MultiplierOptions::new()
+                .output_frac_bits(0)
+                .rounding(OutputRounding::Nearest)
+                .adder_structure(AdderStructure::Tree),
+            MultiplierOptions::new().output_frac_bits(5),
+        ];
+        let max_power = csd.find('.').unwrap() - 1;
+        for options in &frac_options {
+            let report = verify_csd_multiplier(&csd, 7, max_power, options).unwrap();
+            assert!(report.passed(), "{} {:?}", csd, report.first_mismatch);
+        }
+    }
+
+    #[test]
+    fn test_requantize() {
+        let nearest = MultiplierOptions::new()
+            .output_frac_bits(0)
+            .rounding(OutputRounding::Nearest);
+        // -2.5 rounds to -2 (ties toward +inf), truncation gives -3
+        assert_eq!(requantize(-10, 2, &nearest), -2);
+        assert_eq!(
+            requantize(-10, 2, &MultiplierOptions::new().output_frac_bits(0)),
+            -3
+        );
+        assert_eq!(requantize(11, 2, &nearest), 3);
+        assert_eq!(
+            requantize(3, 1, &MultiplierOptions::new().output_frac_bits(3)),
+            12
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! SystemVerilog backend.
+//!
+//! Prints a [`Netlist`] as a module with `logic signed` signals driven from a
+//! single `always_comb` block. Signal widths are sized from the exact value
+//! ranges at the generated input width, which don't scale linearly with it, so
+//! the width is a `localparam int W_IN` rather than an overridable parameter:
+//! signals at least that wide are written as `W_IN` plus an offset, constants
+//! and narrower signals (e.g. a rounding constant) as absolute widths.
+//!
+//! Multi-coefficient modules can optionally pack all coefficient outputs into
+//! one `output logic signed [N-1:0][W-1:0]` port, which is easier to connect to
+//! generic filter wrappers than one port per [`MultiplierSpec`].
+
+use std::fmt::Write;
+
+use super::netlist::{Netlist, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, verilog_constant, verilog_sum,
+    CsdMultiplierError, MultiplierOptions, MultiplierSpec,
+};
+
+/// Output options for [`emit_systemverilog()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::systemverilog::SystemVerilogOptions;
+///
+/// let options = SystemVerilogOptions::new().pack_outputs("y");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SystemVerilogOptions {
+    packed_outputs: Option<String>,
+}
+
+impl SystemVerilogOptions {
+    /// Create options with one output port per coefficient.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pack all outputs into a single `[N-1:0][W-1:0]` port.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the packed output port; element `i` is coefficient `i`
+    pub fn pack_outputs(mut self, name: &str) -> Self {
+        self.packed_outputs = Some(name.to_string());
+        self
+    }
+}
+
+/// Most significant bit index of a `width`-bit signal, relative to `W_IN` unless
+/// the signal is narrower than the input.
+fn sv_msb(width: usize, input_width: usize) -> String {
+    if width < input_width {
+        return (width - 1).to_string();
+    }
+    match width - input_width {
+        0 => "W_IN-1".to_string(),
+        1 => "W_IN".to_string(),
+        k => format!("W_IN+{}", k - 1),
+    }
+}
+
+/// Print a [`Netlist`] as a parameterized SystemVerilog module.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+/// use csd::csd_multiplier::systemverilog::{emit_systemverilog, SystemVerilogOptions};
+///
+/// let netlist = csd_multiplier_netlist("+0-", 8, 2, &MultiplierOptions::new()).unwrap();
+/// let sv = emit_systemverilog(&netlist, &SystemVerilogOptions::new());
+/// assert!(sv.contains("localparam int W_IN = 8"));
+/// assert!(sv.contains("output logic signed [W_IN+1:0] result"));
+/// assert!(sv.contains("always_comb begin"));
+/// ```
+pub fn emit_systemverilog(netlist: &Netlist, options: &SystemVerilogOptions) -> String {
+    let mut sv = String::new();
+    let input_width = netlist.inputs.first().map_or(1, |p| p.width);
+
+    // --- module header ---
+    writeln!(sv).unwrap();
+    writeln!(sv, "module {} #(", netlist.module_name).unwrap();
+    writeln!(sv, "    localparam int W_IN = {}", input_width).unwrap();
+    writeln!(sv, ") (").unwrap();
+    let mut ports: Vec<(String, Option<String>)> = netlist
+        .inputs
+        .iter()
+        .map(|p| {
+            (
+                format!(
+                    "input  logic signed [{}:0] {}",
+                    sv_msb(p.width, input_width),
+                    p.name
+                ),
+                p.comment.clone(),
+            )
+        })
+        .collect();
+    let packed_width = netlist
+        .outputs
+        .iter()
+        .map(|o| o.port.width)
+        .max()
+        .unwrap_or(1);
+    match &options.packed_outputs {
+        Some(name) => {
+            let elements: Vec<String> = netlist
+                .outputs
+                .iter()
+                .enumerate()
+                .map(|(i, o)| format!("{}[{}]: {}", name, i, o.port.name))
+                .collect();
+            ports.push((
+                format!(
+                    "output logic signed [{}:0][{}:0] {}",
+                    netlist.outputs.len().max(1) - 1,
+                    sv_msb(packed_width, input_width),
+                    name
+                ),
+                Some(elements.join(", ")),
+            ));
+        }
+        None => ports.extend(netlist.outputs.iter().map(|o| {
+            (
+                format!(
+                    "output logic signed [{}:0] {}",
+                    sv_msb(o.port.width, input_width),
+                    o.port.name
+                ),
+                o.port.comment.clone(),
+            )
+        })),
+    }
+    for (i, (decl, comment)) in ports.iter().enumerate() {
+        let sep = if i + 1 == ports.len() { "" } else { "," };
+        match comment {
+            Some(c) => writeln!(sv, "    {}{} // {}", decl, sep, c).unwrap(),
+            None => writeln!(sv, "    {}{}", decl, sep).unwrap(),
+        }
+    }
+    writeln!(sv, ");").unwrap();
+
+    // --- signal declarations ---
+    if !netlist.wires.is_empty() {
+        writeln!(sv).unwrap();
+        for wire in &netlist.wires {
+            let msb = match wire.expr {
+                WireExpr::Constant(_) => (wire.width - 1).to_string(),
+                _ => sv_msb(wire.width, input_width),
+            };
+            writeln!(sv, "    logic signed [{}:0] {};", msb, wire.name).unwrap();
+        }
+    }
+
+    // --- combinational logic ---
+    writeln!(sv).unwrap();
+    writeln!(sv, "    always_comb begin").unwrap();
+    let mut first = true;
+    let mut section = |sv: &mut String, comment: &Option<String>| {
+        if let Some(c) = comment {
+            if !first {
+                writeln!(sv).unwrap();
+            }
+            writeln!(sv, "        // {}", c).unwrap();
+        }
+        first = false;
+    };
+    for wire in &netlist.wires {
+        section(&mut sv, &wire.comment);
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                format!("{} <<< {}", netlist.inputs[*input].name, power)
+            }
+            WireExpr::Sum(terms) => verilog_sum(netlist, terms),
+            WireExpr::Constant(value) => verilog_constant(*value, wire.width),
+        };
+        writeln!(sv, "        {} = {};", wire.name, expr).unwrap();
+    }
+    for (i, output) in netlist.outputs.iter().enumerate() {
+        section(&mut sv, &output.comment);
+        let target = match &options.packed_outputs {
+            Some(name) => format!("{}[{}]", name, i),
+            None => output.port.name.clone(),
+        };
+        writeln!(
+            sv,
+            "        {} = {};",
+            target,
+            verilog_sum(netlist, &output.terms)
+        )
+        .unwrap();
+    }
+    writeln!(sv, "    end").unwrap();
+
+    writeln!(sv, "endmodule").unwrap();
+    sv
+}
+
+/// Generate a parameterized SystemVerilog CSD multiplier, the counterpart of
+/// [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::systemverilog::generate_csd_multiplier_sv;
+///
+/// let sv = generate_csd_multiplier_sv("+0-", 8, 2).unwrap();
+/// assert!(sv.contains("result = x_shift2 - x_shift0;"));
+/// ```
+pub fn generate_csd_multiplier_sv(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_sv_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_sv()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_sv_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_systemverilog(&netlist, &SystemVerilogOptions::new()))
+}
+
+/// Generate a SystemVerilog multi-coefficient module with cross-CSE, the
+/// counterpart of [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::MultiplierSpec;
+/// use csd::csd_multiplier::systemverilog::{generate_csd_multipliers_sv, SystemVerilogOptions};
+///
+/// let coeffs: Vec<MultiplierSpec> = ["+0-", "+0+"]
+///     .iter()
+///     .enumerate()
+///     .map(|(i, csd)| MultiplierSpec {
+///         name: format!("y{}", i),
+///         csd: csd.to_string(),
+///         input_width: 8,
+///         max_power: 2,
+///     })
+///     .collect();
+/// let options = SystemVerilogOptions::new().pack_outputs("y");
+/// let sv = generate_csd_multipliers_sv(&coeffs, "taps", &options).unwrap();
+/// assert!(sv.contains("output logic signed [1:0][W_IN+2:0] y"));
+/// assert!(sv.contains("y[1] = x_shift2 + x_shift0;"));
+/// ```
+pub fn generate_csd_multipliers_sv(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &SystemVerilogOptions,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_sv_with(coeffs, module_name, &MultiplierOptions::new(), options)
+}
+
+/// Same as [`generate_csd_multipliers_sv()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_sv_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    multiplier_options: &MultiplierOptions,
+    options: &SystemVerilogOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, multiplier_options)?;
+    Ok(emit_systemverilog(&netlist, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::OutputRounding;
+
+    fn specs(csds: &[&str], max_power: usize) -> Vec<MultiplierSpec> {
+        csds.iter()
+            .enumerate()
+            .map(|(i, csd)| MultiplierSpec {
+                name: format!("y{}", i),
+                csd: csd.to_string(),
+                input_width: 8,
+                max_power,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sv_golden() {
+        let sv = generate_csd_multiplier_sv("+0-0+0-0", 8, 7).unwrap();
+        let expected = r###"
+module csd_multiplier #(
+    localparam int W_IN = 8
+) (
+    input  logic signed [W_IN-1:0] x, // Input value
+    output logic signed [W_IN+6:0] result // Result of multiplication
+);
+
+    logic signed [W_IN+6:0] x_shift7;
+    logic signed [W_IN+4:0] x_shift5;
+    logic signed [W_IN+2:0] x_shift3;
+    logic signed [W_IN:0] x_shift1;
+    logic signed [W_IN+6:0] _pat;
+
+    always_comb begin
+        // Create shifted versions of input
+        x_shift7 = x <<< 7;
+        x_shift5 = x <<< 5;
+        x_shift3 = x <<< 3;
+        x_shift1 = x <<< 1;
+
+        // LCSRe: repeated pattern "+0-0"
+        _pat = x_shift7 - x_shift5;
+
+        // CSD implementation (LCSRe optimized)
+        result = _pat + (_pat >>> 4);
+    end
+endmodule
+"###;
+        assert_eq!(sv, expected);
+    }
+
+    #[test]
+    fn test_sv_all_zeros() {
+        let sv = generate_csd_multiplier_sv("000", 8, 2).unwrap();
+        assert!(!sv.contains("logic signed [W_IN+1:0] x_shift"));
+        assert!(sv.contains("        // CSD implementation\n        result = 0;"));
+    }
+
+    #[test]
+    fn test_sv_separate_outputs() {
+        let coeffs = specs(&["+00-00+0+", "-0+00-00+"], 8);
+        let sv =
+            generate_csd_multipliers_sv(&coeffs, "taps", &SystemVerilogOptions::new()).unwrap();
+        assert!(sv.contains("    output logic signed [W_IN+7:0] y0,\n"));
+        assert!(sv.contains("    output logic signed [W_IN+7:0] y1\n"));
+        assert!(sv.contains("_cse_0 = x_shift8 - x_shift5 + x_shift2;"));
+        assert!(sv.contains("y1 = -x_shift8 + (_cse_0 >>> 2);"));
+    }
+
+    #[test]
+    fn test_sv_packed_outputs() {
+        let coeffs = specs(&["+00-00+0+", "-0+00-00+", "+0-0000-0"], 8);
+        let options = SystemVerilogOptions::new().pack_outputs("y");
+        let sv = generate_csd_multipliers_sv(&coeffs, "taps", &options).unwrap();
+        assert!(
+            sv.contains("output logic signed [2:0][W_IN+7:0] y // y[0]: y0, y[1]: y1, y[2]: y2\n")
+        );
+        assert!(sv.contains("        // y2: +0-0000-0\n        y[2] = "));
+        assert!(!sv.contains("output logic signed [W_IN+7:0] y0"));
+    }
+
+    #[test]
+    fn test_sv_msb() {
+        assert_eq!(sv_msb(8, 8), "W_IN-1");
+        assert_eq!(sv_msb(9, 8), "W_IN");
+        assert_eq!(sv_msb(12, 8), "W_IN+3");
+        assert_eq!(sv_msb(6, 8), "5");
+    }
+
+    /// MSB index of a `[msb:0]` declaration for a given `W_IN`.
+    fn eval_msb(msb: &str, w_in: usize) -> isize {
+        match msb.strip_prefix("W_IN") {
+            Some("") => w_in as isize,
+            Some(offset) => w_in as isize + offset.parse::<isize>().unwrap(),
+            None => msb.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_sv_widths_for_other_input_widths() {
+        let options = MultiplierOptions::new()
+            .output_frac_bits(0)
+            .rounding(OutputRounding::Nearest);
+        for w_in in 1..=10 {
+            let netlist = csd_multiplier_netlist("+0-.+", w_in, 2, &options).unwrap();
+            let sv = emit_systemverilog(&netlist, &SystemVerilogOptions::new());
+            assert!(sv.contains(&format!("localparam int W_IN = {}\n", w_in)));
+            let widths = netlist
+                .inputs
+                .iter()
+                .map(|p| (&p.name, p.width))
+                .chain(netlist.wires.iter().map(|w| (&w.name, w.width)))
+                .chain(netlist.outputs.iter().map(|o| (&o.port.name, o.port.width)));
+            for (name, width) in widths {
+                let decl = sv
+                    .lines()
+                    .find(|l| {
+                        l.contains("signed [")
+                            && l.split([' ', ',', ';']).any(|token| token == name.as_str())
+                    })
+                    .unwrap();
+                let msb = &decl[decl.find('[').unwrap() + 1..decl.find(":0]").unwrap()];
+                assert_eq!(eval_msb(msb, w_in), width as isize - 1, "{}", decl);
+            }
+        }
+        let sv = emit_systemverilog(
+            &csd_multiplier_netlist("+0-.+", 8, 2, &options).unwrap(),
+            &SystemVerilogOptions::new(),
+        );
+        assert!(!sv.contains("W_IN-7"));
+    }
+}
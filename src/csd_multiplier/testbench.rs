@@ -0,0 +1,791 @@
+//! Self-checking Verilog testbenches.
+//!
+//! Each testbench instantiates a generated module, drives its input `x` either
+//! exhaustively (small widths) or with corner cases plus `$random` vectors, and
+//! compares every output against `x * coefficient` evaluated at full precision.
+//! A mismatch — including an output port too narrow for the product — prints
+//! `FAIL`, otherwise the testbench prints `PASS`. The output runs as-is under
+//! Icarus Verilog (`iverilog -o tb dut.v tb.v && vvp tb`) or Verilator (`--binary`).
+
+use std::fmt::Write;
+
+use super::fir::{fir_filter, FirFilter, FirOptions};
+use super::netlist::Netlist;
+use super::{
+    aligned_values, check_product_width, csd_multiplier_netlist, csd_multipliers_netlist,
+    csd_value, split_binary_point, CsdMultiplierError, MultiplierOptions, MultiplierSpec,
+    OutputRounding,
+};
+
+/// Stimulus options for generated testbenches.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::testbench::TestbenchOptions;
+///
+/// let options = TestbenchOptions::new().exhaustive_max_width(10).random_vectors(500);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestbenchOptions {
+    exhaustive_max_width: usize,
+    random_vectors: usize,
+    seed: u32,
+}
+
+impl Default for TestbenchOptions {
+    fn default() -> Self {
+        Self {
+            exhaustive_max_width: 12,
+            random_vectors: 1000,
+            seed: 1,
+        }
+    }
+}
+
+impl TestbenchOptions {
+    /// Create default options: exhaustive up to 12-bit inputs, else 1000 random vectors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the widest input that is still tested exhaustively.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Inputs of at most this many bits (capped at 30) are driven with every value
+    pub fn exhaustive_max_width(mut self, width: usize) -> Self {
+        self.exhaustive_max_width = width;
+        self
+    }
+
+    /// Set the number of random vectors used for wider inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of `$random` input values after the corner cases
+    pub fn random_vectors(mut self, count: usize) -> Self {
+        self.random_vectors = count;
+        self
+    }
+
+    /// Set the `$random` seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Initial value of the testbench `seed` variable
+    pub fn seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// Number of bits of a signed integer holding `value`.
+fn signed_bits(value: i128) -> usize {
+    let magnitude = if value < 0 { !value } else { value };
+    (128 - magnitude.leading_zeros() as usize) + 1
+}
+
+/// Signed Verilog literal of `width` bits, parenthesized when negative.
+fn signed_literal(value: i128, width: usize) -> String {
+    if value < 0 {
+        format!("(-{}'sd{})", width, value.unsigned_abs())
+    } else {
+        format!("{}'sd{}", width, value)
+    }
+}
+
+/// `$random` expression covering `width` bits.
+fn random_value(width: usize) -> String {
+    let words = vec!["$random(seed)"; (width + 31) / 32];
+    if words.len() == 1 {
+        words[0].to_string()
+    } else {
+        format!("{{{}}}", words.join(", "))
+    }
+}
+
+/// Expected value of one output, `(x * coefficient + round) >>> drop`.
+#[derive(Debug, Clone, Copy)]
+struct Expected {
+    coefficient: i128,
+    round: i128,
+    drop: usize,
+}
+
+impl Expected {
+    /// The exact product `x * coefficient`.
+    fn exact(coefficient: i128) -> Self {
+        Self {
+            coefficient,
+            round: 0,
+            drop: 0,
+        }
+    }
+}
+
+/// Expected values of the outputs of a netlist built with `options`, whose
+/// `coefficients` have `frac_bits` fractional bits: the exact product brought
+/// to each output's fractional bits the way the generator truncates or rounds it.
+///
+/// Fails with `CsdMultiplierError::ProductTooWide` when a full product, and so
+/// its coefficient, doesn't fit the `i128` arithmetic.
+fn requantized(
+    netlist: &Netlist,
+    coefficients: &[i128],
+    frac_bits: usize,
+    options: &MultiplierOptions,
+) -> Result<Vec<Expected>, CsdMultiplierError> {
+    netlist
+        .outputs
+        .iter()
+        .zip(coefficients)
+        .map(|(output, &c)| {
+            let target = output.port.frac_bits;
+            check_product_width(output.port.width + frac_bits.saturating_sub(target))?;
+            if target >= frac_bits {
+                return Ok(Expected::exact(c << (target - frac_bits)));
+            }
+            let drop = frac_bits - target;
+            let round = match options.rounding {
+                OutputRounding::Truncate => 0,
+                OutputRounding::Nearest => 1 << (drop - 1),
+            };
+            Ok(Expected {
+                coefficient: c,
+                round,
+                drop,
+            })
+        })
+        .collect()
+}
+
+/// Print a self-checking testbench for a single-input [`Netlist`].
+///
+/// `coefficients[k]` is the constant that output `k` multiplies `x` by.
+///
+/// # Panics
+///
+/// Panics if the netlist has no input or `coefficients` does not have one
+/// entry per output.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+/// use csd::csd_multiplier::testbench::{emit_verilog_testbench, TestbenchOptions};
+///
+/// let netlist = csd_multiplier_netlist("+0-", 8, 2, &MultiplierOptions::new()).unwrap();
+/// let tb = emit_verilog_testbench(&netlist, &[3], &TestbenchOptions::new());
+/// assert!(tb.contains("module csd_multiplier_tb;"));
+/// assert!(tb.contains("expected_result = x * 32'sd3;"));
+/// ```
+pub fn emit_verilog_testbench(
+    netlist: &Netlist,
+    coefficients: &[i128],
+    options: &TestbenchOptions,
+) -> String {
+    assert_eq!(
+        coefficients.len(),
+        netlist.outputs.len(),
+        "one coefficient per output is required"
+    );
+    let expected: Vec<Expected> = coefficients.iter().map(|&c| Expected::exact(c)).collect();
+    print_testbench(netlist, &expected, options)
+}
+
+/// Print the testbench of [`emit_verilog_testbench()`] with one [`Expected`]
+/// value per output.
+fn print_testbench(netlist: &Netlist, expected: &[Expected], options: &TestbenchOptions) -> String {
+    let input = &netlist.inputs[0];
+    let w = input.width;
+    // Wide enough for every exact product, so overflowing outputs are caught
+    let ew = expected
+        .iter()
+        .map(|e| w + signed_bits(e.coefficient) + usize::from(e.round != 0))
+        .max()
+        .unwrap_or(w)
+        .max(32);
+    // The loop counter is a 32-bit `integer`
+    let exhaustive = w <= options.exhaustive_max_width.min(30);
+
+    let mut tb = String::new();
+    writeln!(tb, "`timescale 1ns / 1ps").unwrap();
+    writeln!(tb).unwrap();
+    writeln!(tb, "// Self-checking testbench for {}", netlist.module_name).unwrap();
+    writeln!(tb, "module {}_tb;", netlist.module_name).unwrap();
+    writeln!(tb, "    reg signed [{}:0] {};", w - 1, input.name).unwrap();
+    for output in &netlist.outputs {
+        writeln!(
+            tb,
+            "    wire signed [{}:0] {};",
+            output.port.width - 1,
+            output.port.name
+        )
+        .unwrap();
+    }
+    for output in &netlist.outputs {
+        writeln!(
+            tb,
+            "    reg signed [{}:0] expected_{};",
+            ew - 1,
+            output.port.name
+        )
+        .unwrap();
+    }
+    writeln!(tb, "    integer errors;").unwrap();
+    writeln!(tb, "    integer i;").unwrap();
+    if !exhaustive {
+        writeln!(tb, "    integer seed;").unwrap();
+    }
+
+    // --- device under test ---
+    writeln!(tb).unwrap();
+    writeln!(tb, "    {} dut (", netlist.module_name).unwrap();
+    let names: Vec<&str> = netlist
+        .inputs
+        .iter()
+        .map(|p| p.name.as_str())
+        .chain(netlist.outputs.iter().map(|o| o.port.name.as_str()))
+        .collect();
+    for (i, name) in names.iter().enumerate() {
+        let sep = if i + 1 == names.len() { "" } else { "," };
+        writeln!(tb, "        .{}({}){}", name, name, sep).unwrap();
+    }
+    writeln!(tb, "    );").unwrap();
+
+    // --- checker ---
+    writeln!(tb).unwrap();
+    writeln!(tb, "    task check;").unwrap();
+    writeln!(tb, "        begin").unwrap();
+    writeln!(tb, "            #1;").unwrap();
+    for (output, e) in netlist.outputs.iter().zip(expected) {
+        let name = &output.port.name;
+        let product = format!("{} * {}", input.name, signed_literal(e.coefficient, ew));
+        let value = match (e.drop, e.round) {
+            (0, _) => product,
+            (drop, 0) => format!("({}) >>> {}", product, drop),
+            (drop, round) => format!("({} + {}) >>> {}", product, signed_literal(round, ew), drop),
+        };
+        writeln!(tb, "            expected_{} = {};", name, value).unwrap();
+        writeln!(tb, "            if ({} !== expected_{}) begin", name, name).unwrap();
+        writeln!(
+            tb,
+            "                $display(\"FAIL: {}=%0d {}=%0d expected=%0d\", {}, {}, expected_{});",
+            input.name, name, input.name, name, name
+        )
+        .unwrap();
+        writeln!(tb, "                errors = errors + 1;").unwrap();
+        writeln!(tb, "            end").unwrap();
+    }
+    writeln!(tb, "        end").unwrap();
+    writeln!(tb, "    endtask").unwrap();
+
+    // --- stimulus ---
+    writeln!(tb).unwrap();
+    writeln!(tb, "    initial begin").unwrap();
+    writeln!(tb, "        errors = 0;").unwrap();
+    let vectors = if exhaustive {
+        let count = 1u128 << w;
+        writeln!(tb, "        // Exhaustive: all {} input values", count).unwrap();
+        writeln!(tb, "        for (i = 0; i < {}; i = i + 1) begin", count).unwrap();
+        writeln!(tb, "            {} = i;", input.name).unwrap();
+        writeln!(tb, "            check;").unwrap();
+        writeln!(tb, "        end").unwrap();
+        count
+    } else {
+        writeln!(tb, "        // Corner cases").unwrap();
+        let corners = [
+            format!("{{1'b1, {{{}{{1'b0}}}}}}", w - 1),
+            format!("{{1'b0, {{{}{{1'b1}}}}}}", w - 1),
+            "0".to_string(),
+            "1".to_string(),
+            "-1".to_string(),
+        ];
+        for corner in &corners {
+            writeln!(tb, "        {} = {};", input.name, corner).unwrap();
+            writeln!(tb, "        check;").unwrap();
+        }
+        writeln!(tb, "        // {} random vectors", options.random_vectors).unwrap();
+        writeln!(tb, "        seed = {};", options.seed).unwrap();
+        let random = random_value(w);
+        writeln!(
+            tb,
+            "        for (i = 0; i < {}; i = i + 1) begin",
+            options.random_vectors
+        )
+        .unwrap();
+        writeln!(tb, "            {} = {};", input.name, random).unwrap();
+        writeln!(tb, "            check;").unwrap();
+        writeln!(tb, "        end").unwrap();
+        (corners.len() + options.random_vectors) as u128
+    };
+    writeln!(tb, "        if (errors == 0)").unwrap();
+    writeln!(
+        tb,
+        "            $display(\"PASS: {}, {} vectors\");",
+        netlist.module_name, vectors
+    )
+    .unwrap();
+    writeln!(tb, "        else").unwrap();
+    writeln!(
+        tb,
+        "            $display(\"FAIL: {}, %0d mismatches\", errors);",
+        netlist.module_name
+    )
+    .unwrap();
+    writeln!(tb, "        $finish;").unwrap();
+    writeln!(tb, "    end").unwrap();
+    writeln!(tb, "endmodule").unwrap();
+    tb
+}
+
+/// Generate the testbench for the module produced by
+/// [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier), plus
+/// `CsdMultiplierError::ProductTooWide` if the product needs more than 125 bits.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::testbench::{generate_csd_multiplier_testbench, TestbenchOptions};
+///
+/// let tb = generate_csd_multiplier_testbench("+00-00+0+", 8, 8, &TestbenchOptions::new()).unwrap();
+/// assert!(tb.contains("csd_multiplier dut ("));
+/// assert!(tb.contains("expected_result = x * 32'sd229;"));
+/// ```
+pub fn generate_csd_multiplier_testbench(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &TestbenchOptions,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_testbench_with(
+        csd_str,
+        input_width,
+        max_power,
+        &MultiplierOptions::new(),
+        options,
+    )
+}
+
+/// Generate the testbench for the module produced by
+/// [`generate_csd_multiplier_with()`](super::generate_csd_multiplier_with).
+///
+/// Outputs reduced with [`MultiplierOptions::output_frac_bits()`] are compared
+/// against the exact product truncated or rounded the same way.
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier), plus
+/// `CsdMultiplierError::ProductTooWide` if the product needs more than 125 bits.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{MultiplierOptions, Naming, OutputRounding};
+/// use csd::csd_multiplier::testbench::{generate_csd_multiplier_testbench_with, TestbenchOptions};
+///
+/// let options = MultiplierOptions::new()
+///     .output_frac_bits(0)
+///     .rounding(OutputRounding::Nearest)
+///     .naming(Naming::new().module_name("gain"));
+/// let tb = generate_csd_multiplier_testbench_with("+.0-", 8, 0, &options, &TestbenchOptions::new())
+///     .unwrap();
+/// assert!(tb.contains("gain dut ("));
+/// assert!(tb.contains("expected_result = (x * 32'sd3 + 32'sd2) >>> 2;"));
+/// ```
+pub fn generate_csd_multiplier_testbench_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    multiplier_options: &MultiplierOptions,
+    options: &TestbenchOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, multiplier_options)?;
+    let (_, frac_bits) = split_binary_point(csd_str);
+    let expected = requantized(
+        &netlist,
+        &[csd_value(csd_str)],
+        frac_bits,
+        multiplier_options,
+    )?;
+    Ok(print_testbench(&netlist, &expected, options))
+}
+
+/// Generate the testbench for the module produced by
+/// [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers), plus
+/// `CsdMultiplierError::ProductTooWide` if a product needs more than 125 bits.
+pub fn generate_csd_multipliers_testbench(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &TestbenchOptions,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_testbench_with(coeffs, module_name, &MultiplierOptions::new(), options)
+}
+
+/// Generate the testbench for the module produced by
+/// [`generate_csd_multipliers_with()`](super::generate_csd_multipliers_with).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers), plus
+/// `CsdMultiplierError::ProductTooWide` if a product needs more than 125 bits.
+pub fn generate_csd_multipliers_testbench_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    multiplier_options: &MultiplierOptions,
+    options: &TestbenchOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, multiplier_options)?;
+    let (coefficients, frac_bits) = aligned_values(coeffs);
+    let expected = requantized(&netlist, &coefficients, frac_bits, multiplier_options)?;
+    Ok(print_testbench(&netlist, &expected, options))
+}
+
+/// Print a self-checking testbench for a [`FirFilter`].
+///
+/// The testbench keeps its own history of the last $N$ samples and compares
+/// `y` against $\sum_k c_k \cdot x[n-k]$ with the integer coefficients
+/// $c_k = h_k \cdot 2^F$ before every clock edge. Stimulus is an impulse
+/// (checking every tap), runs of the most positive and most negative sample
+/// (exercising the widest accumulator values) and `$random` samples.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::fir::{fir_filter, FirOptions};
+/// use csd::csd_multiplier::testbench::{emit_fir_testbench, TestbenchOptions};
+///
+/// let filter = fir_filter(&[0.25, 0.5, 0.25], 8, &FirOptions::new()).unwrap();
+/// let tb = emit_fir_testbench(&filter, &TestbenchOptions::new());
+/// assert!(tb.contains("module csd_fir_tb;"));
+/// assert!(tb.contains("expected_y = history[0] * 32'sd1024 + history[1] * 32'sd2048 + history[2] * 32'sd1024;"));
+/// ```
+pub fn emit_fir_testbench(filter: &FirFilter, options: &TestbenchOptions) -> String {
+    let name = filter.module_name();
+    let w = filter.input_width();
+    let coefficients = filter.coefficients();
+    let taps = coefficients.len();
+    let magnitude: i128 = coefficients.iter().map(|c| c.abs()).sum();
+    // Wide enough for the exact output, so an overflowing `y` is caught
+    let ew = (w + signed_bits(magnitude))
+        .max(filter.output_width())
+        .max(32);
+    let products: Vec<String> = coefficients
+        .iter()
+        .enumerate()
+        .filter(|(_, &c)| c != 0)
+        .map(|(k, &c)| format!("history[{}] * {}", k, signed_literal(c, ew)))
+        .collect();
+    let expected = if products.is_empty() {
+        "0".to_string()
+    } else {
+        products.join(" + ")
+    };
+
+    let mut tb = String::new();
+    writeln!(tb, "`timescale 1ns / 1ps").unwrap();
+    writeln!(tb).unwrap();
+    writeln!(tb, "// Self-checking testbench for {}", name).unwrap();
+    writeln!(tb, "module {}_tb;", name).unwrap();
+    writeln!(tb, "    reg clk;").unwrap();
+    writeln!(tb, "    reg rst;").unwrap();
+    writeln!(tb, "    reg signed [{}:0] x;", w - 1).unwrap();
+    writeln!(tb, "    wire signed [{}:0] y;", filter.output_width() - 1).unwrap();
+    writeln!(tb, "    reg signed [{}:0] history [0:{}];", w - 1, taps - 1).unwrap();
+    writeln!(tb, "    reg signed [{}:0] expected_y;", ew - 1).unwrap();
+    writeln!(tb, "    integer errors;").unwrap();
+    writeln!(tb, "    integer t;").unwrap();
+    writeln!(tb, "    integer i;").unwrap();
+    writeln!(tb, "    integer k;").unwrap();
+    writeln!(tb, "    integer seed;").unwrap();
+
+    // --- device under test ---
+    writeln!(tb).unwrap();
+    writeln!(tb, "    {} dut (", name).unwrap();
+    writeln!(tb, "        .clk(clk),").unwrap();
+    writeln!(tb, "        .rst(rst),").unwrap();
+    writeln!(tb, "        .{}(x),", filter.input_name()).unwrap();
+    writeln!(tb, "        .{}(y)", filter.output_name()).unwrap();
+    writeln!(tb, "    );").unwrap();
+    writeln!(tb).unwrap();
+    writeln!(tb, "    always #5 clk = ~clk;").unwrap();
+
+    // --- checker ---
+    writeln!(tb).unwrap();
+    writeln!(
+        tb,
+        "    // Apply one sample, check y, then clock it into the filter"
+    )
+    .unwrap();
+    writeln!(tb, "    task step;").unwrap();
+    writeln!(tb, "        input signed [{}:0] sample;", w - 1).unwrap();
+    writeln!(tb, "        begin").unwrap();
+    writeln!(tb, "            x = sample;").unwrap();
+    writeln!(tb, "            for (k = {}; k > 0; k = k - 1)", taps - 1).unwrap();
+    writeln!(tb, "                history[k] = history[k - 1];").unwrap();
+    writeln!(tb, "            history[0] = sample;").unwrap();
+    writeln!(tb, "            #1;").unwrap();
+    writeln!(tb, "            expected_y = {};", expected).unwrap();
+    writeln!(tb, "            if (y !== expected_y) begin").unwrap();
+    writeln!(
+        tb,
+        "                $display(\"FAIL: t=%0d x=%0d y=%0d expected=%0d\", t, x, y, expected_y);"
+    )
+    .unwrap();
+    writeln!(tb, "                errors = errors + 1;").unwrap();
+    writeln!(tb, "            end").unwrap();
+    writeln!(tb, "            t = t + 1;").unwrap();
+    writeln!(tb, "            @(posedge clk);").unwrap();
+    writeln!(tb, "            #1;").unwrap();
+    writeln!(tb, "        end").unwrap();
+    writeln!(tb, "    endtask").unwrap();
+
+    // --- stimulus ---
+    writeln!(tb).unwrap();
+    writeln!(tb, "    initial begin").unwrap();
+    writeln!(tb, "        errors = 0;").unwrap();
+    writeln!(tb, "        t = 0;").unwrap();
+    writeln!(tb, "        clk = 0;").unwrap();
+    writeln!(tb, "        rst = 1;").unwrap();
+    writeln!(tb, "        x = 0;").unwrap();
+    writeln!(tb, "        for (k = 0; k < {}; k = k + 1)", taps).unwrap();
+    writeln!(tb, "            history[k] = 0;").unwrap();
+    writeln!(tb, "        @(posedge clk);").unwrap();
+    writeln!(tb, "        #1;").unwrap();
+    writeln!(tb, "        rst = 0;").unwrap();
+    let min = format!("{{1'b1, {{{}{{1'b0}}}}}}", w - 1);
+    let max = format!("{{1'b0, {{{}{{1'b1}}}}}}", w - 1);
+    writeln!(tb, "        // Impulse response").unwrap();
+    writeln!(tb, "        step({});", min).unwrap();
+    writeln!(tb, "        for (i = 0; i < {}; i = i + 1)", taps).unwrap();
+    writeln!(tb, "            step(0);").unwrap();
+    writeln!(tb, "        // Full-scale runs").unwrap();
+    for value in [&max, &min] {
+        writeln!(tb, "        for (i = 0; i < {}; i = i + 1)", taps).unwrap();
+        writeln!(tb, "            step({});", value).unwrap();
+    }
+    writeln!(tb, "        // {} random samples", options.random_vectors).unwrap();
+    writeln!(tb, "        seed = {};", options.seed).unwrap();
+    writeln!(
+        tb,
+        "        for (i = 0; i < {}; i = i + 1)",
+        options.random_vectors
+    )
+    .unwrap();
+    writeln!(tb, "            step({});", random_value(w)).unwrap();
+    let vectors = 1 + 3 * taps + options.random_vectors;
+    writeln!(tb, "        if (errors == 0)").unwrap();
+    writeln!(
+        tb,
+        "            $display(\"PASS: {}, {} samples\");",
+        name, vectors
+    )
+    .unwrap();
+    writeln!(tb, "        else").unwrap();
+    writeln!(
+        tb,
+        "            $display(\"FAIL: {}, %0d mismatches\", errors);",
+        name
+    )
+    .unwrap();
+    writeln!(tb, "        $finish;").unwrap();
+    writeln!(tb, "    end").unwrap();
+    writeln!(tb, "endmodule").unwrap();
+    tb
+}
+
+/// Generate the testbench for the filter produced by
+/// [`generate_fir_filter()`](super::fir::generate_fir_filter).
+///
+/// # Errors
+///
+/// Same as [`fir_filter()`](super::fir::fir_filter).
+pub fn generate_fir_filter_testbench(
+    taps: &[f64],
+    input_width: usize,
+    filter_options: &FirOptions,
+    options: &TestbenchOptions,
+) -> Result<String, CsdMultiplierError> {
+    let filter = fir_filter(taps, input_width, filter_options)?;
+    Ok(emit_fir_testbench(&filter, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::Naming;
+
+    #[test]
+    fn test_testbench_exhaustive_golden() {
+        let tb = generate_csd_multiplier_testbench("+0-", 4, 2, &TestbenchOptions::new()).unwrap();
+        let expected = r###"`timescale 1ns / 1ps
+
+// Self-checking testbench for csd_multiplier
+module csd_multiplier_tb;
+    reg signed [3:0] x;
+    wire signed [5:0] result;
+    reg signed [31:0] expected_result;
+    integer errors;
+    integer i;
+
+    csd_multiplier dut (
+        .x(x),
+        .result(result)
+    );
+
+    task check;
+        begin
+            #1;
+            expected_result = x * 32'sd3;
+            if (result !== expected_result) begin
+                $display("FAIL: x=%0d result=%0d expected=%0d", x, result, expected_result);
+                errors = errors + 1;
+            end
+        end
+    endtask
+
+    initial begin
+        errors = 0;
+        // Exhaustive: all 16 input values
+        for (i = 0; i < 16; i = i + 1) begin
+            x = i;
+            check;
+        end
+        if (errors == 0)
+            $display("PASS: csd_multiplier, 16 vectors");
+        else
+            $display("FAIL: csd_multiplier, %0d mismatches", errors);
+        $finish;
+    end
+endmodule
+"###;
+        assert_eq!(tb, expected);
+    }
+
+    #[test]
+    fn test_testbench_random() {
+        let options = TestbenchOptions::new().random_vectors(50).seed(7);
+        let tb = generate_csd_multiplier_testbench("-0+0-", 40, 4, &options).unwrap();
+        assert!(tb.contains("integer seed;"));
+        assert!(tb.contains("x = {1'b1, {39{1'b0}}};"));
+        assert!(tb.contains("x = {1'b0, {39{1'b1}}};"));
+        assert!(tb.contains("seed = 7;"));
+        assert!(tb.contains("x = {$random(seed), $random(seed)};"));
+        assert!(tb.contains("expected_result = x * (-45'sd13);"));
+        assert!(tb.contains("PASS: csd_multiplier, 55 vectors"));
+    }
+
+    #[test]
+    fn test_testbench_multi() {
+        let coeffs: Vec<MultiplierSpec> = ["+00-00+0+", "-0+00-00+"]
+            .iter()
+            .enumerate()
+            .map(|(i, csd)| MultiplierSpec {
+                name: format!("y{}", i),
+                csd: csd.to_string(),
+                input_width: 8,
+                max_power: 8,
+            })
+            .collect();
+        let tb =
+            generate_csd_multipliers_testbench(&coeffs, "taps", &TestbenchOptions::new()).unwrap();
+        assert!(tb.contains("module taps_tb;"));
+        assert!(tb.contains("        .y0(y0),\n        .y1(y1)\n"));
+        assert!(tb.contains("expected_y0 = x * 32'sd229;"));
+        assert!(tb.contains("expected_y1 = x * (-32'sd199);"));
+        assert!(tb.contains("for (i = 0; i < 256; i = i + 1)"));
+    }
+
+    #[test]
+    fn test_testbench_with_options() {
+        let naming = Naming::new().module_name("gain").input_name("din");
+        let options = MultiplierOptions::new().output_frac_bits(1).naming(naming);
+        let tb = generate_csd_multiplier_testbench_with(
+            "+0-.0+",
+            8,
+            2,
+            &options,
+            &TestbenchOptions::new(),
+        )
+        .unwrap();
+        assert!(tb.contains("    reg signed [7:0] din;\n"));
+        assert!(tb.contains("        .din(din),\n"));
+        assert!(tb.contains("expected_result = (din * 32'sd13) >>> 1;"));
+
+        let coeffs = vec![
+            MultiplierSpec::from_f64("y0", 0.75, 2, 8).unwrap(),
+            MultiplierSpec::from_integer("y1", -3, 8),
+        ];
+        let options = MultiplierOptions::new().output_frac_bits(3);
+        let tb = generate_csd_multipliers_testbench_with(
+            &coeffs,
+            "taps",
+            &options,
+            &TestbenchOptions::new(),
+        )
+        .unwrap();
+        assert!(tb.contains("expected_y0 = x * 32'sd6;"));
+        assert!(tb.contains("expected_y1 = x * (-32'sd24);"));
+    }
+
+    #[test]
+    fn test_testbench_product_limit() {
+        let too_wide = |width| CsdMultiplierError::ProductTooWide { width, max: 125 };
+        let tb = TestbenchOptions::new();
+        // Padding to 200 fractional bits or a 131-digit coefficient leaves `i128`
+        let padded = MultiplierOptions::new().output_frac_bits(200);
+        assert_eq!(
+            generate_csd_multiplier_testbench_with("+0-", 8, 2, &padded, &tb).unwrap_err(),
+            too_wide(210)
+        );
+        let csd = format!("+.{}+", "0".repeat(129));
+        let truncated = MultiplierOptions::new().output_frac_bits(0);
+        assert_eq!(
+            generate_csd_multiplier_testbench_with(&csd, 8, 0, &truncated, &tb).unwrap_err(),
+            too_wide(138)
+        );
+        let spec = MultiplierSpec {
+            name: "y0".to_string(),
+            csd,
+            input_width: 8,
+            max_power: 0,
+        };
+        assert!(generate_csd_multipliers_testbench(&[spec], "taps", &tb).is_err());
+    }
+
+    #[test]
+    fn test_fir_testbench() {
+        let options = TestbenchOptions::new().random_vectors(20).seed(3);
+        let tb = generate_fir_filter_testbench(
+            &[0.5, 0.0, -0.5],
+            12,
+            &FirOptions::new().coefficient_frac_bits(2),
+            &options,
+        )
+        .unwrap();
+        assert!(tb.contains("    reg signed [11:0] history [0:2];\n"));
+        assert!(tb.contains("expected_y = history[0] * 32'sd2 + history[2] * (-32'sd2);"));
+        assert!(tb.contains("        step({1'b1, {11{1'b0}}});\n"));
+        assert!(tb.contains("            step($random(seed));\n"));
+        assert!(tb.contains("$display(\"PASS: csd_fir, 30 samples\");"));
+    }
+
+    #[test]
+    fn test_signed_bits() {
+        assert_eq!(signed_bits(0), 1);
+        assert_eq!(signed_bits(1), 2);
+        assert_eq!(signed_bits(-1), 1);
+        assert_eq!(signed_bits(3), 3);
+        assert_eq!(signed_bits(-4), 3);
+        assert_eq!(signed_bits(229), 9);
+    }
+}
@@ -0,0 +1,598 @@
+//! Truncated constant multipliers with a guaranteed error bound.
+//!
+//! When only the top bits of $P = x \cdot C$ are needed, dropping $s$ LSBs of
+//! the exact product still pays for every adder bit below the output. A
+//! truncated shift-add multiplier instead drops the $t \le s$ lowest columns
+//! of every partial product,
+//!
+//! $$ \tilde{P} = \sum_i d_i \left\lfloor \frac{x \cdot 2^{p_i}}{2^t} \right\rfloor + K, \qquad
+//!    y = \left\lfloor \frac{\tilde{P}}{2^{s-t}} \right\rfloor, $$
+//!
+//! so the adders shrink by $t$ bits. Partial product $i$ with $p_i < t$ loses
+//! between $0$ and $2^t - 2^{p_i}$ (in units of the product LSB), and the
+//! final shift between $0$ and $2^s - 2^t$. With $A$ and $B$ the summed
+//! losses of the added and the subtracted partial products, the error
+//! $e = y - P / 2^s$ in output LSBs is bounded by
+//!
+//! $$ \frac{K 2^t - A - (2^s - 2^t)}{2^s} \le e \le \frac{K 2^t + B}{2^s}. $$
+//!
+//! The compensation constant $K$ centres this interval, and
+//! [`truncated_multiplier()`] picks the largest $t$ whose bound satisfies
+//! $|e| \le$ [`TruncationOptions::max_error()`]. At $t = 0$, $K$ is the usual
+//! rounding constant, so no bound below $1/2$ is reachable once bits are
+//! dropped; any bound below $1$ gives a faithfully rounded product (one of the
+//! two output values nearest to $P / 2^s$).
+//! [`TruncatedMultiplier::verify()`] measures the actual error over every input
+//! with the bit-accurate [`simulator`](super::simulator).
+//!
+//! Partial products are summed flat, one term per nonzero digit: LCSRe
+//! sharing would truncate a shared pattern once for several positions.
+
+use super::netlist::{Netlist, Output, Port, Source, Term, Wire, WireExpr};
+use super::simulator::simulate;
+use super::{
+    check_coefficient, check_product_width, csd_value, emit_verilog, frac_bits_text, parse_terms,
+    split_binary_point, CsdMultiplierError, Naming, TermOp, Validation,
+};
+
+/// Options for the truncated multiplier generator.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::truncated::TruncationOptions;
+/// use csd::csd_multiplier::Naming;
+///
+/// // Keep the product's top bits, faithfully rounded
+/// let options = TruncationOptions::new()
+///     .drop_bits(8)
+///     .max_error(0.99)
+///     .naming(Naming::new().module_name("gain"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TruncationOptions {
+    naming: Naming,
+    drop_bits: usize,
+    max_error: f64,
+}
+
+impl Default for TruncationOptions {
+    fn default() -> Self {
+        Self {
+            naming: Naming::default(),
+            drop_bits: 0,
+            max_error: 0.5,
+        }
+    }
+}
+
+impl TruncationOptions {
+    /// Create default options: no dropped bits, error bound 1/2 output LSB.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the module, port and signal names.
+    ///
+    /// The module defaults to `csd_truncated`, the ports to `x` and `result`;
+    /// the wire prefix applies to the shift, compensation and full-width wires.
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - Names to use instead of the defaults
+    pub fn naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Set the number $s$ of LSBs of the exact product the output omits.
+    ///
+    /// The output keeps $F - s$ of the coefficient's $F$ fractional bits; for
+    /// $s > F$ it is an integer in units of $2^{s-F}$.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - LSBs of $x \cdot C$ below the output LSB
+    pub fn drop_bits(mut self, bits: usize) -> Self {
+        self.drop_bits = bits;
+        self
+    }
+
+    /// Set the largest allowed $|e|$, in output LSBs.
+    ///
+    /// # Arguments
+    ///
+    /// * `bound` - Worst-case error bound; $1/2$ or more when bits are dropped
+    pub fn max_error(mut self, bound: f64) -> Self {
+        self.max_error = bound;
+        self
+    }
+}
+
+/// Observed output error of a [`TruncatedMultiplier`] over every input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TruncationReport {
+    /// Number of input values simulated
+    pub vectors: u128,
+    /// Most negative error, in output LSBs
+    pub min_error: f64,
+    /// Most positive error, in output LSBs
+    pub max_error: f64,
+}
+
+/// A generated truncated constant multiplier.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::quantize_coefficient;
+/// use csd::csd_multiplier::truncated::{truncated_multiplier, TruncationOptions};
+///
+/// // 12-bit input times 0.8, keeping only the integer part of the product
+/// let q = quantize_coefficient(0.8, 10).unwrap();
+/// let options = TruncationOptions::new().drop_bits(q.frac_bits).max_error(0.99);
+/// let m = truncated_multiplier(&q.csd, 12, q.max_power, &options).unwrap();
+/// let (lo, hi) = m.error_bounds();
+/// assert!(lo >= -0.99 && hi <= 0.99);
+/// let report = m.verify();
+/// assert!(lo <= report.min_error && report.max_error <= hi);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TruncatedMultiplier {
+    netlist: Netlist,
+    coefficient: i128,
+    drop_bits: usize,
+    truncated_bits: usize,
+    compensation: i128,
+    error_bounds: (f64, f64),
+}
+
+/// "1 LSB", "3 LSBs", ...
+fn lsbs_text(bits: usize) -> String {
+    match bits {
+        1 => "1 LSB".to_string(),
+        n => format!("{} LSBs", n),
+    }
+}
+
+/// Error interval, in product LSBs, of truncating `t` columns with
+/// compensation `k` and dropping `s` bits in total.
+fn error_interval(digits: &[(usize, TermOp)], s: usize, t: usize, k: i128) -> (i128, i128) {
+    let loss = |op: TermOp| -> i128 {
+        digits
+            .iter()
+            .filter(|&&(p, o)| p < t && o == op)
+            .map(|&(p, _)| (1i128 << t) - (1i128 << p))
+            .sum()
+    };
+    let comp = k << t;
+    (
+        comp - loss(TermOp::Add) - ((1i128 << s) - (1i128 << t)),
+        comp + loss(TermOp::Sub),
+    )
+}
+
+/// Compensation constant minimizing the worst-case error, and that error
+/// interval, for `t` truncated columns.
+fn best_compensation(digits: &[(usize, TermOp)], s: usize, t: usize) -> (i128, (i128, i128)) {
+    let (lo, hi) = error_interval(digits, s, t, 0);
+    // Centre (lo + hi) / 2 + K 2^t on zero
+    let k = (-(lo + hi)).div_euclid(1i128 << (t + 1));
+    [k, k + 1]
+        .into_iter()
+        .map(|k| (k, error_interval(digits, s, t, k)))
+        .min_by_key(|&(k, (lo, hi))| (lo.abs().max(hi.abs()), k.abs()))
+        .unwrap()
+}
+
+/// Build a truncated multiplier for `csd_str`, truncating as many
+/// partial-product columns as the error bound allows.
+///
+/// # Arguments
+///
+/// * `csd_str` - Signed-digit string, optionally with fractional digits
+/// * `input_width` - Bit width of the signed input `x`
+/// * `max_power` - Highest power of the integer digits
+/// * `options` - Output LSB, error bound and names
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier) for an
+/// invalid coefficient, `CsdMultiplierError::TooManyDropBits` if
+/// `options.drop_bits()` exceeds the product width,
+/// `CsdMultiplierError::ProductTooWide` if the product needs more than 125 bits,
+/// and `CsdMultiplierError::ErrorBoundUnreachable` if even a correctly rounded
+/// product exceeds `options.max_error()`.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::truncated::{truncated_multiplier, TruncationOptions};
+///
+/// let options = TruncationOptions::new().drop_bits(6).max_error(0.99);
+/// let m = truncated_multiplier("+0-0+0-0+", 10, 8, &options).unwrap();
+/// assert!(m.truncated_bits() > 0);
+/// assert!(m.simulate(-512).abs_diff((-512 * 205) >> 6) <= 1);
+/// ```
+pub fn truncated_multiplier(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &TruncationOptions,
+) -> Result<TruncatedMultiplier, CsdMultiplierError> {
+    let (csd, max_power) =
+        check_coefficient(csd_str, input_width, max_power, Validation::SignedDigit)?;
+    let (digits_str, frac_bits) = split_binary_point(&csd);
+    let digits = parse_terms(&digits_str, max_power + frac_bits)?;
+    let s = options.drop_bits;
+    let product_width = input_width + max_power + frac_bits + 1;
+    check_product_width(product_width + 1)?;
+    if s > product_width {
+        return Err(CsdMultiplierError::TooManyDropBits {
+            drop_bits: s,
+            product_width,
+        });
+    }
+    let lsb = 2f64.powi(s as i32);
+
+    // Largest truncation whose compensated error stays within the bound
+    let mut best_error = f64::INFINITY;
+    let mut choice = None;
+    for t in 0..=s {
+        let (k, (lo, hi)) = best_compensation(&digits, s, t);
+        let worst = lo.abs().max(hi.abs()) as f64 / lsb;
+        best_error = best_error.min(worst);
+        if worst <= options.max_error {
+            choice = Some((t, k, (lo as f64 / lsb, hi as f64 / lsb)));
+        }
+    }
+    let Some((t, k, error_bounds)) = choice else {
+        return Err(CsdMultiplierError::ErrorBoundUnreachable {
+            requested: options.max_error,
+            achievable: best_error,
+        });
+    };
+
+    // --- netlist ---
+    let naming = &options.naming;
+    let width = product_width + 1;
+    let mut netlist = Netlist {
+        module_name: naming.module("csd_truncated", &[&csd]),
+        inputs: vec![Port {
+            name: naming.input().to_string(),
+            width: input_width,
+            frac_bits: 0,
+            comment: Some("Input value".to_string()),
+        }],
+        wires: Vec::new(),
+        outputs: Vec::new(),
+    };
+    let push_wire = |netlist: &mut Netlist, name: String, expr, comment| {
+        netlist.wires.push(Wire {
+            name,
+            width,
+            expr,
+            comment,
+        });
+        Source::Wire(netlist.wires.len() - 1)
+    };
+
+    // Columns at and above 2^t: exact shifted inputs; below: `x >>> (t - p)`
+    let mut terms: Vec<Term> = Vec::new();
+    for &(p, op) in &digits {
+        let term = if p >= t {
+            let power = p - t;
+            let comment = terms
+                .is_empty()
+                .then(|| "Create shifted versions of input".to_string());
+            let source = push_wire(
+                &mut netlist,
+                naming.shift_wire(power),
+                WireExpr::Shift { input: 0, power },
+                comment,
+            );
+            Term {
+                op,
+                source,
+                shift: 0,
+            }
+        } else {
+            Term {
+                op,
+                source: Source::Input(0),
+                shift: p as isize - t as isize,
+            }
+        };
+        terms.push(term);
+    }
+    if k != 0 {
+        let source = push_wire(
+            &mut netlist,
+            naming.wire("_comp"),
+            WireExpr::Constant(k),
+            Some("Compensation for the truncated columns".to_string()),
+        );
+        terms.push(Term {
+            op: TermOp::Add,
+            source,
+            shift: 0,
+        });
+    }
+    // Avoid a leading negation when any term is added
+    if let Some(first_add) = terms.iter().position(|t| t.op == TermOp::Add) {
+        let term = terms.remove(first_add);
+        terms.insert(0, term);
+    }
+
+    let out_frac_bits = frac_bits.saturating_sub(s);
+    let port = Port {
+        name: naming.output("result").to_string(),
+        width,
+        frac_bits: out_frac_bits,
+        comment: Some(match out_frac_bits {
+            0 => format!("Product without its {}", lsbs_text(s)),
+            f => format!(
+                "Product without its {} ({})",
+                lsbs_text(s),
+                frac_bits_text(f)
+            ),
+        }),
+    };
+    let sum_comment = match t {
+        0 => "CSD implementation".to_string(),
+        1 => "Truncated CSD implementation: 1 column dropped".to_string(),
+        t => format!("Truncated CSD implementation: {} columns dropped", t),
+    };
+    let r = s - t;
+    if r == 0 {
+        netlist.outputs.push(Output {
+            port,
+            terms,
+            comment: Some(sum_comment),
+        });
+    } else {
+        let full = push_wire(
+            &mut netlist,
+            naming.wire(&format!("{}_full", port.name)),
+            WireExpr::Sum(terms),
+            Some(sum_comment),
+        );
+        netlist.outputs.push(Output {
+            port,
+            terms: vec![Term {
+                op: TermOp::Add,
+                source: full,
+                shift: -(r as isize),
+            }],
+            comment: Some(format!("Drop the remaining {}", lsbs_text(r))),
+        });
+    }
+    netlist.minimize_widths();
+
+    Ok(TruncatedMultiplier {
+        netlist,
+        coefficient: csd_value(&csd),
+        drop_bits: s,
+        truncated_bits: t,
+        compensation: k,
+        error_bounds,
+    })
+}
+
+impl TruncatedMultiplier {
+    /// The shift-add [`Netlist`], for the other backends.
+    pub fn netlist(&self) -> &Netlist {
+        &self.netlist
+    }
+
+    /// Number $t$ of LSB columns dropped from every partial product.
+    pub fn truncated_bits(&self) -> usize {
+        self.truncated_bits
+    }
+
+    /// Compensation constant $K$, in units of $2^t$ product LSBs.
+    pub fn compensation(&self) -> i128 {
+        self.compensation
+    }
+
+    /// Analytic worst-case error interval `(min, max)`, in output LSBs.
+    pub fn error_bounds(&self) -> (f64, f64) {
+        self.error_bounds
+    }
+
+    /// Output for input `x`, bit-accurate to the Verilog.
+    pub fn simulate(&self, x: i128) -> i128 {
+        simulate(&self.netlist, &[x])[0]
+    }
+
+    /// Simulate every input value and measure the error against $x \cdot C / 2^s$.
+    ///
+    /// The run takes $2^{W_{\text{in}}}$ simulations, like
+    /// [`verify_exhaustive()`](super::simulator::verify_exhaustive).
+    pub fn verify(&self) -> TruncationReport {
+        let width = self.netlist.inputs[0].width;
+        let lsb = 2f64.powi(self.drop_bits as i32);
+        let mut report = TruncationReport {
+            vectors: 0,
+            min_error: f64::INFINITY,
+            max_error: f64::NEG_INFINITY,
+        };
+        for x in -(1i128 << (width - 1))..(1i128 << (width - 1)) {
+            let error = ((self.simulate(x) << self.drop_bits) - x * self.coefficient) as f64 / lsb;
+            report.min_error = report.min_error.min(error);
+            report.max_error = report.max_error.max(error);
+            report.vectors += 1;
+        }
+        report
+    }
+
+    /// Verilog module text.
+    pub fn verilog(&self) -> String {
+        emit_verilog(&self.netlist)
+    }
+}
+
+/// Generate a truncated CSD multiplier whose output omits `drop_bits` LSBs of
+/// the product and is never off by more than `max_error` output LSBs.
+///
+/// # Errors
+///
+/// Same as [`truncated_multiplier()`].
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::truncated::generate_truncated_multiplier;
+///
+/// let v = generate_truncated_multiplier("+0-0+0-0+", 10, 8, 6, 0.99).unwrap();
+/// assert!(v.contains("module csd_truncated ("));
+/// assert!(v.contains("(x >>> "));
+/// ```
+pub fn generate_truncated_multiplier(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    drop_bits: usize,
+    max_error: f64,
+) -> Result<String, CsdMultiplierError> {
+    let options = TruncationOptions::new()
+        .drop_bits(drop_bits)
+        .max_error(max_error);
+    Ok(truncated_multiplier(csd_str, input_width, max_power, &options)?.verilog())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::report::full_adder_cells;
+
+    #[test]
+    fn test_truncated_bounds_hold() {
+        let cases = [
+            ("+0-0+0-0+", 8),
+            ("+00-00+0+", 8),
+            ("-0+0-.0+0+", 4),
+            ("+.0-0+00-0+", 0),
+        ];
+        for &(csd, max_power) in &cases {
+            for drop in [2, 5, 8] {
+                for bound in [0.5, 0.75, 0.99, 2.0] {
+                    let options = TruncationOptions::new().drop_bits(drop).max_error(bound);
+                    let m = truncated_multiplier(csd, 10, max_power, &options).unwrap();
+                    let (lo, hi) = m.error_bounds();
+                    assert!(-bound <= lo && hi <= bound, "{} {} {}", csd, drop, bound);
+                    let report = m.verify();
+                    assert_eq!(report.vectors, 1024);
+                    assert!(
+                        lo <= report.min_error && report.max_error <= hi,
+                        "{} drop {} bound {}: {:?} not within {:?}",
+                        csd,
+                        drop,
+                        bound,
+                        report,
+                        (lo, hi)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_truncated_saves_adder_bits() {
+        let half = TruncationOptions::new().drop_bits(8);
+        let faithful = half.clone().max_error(0.99);
+        let rounded = truncated_multiplier("+00-00+0+", 12, 8, &half).unwrap();
+        let truncated = truncated_multiplier("+00-00+0+", 12, 8, &faithful).unwrap();
+        assert!(truncated.truncated_bits() > rounded.truncated_bits());
+        assert!(full_adder_cells(truncated.netlist()) < full_adder_cells(rounded.netlist()));
+        // As accurate as rounding the exact product to nearest
+        let report = rounded.verify();
+        assert!(report.min_error >= -0.5 && report.max_error <= 0.5);
+        let report = truncated.verify();
+        assert!(report.min_error > -1.0 && report.max_error < 1.0);
+    }
+
+    #[test]
+    fn test_truncated_exact_without_dropped_bits() {
+        let m = truncated_multiplier("+0-", 8, 2, &TruncationOptions::new()).unwrap();
+        assert_eq!(m.error_bounds(), (0.0, 0.0));
+        assert_eq!(m.simulate(-128), -384);
+        let v = m.verilog();
+        assert!(v.contains("    assign result = x_shift2 - x_shift0;"));
+    }
+
+    #[test]
+    fn test_truncated_naming() {
+        let naming = Naming::new()
+            .module_name("gain")
+            .input_name("a")
+            .output_name("p")
+            .wire_prefix("u0")
+            .coefficient_suffix(true);
+        let options = TruncationOptions::new()
+            .drop_bits(6)
+            .max_error(0.99)
+            .naming(naming);
+        let m = truncated_multiplier("+0-0+0-0+", 10, 8, &options).unwrap();
+        let v = m.verilog();
+        assert!(v.contains("module gain_205 ("), "{}", v);
+        assert!(v.contains("input signed [9:0] a,"), "{}", v);
+        for name in ["u0_a_shift", "u0_comp", "u0_p_full", "assign p = "] {
+            assert!(v.contains(name), "{} missing:\n{}", name, v);
+        }
+        for name in ["x_shift", "result", " _comp"] {
+            assert!(!v.contains(name), "{} left:\n{}", name, v);
+        }
+    }
+
+    #[test]
+    fn test_truncated_unreachable_bound() {
+        let options = TruncationOptions::new().drop_bits(4).max_error(0.25);
+        let err = truncated_multiplier("+0-", 8, 2, &options).unwrap_err();
+        assert_eq!(
+            err,
+            CsdMultiplierError::ErrorBoundUnreachable {
+                requested: 0.25,
+                achievable: 0.5
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "No truncation meets the error bound of 0.25 output LSBs (best achievable: 0.5)"
+        );
+    }
+
+    #[test]
+    fn test_truncated_drop_bits_limit() {
+        let options = TruncationOptions::new().drop_bits(130).max_error(1e9);
+        let err = truncated_multiplier("+0-", 8, 2, &options).unwrap_err();
+        assert_eq!(
+            err,
+            CsdMultiplierError::TooManyDropBits {
+                drop_bits: 130,
+                product_width: 11
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Cannot drop 130 bits from a 11-bit product"
+        );
+        assert_eq!(
+            truncated_multiplier("+0-", 200, 2, &TruncationOptions::new()).unwrap_err(),
+            CsdMultiplierError::ProductTooWide {
+                width: 204,
+                max: 125
+            }
+        );
+
+        // Dropping the whole product still meets a bound of one LSB
+        let options = TruncationOptions::new().drop_bits(11).max_error(1.0);
+        let m = truncated_multiplier("+0-", 8, 2, &options).unwrap();
+        assert_eq!(m.truncated_bits(), 11);
+        let report = m.verify();
+        assert!(report.min_error >= -1.0 && report.max_error <= 1.0);
+        assert!(m
+            .verilog()
+            .contains("// Truncated CSD implementation: 11 columns dropped"));
+    }
+}
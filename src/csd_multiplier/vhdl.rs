@@ -0,0 +1,392 @@
+//! VHDL-2008 backend.
+//!
+//! Prints a [`Netlist`] as an `entity`/`architecture` pair using `ieee.numeric_std`
+//! `signed` arithmetic. Every operand is brought to the width of the signal it is
+//! assigned to with the same two's-complement wrap-around as the Verilog output
+//! (`resize` only ever widens; narrowing uses slices), so both backends describe
+//! bit-identical hardware, including the LCSRe `_pat` and cross-CSE `_cse_0` wires.
+//!
+//! VHDL identifiers cannot start with an underscore, so internal wires such as
+//! `_pat` are renamed with a `w` prefix (`w_pat`).
+
+use std::fmt::Write;
+
+use super::netlist::{Netlist, Port, Term, WireExpr};
+use super::{
+    csd_multiplier_netlist, csd_multipliers_netlist, CsdMultiplierError, MultiplierOptions,
+    MultiplierSpec, TermOp,
+};
+
+/// Map a netlist signal name to a legal VHDL identifier.
+fn vhdl_identifier(name: &str) -> String {
+    if name.starts_with('_') {
+        format!("w{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Convert `name` (a signal of `from` bits) to `to` bits, keeping the low bits.
+fn fit(name: &str, from: usize, to: usize) -> String {
+    match from.cmp(&to) {
+        std::cmp::Ordering::Equal => name.to_string(),
+        std::cmp::Ordering::Less => format!("resize({}, {})", name, to),
+        std::cmp::Ordering::Greater => format!("{}({} downto 0)", name, to - 1),
+    }
+}
+
+/// Render one term's operand at `width` bits, without its sign.
+fn vhdl_operand(netlist: &Netlist, term: &Term, width: usize) -> String {
+    let name = vhdl_identifier(netlist.source_name(term.source));
+    let from = netlist.source_width(term.source);
+    match term.shift {
+        0 => fit(&name, from, width),
+        s if s > 0 => format!("shift_left({}, {})", fit(&name, from, width), s),
+        s => {
+            let s = (-s) as usize;
+            if from <= width {
+                format!("shift_right({}, {})", fit(&name, from, width), s)
+            } else if from >= s + width {
+                // The low `width` bits of the shifted value are a plain slice
+                format!("{}({} downto {})", name, s + width - 1, s)
+            } else {
+                format!("resize({}({} downto {}), {})", name, from - 1, s, width)
+            }
+        }
+    }
+}
+
+/// Render a `width`-bit constant. `to_signed` takes a 32-bit `integer`, so wider
+/// values are written as a two's-complement bit-string literal.
+fn vhdl_constant(value: i128, width: usize) -> String {
+    if i32::try_from(value).is_ok() {
+        return format!("to_signed({}, {})", value, width);
+    }
+    let bits: String = (0..width)
+        .rev()
+        .map(|i| {
+            if (value >> i.min(127)) & 1 == 1 {
+                '1'
+            } else {
+                '0'
+            }
+        })
+        .collect();
+    format!("signed'(\"{}\")", bits)
+}
+
+/// Render a signed sum of terms at `width` bits.
+fn vhdl_sum(netlist: &Netlist, terms: &[Term], width: usize) -> String {
+    if terms.is_empty() {
+        return format!("to_signed(0, {})", width);
+    }
+    let mut expr = String::new();
+    for (i, term) in terms.iter().enumerate() {
+        match (i, term.op) {
+            (0, TermOp::Add) => {}
+            (0, TermOp::Sub) => expr.push('-'),
+            (_, TermOp::Add) => expr.push_str(" + "),
+            (_, TermOp::Sub) => expr.push_str(" - "),
+        }
+        expr.push_str(&vhdl_operand(netlist, term, width));
+    }
+    expr
+}
+
+/// Print a [`Netlist`] as a VHDL-2008 entity and architecture.
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::{csd_multiplier_netlist, MultiplierOptions};
+/// use csd::csd_multiplier::vhdl::emit_vhdl;
+///
+/// let netlist = csd_multiplier_netlist("+0-0+0-0", 8, 7, &MultiplierOptions::new()).unwrap();
+/// let v = emit_vhdl(&netlist);
+/// assert!(v.contains("signal w_pat : signed(14 downto 0);"));
+/// assert!(v.contains("result <= w_pat + shift_right(w_pat, 4);"));
+/// ```
+pub fn emit_vhdl(netlist: &Netlist) -> String {
+    let mut vhdl = String::new();
+    let entity = vhdl_identifier(&netlist.module_name);
+
+    writeln!(vhdl, "library ieee;").unwrap();
+    writeln!(vhdl, "use ieee.std_logic_1164.all;").unwrap();
+    writeln!(vhdl, "use ieee.numeric_std.all;").unwrap();
+
+    // --- entity ---
+    writeln!(vhdl).unwrap();
+    writeln!(vhdl, "entity {} is", entity).unwrap();
+    writeln!(vhdl, "    port (").unwrap();
+    let ports: Vec<(&str, &Port)> = netlist
+        .inputs
+        .iter()
+        .map(|p| ("in ", p))
+        .chain(netlist.outputs.iter().map(|o| ("out", &o.port)))
+        .collect();
+    for (i, (dir, port)) in ports.iter().enumerate() {
+        let sep = if i + 1 == ports.len() { "" } else { ";" };
+        write!(
+            vhdl,
+            "        {} : {} signed({} downto 0){}",
+            vhdl_identifier(&port.name),
+            dir,
+            port.width - 1,
+            sep
+        )
+        .unwrap();
+        match &port.comment {
+            Some(c) => writeln!(vhdl, " -- {}", c).unwrap(),
+            None => writeln!(vhdl).unwrap(),
+        }
+    }
+    writeln!(vhdl, "    );").unwrap();
+    writeln!(vhdl, "end entity {};", entity).unwrap();
+
+    // --- signal declarations ---
+    writeln!(vhdl).unwrap();
+    writeln!(vhdl, "architecture rtl of {} is", entity).unwrap();
+    for wire in &netlist.wires {
+        writeln!(
+            vhdl,
+            "    signal {} : signed({} downto 0);",
+            vhdl_identifier(&wire.name),
+            wire.width - 1
+        )
+        .unwrap();
+    }
+    writeln!(vhdl, "begin").unwrap();
+
+    // --- concurrent assignments ---
+    for wire in &netlist.wires {
+        if let Some(comment) = &wire.comment {
+            writeln!(vhdl).unwrap();
+            writeln!(vhdl, "    -- {}", comment).unwrap();
+        }
+        let expr = match &wire.expr {
+            WireExpr::Shift { input, power } => {
+                let port = &netlist.inputs[*input];
+                format!(
+                    "shift_left({}, {})",
+                    fit(&vhdl_identifier(&port.name), port.width, wire.width),
+                    power
+                )
+            }
+            WireExpr::Sum(terms) => vhdl_sum(netlist, terms, wire.width),
+            WireExpr::Constant(value) => vhdl_constant(*value, wire.width),
+        };
+        writeln!(vhdl, "    {} <= {};", vhdl_identifier(&wire.name), expr).unwrap();
+    }
+    for output in &netlist.outputs {
+        if let Some(comment) = &output.comment {
+            writeln!(vhdl).unwrap();
+            writeln!(vhdl, "    -- {}", comment).unwrap();
+        }
+        writeln!(
+            vhdl,
+            "    {} <= {};",
+            vhdl_identifier(&output.port.name),
+            vhdl_sum(netlist, &output.terms, output.port.width)
+        )
+        .unwrap();
+    }
+
+    writeln!(vhdl, "end architecture rtl;").unwrap();
+    vhdl
+}
+
+/// Generate a VHDL CSD multiplier entity, the counterpart of
+/// [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+///
+/// # Examples
+///
+/// ```
+/// use csd::csd_multiplier::vhdl::generate_csd_multiplier_vhdl;
+///
+/// let v = generate_csd_multiplier_vhdl("+0-", 8, 2).unwrap();
+/// assert!(v.contains("entity csd_multiplier is"));
+/// assert!(v.contains("result <= x_shift2 - resize(x_shift0, 10);"));
+/// ```
+pub fn generate_csd_multiplier_vhdl(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multiplier_vhdl_with(csd_str, input_width, max_power, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multiplier_vhdl()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multiplier()`](super::generate_csd_multiplier).
+pub fn generate_csd_multiplier_vhdl_with(
+    csd_str: &str,
+    input_width: usize,
+    max_power: usize,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multiplier_netlist(csd_str, input_width, max_power, options)?;
+    Ok(emit_vhdl(&netlist))
+}
+
+/// Generate a VHDL multi-coefficient entity with cross-CSE, the counterpart of
+/// [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_vhdl(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+) -> Result<String, CsdMultiplierError> {
+    generate_csd_multipliers_vhdl_with(coeffs, module_name, &MultiplierOptions::new())
+}
+
+/// Same as [`generate_csd_multipliers_vhdl()`], with explicit [`MultiplierOptions`].
+///
+/// # Errors
+///
+/// Same as [`generate_csd_multipliers()`](super::generate_csd_multipliers).
+pub fn generate_csd_multipliers_vhdl_with(
+    coeffs: &[MultiplierSpec],
+    module_name: &str,
+    options: &MultiplierOptions,
+) -> Result<String, CsdMultiplierError> {
+    let netlist = csd_multipliers_netlist(coeffs, module_name, options)?;
+    Ok(emit_vhdl(&netlist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csd_multiplier::netlist::Source;
+    use crate::csd_multiplier::{quantize_coefficient, AdderStructure, OutputRounding};
+
+    #[test]
+    fn test_vhdl_golden() {
+        let v = generate_csd_multiplier_vhdl("+0-", 8, 2).unwrap();
+        let expected = r###"library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+
+entity csd_multiplier is
+    port (
+        x : in  signed(7 downto 0); -- Input value
+        result : out signed(9 downto 0) -- Result of multiplication
+    );
+end entity csd_multiplier;
+
+architecture rtl of csd_multiplier is
+    signal x_shift2 : signed(9 downto 0);
+    signal x_shift0 : signed(7 downto 0);
+begin
+
+    -- Create shifted versions of input
+    x_shift2 <= shift_left(resize(x, 10), 2);
+    x_shift0 <= shift_left(x, 0);
+
+    -- CSD implementation
+    result <= x_shift2 - resize(x_shift0, 10);
+end architecture rtl;
+"###;
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn test_vhdl_all_zeros() {
+        let v = generate_csd_multiplier_vhdl("000", 8, 2).unwrap();
+        assert!(v.contains("result <= to_signed(0, 1);"));
+        assert!(!v.contains("signal"));
+    }
+
+    #[test]
+    fn test_vhdl_leading_minus() {
+        let v = generate_csd_multiplier_vhdl("-0-", 8, 2).unwrap();
+        assert!(v.contains("result <= -resize(x_shift2, 11) - resize(x_shift0, 11);"));
+    }
+
+    #[test]
+    fn test_vhdl_multi_cse() {
+        let coeffs = vec![
+            MultiplierSpec {
+                name: "y0".to_string(),
+                csd: "+00-00+0+".to_string(),
+                input_width: 8,
+                max_power: 8,
+            },
+            MultiplierSpec {
+                name: "y1".to_string(),
+                csd: "-0+00-00+".to_string(),
+                input_width: 8,
+                max_power: 8,
+            },
+        ];
+        let v = generate_csd_multipliers_vhdl(&coeffs, "csd_filter").unwrap();
+        assert!(v.contains("entity csd_filter is"));
+        assert!(v.contains("        y0 : out signed(15 downto 0);\n"));
+        assert!(v.contains("        y1 : out signed(15 downto 0)\n"));
+        assert!(v.contains("w_cse_0 <= x_shift8 - resize(x_shift5, 16) + resize(x_shift2, 16);"));
+        assert!(v.contains("y0 <= w_cse_0 + resize(x_shift0, 16);"));
+        assert!(v.contains("y1 <= -x_shift8 + shift_right(w_cse_0, 2);"));
+    }
+
+    #[test]
+    fn test_vhdl_tree() {
+        let options = MultiplierOptions::new().adder_structure(AdderStructure::Tree);
+        let netlist = csd_multiplier_netlist("+00-00+0+", 8, 8, &options).unwrap();
+        let v = emit_vhdl(&netlist);
+        assert!(v.contains("w_t0 <= x_shift8 - resize(x_shift5, 16);"));
+        assert!(v.contains("signal w_t1 : signed(10 downto 0);"));
+        assert!(v.contains("result <= w_t0 + resize(w_t1, 16);"));
+    }
+
+    #[test]
+    fn test_vhdl_width_conversion() {
+        let netlist = csd_multiplier_netlist("+0-", 8, 2, &MultiplierOptions::new()).unwrap();
+        let wide = Term::add(Source::Wire(0));
+        assert_eq!(vhdl_operand(&netlist, &wide, 6), "x_shift2(5 downto 0)");
+        let shifted = Term { shift: -3, ..wide };
+        assert_eq!(vhdl_operand(&netlist, &shifted, 6), "x_shift2(8 downto 3)");
+        assert_eq!(
+            vhdl_operand(&netlist, &shifted, 8),
+            "resize(x_shift2(9 downto 3), 8)"
+        );
+        assert_eq!(
+            vhdl_operand(&netlist, &shifted, 12),
+            "shift_right(resize(x_shift2, 12), 3)"
+        );
+        let left = Term { shift: 1, ..wide };
+        assert_eq!(
+            vhdl_operand(&netlist, &left, 12),
+            "shift_left(resize(x_shift2, 12), 1)"
+        );
+    }
+
+    #[test]
+    fn test_vhdl_wide_constant() {
+        assert_eq!(vhdl_constant(-3, 8), "to_signed(-3, 8)");
+        assert_eq!(
+            vhdl_constant(1 << 33, 35),
+            "signed'(\"01000000000000000000000000000000000\")"
+        );
+        assert_eq!(
+            vhdl_constant(-(1 << 33), 35),
+            "signed'(\"11000000000000000000000000000000000\")"
+        );
+
+        // Rounding a 40-fraction-bit product to an integer adds 2^39
+        let q = quantize_coefficient(0.7, 40).unwrap();
+        let options = MultiplierOptions::new()
+            .output_frac_bits(0)
+            .rounding(OutputRounding::Nearest);
+        let netlist = csd_multiplier_netlist(&q.csd, 8, q.max_power, &options).unwrap();
+        let v = emit_vhdl(&netlist);
+        assert!(!v.contains("to_signed(549755813888"));
+        assert!(v.contains("w_round <= signed'(\"01"));
+    }
+}
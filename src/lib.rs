@@ -5,7 +5,7 @@
 //!
 //! # Features
 //!
-//! - `multiplier` (default): CSD multiplier module for Verilog code generation
+//! - `multiplier` (default): CSD multiplier module for Verilog, SystemVerilog and VHDL code generation
 //! - `lcsre` (default): Longest common substring with repeated elements
 //! - `std` (optional): Logging support via env_logger
 //!
@@ -42,8 +42,93 @@ pub use crate::csd::{
 
 #[cfg(feature = "multiplier")]
 pub use crate::csd_multiplier::{
-    generate_csd_multiplier, generate_csd_multipliers, CsdMultiplier, CsdMultiplierError,
-    MultiplierSpec,
+    adder_cost_tradeoff, emit_verilog_with, generate_csd_multiplier, generate_csd_multiplier_with,
+    generate_csd_multipliers, generate_csd_multipliers_with, quantize_coefficient, AdderCost,
+    AdderStructure, CommentStyle, CsdMultiplier, CsdMultiplierError, MultiplierOptions,
+    MultiplierSpec, Naming, OutputRounding, QuantizedCoefficient, SignExtension, Validation,
+    VerilogStyle,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::amaranth::{
+    generate_csd_multiplier_amaranth, generate_csd_multiplier_amaranth_with,
+    generate_csd_multipliers_amaranth, generate_csd_multipliers_amaranth_with,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::bank::{
+    coefficient_bank, generate_coefficient_bank, BankReport, CoefficientBank,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::chisel::{
+    generate_csd_multiplier_chisel, generate_csd_multiplier_chisel_with,
+    generate_csd_multipliers_chisel, generate_csd_multipliers_chisel_with,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::cmvm::{cmvm_netlist, generate_cmvm, generate_cmvm_with};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::complex::{
+    complex_multiplier, generate_complex_multiplier, ComplexForm, ComplexMultiplier, ComplexOptions,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::fir::{
+    fir_filter, generate_fir_filter, FirFilter, FirForm, FirOptions, FirSymmetry,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::graph::{
+    generate_csd_multiplier_dot, generate_csd_multiplier_dot_with, generate_csd_multiplier_mermaid,
+    generate_csd_multiplier_mermaid_with, generate_csd_multipliers_dot,
+    generate_csd_multipliers_dot_with, generate_csd_multipliers_mermaid,
+    generate_csd_multipliers_mermaid_with,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::hls::{
+    generate_csd_multiplier_hls, generate_csd_multiplier_hls_with, generate_csd_multipliers_hls,
+    generate_csd_multipliers_hls_with,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::model::{
+    generate_csd_multiplier_c, generate_csd_multiplier_c_with, generate_csd_multiplier_rust,
+    generate_csd_multiplier_rust_with, generate_csd_multipliers_c, generate_csd_multipliers_c_with,
+    generate_csd_multipliers_rust, generate_csd_multipliers_rust_with,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::report::{
+    generate_csd_multiplier_with_report, generate_csd_multipliers_with_report, CostReport,
+    SignalCost,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::systemverilog::{
+    generate_csd_multiplier_sv, generate_csd_multiplier_sv_with, generate_csd_multipliers_sv,
+    generate_csd_multipliers_sv_with, SystemVerilogOptions,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::testbench::{
+    emit_fir_testbench, generate_csd_multiplier_testbench, generate_csd_multiplier_testbench_with,
+    generate_csd_multipliers_testbench, generate_csd_multipliers_testbench_with,
+    generate_fir_filter_testbench, TestbenchOptions,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::truncated::{
+    generate_truncated_multiplier, truncated_multiplier, TruncatedMultiplier, TruncationOptions,
+    TruncationReport,
+};
+
+#[cfg(feature = "multiplier")]
+pub use crate::csd_multiplier::vhdl::{
+    generate_csd_multiplier_vhdl, generate_csd_multiplier_vhdl_with, generate_csd_multipliers_vhdl,
+    generate_csd_multipliers_vhdl_with,
 };
 
 #[cfg(feature = "lcsre")]
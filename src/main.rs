@@ -1,11 +1,17 @@
 //! Canonical Signed Digit (CSD) CLI binary.
 //!
-//! Handles conversion between decimal numbers and CSD representation via command-line interface.
+//! Handles conversion between decimal numbers and CSD representation via command-line interface,
+//! and generates Verilog constant multipliers from a coefficient or a coefficient file.
 //! CSD uses: '+' for +1, '-' for -1, '0' for 0
 //!
 //! Original author: Harnesser
 //! License: GPL2
 use csd::csd::{to_csd, to_csdnnz, to_decimal};
+#[cfg(feature = "multiplier")]
+use csd::csd_multiplier::{
+    generate_csd_multiplier_with, generate_csd_multipliers, quantize_coefficient,
+    MultiplierOptions, MultiplierSpec, Naming,
+};
 
 /// CLI result type alias.
 pub type CliResult = Result<String, String>;
@@ -50,10 +56,180 @@ pub fn handle_to_decimal(args: &[String]) -> CliResult {
     Ok(to_decimal(&args[2]).to_string())
 }
 
+/// Options of the `verilog` and `filter` commands.
+#[cfg(feature = "multiplier")]
+struct GeneratorArgs<'a> {
+    positional: Vec<&'a str>,
+    width: usize,
+    frac_bits: Option<usize>,
+    module: Option<&'a str>,
+    out: Option<&'a str>,
+}
+
+/// Split `args` (after the command name) into positional arguments and
+/// `--width`, `--frac-bits`, `--module` and `--out` options.
+#[cfg(feature = "multiplier")]
+fn parse_generator_args<'a>(
+    command: &str,
+    args: &'a [String],
+) -> Result<GeneratorArgs<'a>, String> {
+    let mut parsed = GeneratorArgs {
+        positional: Vec::new(),
+        width: 0,
+        frac_bits: None,
+        module: None,
+        out: None,
+    };
+    let mut width = None;
+    let mut iter = args.iter().skip(2);
+    while let Some(arg) = iter.next() {
+        let flag = arg.as_str();
+        if !flag.starts_with("--") {
+            parsed.positional.push(flag);
+            continue;
+        }
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Error: {} {} requires a value", command, flag))?;
+        let number = |value: &str| -> Result<usize, String> {
+            value
+                .parse()
+                .map_err(|e| format!("Error parsing {}: {}", flag, e))
+        };
+        match flag {
+            "--width" => width = Some(number(value)?),
+            "--frac-bits" => parsed.frac_bits = Some(number(value)?),
+            "--module" => parsed.module = Some(value),
+            "--out" => parsed.out = Some(value),
+            _ => return Err(format!("Error: unknown option {} for {}", flag, command)),
+        }
+    }
+    parsed.width = width.ok_or_else(|| format!("Error: {} requires --width N", command))?;
+    Ok(parsed)
+}
+
+/// Whether `text` is a CSD string rather than a decimal value: only '+',
+/// '-', '0' and '.' with at least one non-zero digit (so "+000" is 8 and
+/// "-0.0" is -1; strings of zeros mean 0 either way).
+#[cfg(feature = "multiplier")]
+fn is_csd(text: &str) -> bool {
+    text.chars().all(|c| matches!(c, '+' | '-' | '0' | '.'))
+        && text.chars().any(|c| matches!(c, '+' | '-'))
+}
+
+/// CSD string and `max_power` for a coefficient given as CSD or as a decimal
+/// value, quantized to `frac_bits` fractional bits (an integer without them).
+#[cfg(feature = "multiplier")]
+fn parse_coefficient(text: &str, frac_bits: Option<usize>) -> Result<(String, usize), String> {
+    if is_csd(text) {
+        if frac_bits.is_some() {
+            return Err(format!(
+                "Error: --frac-bits only applies to decimal values, not to CSD string '{}'",
+                text
+            ));
+        }
+        let integer_digits = text.find('.').unwrap_or(text.len());
+        if integer_digits == 0 {
+            return Err(format!(
+                "Error: CSD string '{}' has no integer digits",
+                text
+            ));
+        }
+        return Ok((text.to_string(), integer_digits - 1));
+    }
+    let value: f64 = text
+        .parse()
+        .map_err(|e| format!("Error parsing value '{}': {}", text, e))?;
+    let q =
+        quantize_coefficient(value, frac_bits.unwrap_or(0)).map_err(|e| format!("Error: {}", e))?;
+    if frac_bits.is_none() && q.error != 0.0 {
+        return Err(format!(
+            "Error: {} is not an integer; pass --frac-bits F to quantize it",
+            text
+        ));
+    }
+    Ok((q.csd, q.max_power))
+}
+
+/// Return `verilog`, or write it to `out` and report where it went.
+#[cfg(feature = "multiplier")]
+fn write_output(verilog: String, out: Option<&str>) -> CliResult {
+    match out {
+        Some(path) => {
+            std::fs::write(path, &verilog).map_err(|e| format!("Error writing {}: {}", path, e))?;
+            Ok(format!("Wrote {}", path))
+        }
+        None => Ok(verilog.trim_end().to_string()),
+    }
+}
+
+/// Handle the verilog command
+#[cfg(feature = "multiplier")]
+pub fn handle_verilog(args: &[String]) -> CliResult {
+    let parsed = parse_generator_args("verilog", args)?;
+    let [coefficient] = parsed.positional[..] else {
+        return Err("Error: verilog requires one value or CSD string".to_string());
+    };
+    let (csd, max_power) = parse_coefficient(coefficient, parsed.frac_bits)?;
+    let mut naming = Naming::new();
+    if let Some(module) = parsed.module {
+        naming = naming.module_name(module);
+    }
+    let options = MultiplierOptions::new().naming(naming);
+    let verilog = generate_csd_multiplier_with(&csd, parsed.width, max_power, &options)
+        .map_err(|e| format!("Error: {}", e))?;
+    write_output(verilog, parsed.out)
+}
+
+/// Handle the filter command
+///
+/// Every non-empty line of the coefficient file that does not start with `#`
+/// holds a value or CSD string, optionally preceded by an output name
+/// (default `y0`, `y1`, ...).
+#[cfg(feature = "multiplier")]
+pub fn handle_filter(args: &[String]) -> CliResult {
+    let parsed = parse_generator_args("filter", args)?;
+    let [path] = parsed.positional[..] else {
+        return Err("Error: filter requires a coefficient file".to_string());
+    };
+    let text =
+        std::fs::read_to_string(path).map_err(|e| format!("Error reading {}: {}", path, e))?;
+    let mut coeffs = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (name, coefficient) = match fields[..] {
+            [coefficient] => (format!("y{}", coeffs.len()), coefficient),
+            [name, coefficient] => (name.to_string(), coefficient),
+            _ => {
+                return Err(format!(
+                    "Error: {} line {}: expected '[name] value'",
+                    path,
+                    number + 1
+                ))
+            }
+        };
+        let (csd, max_power) = parse_coefficient(coefficient, parsed.frac_bits)
+            .map_err(|e| format!("{} ({} line {})", e, path, number + 1))?;
+        coeffs.push(MultiplierSpec {
+            name,
+            csd,
+            input_width: parsed.width,
+            max_power,
+        });
+    }
+    let verilog = generate_csd_multipliers(&coeffs, parsed.module.unwrap_or("csd_filter"))
+        .map_err(|e| format!("Error: {}", e))?;
+    write_output(verilog, parsed.out)
+}
+
 /// Run the CLI application with the given arguments
 pub fn run_cli(args: &[String]) -> Result<String, String> {
     if args.len() < 2 {
-        let help = "Usage: csd-rs <command> [args]\n\nCommands:\n  to_csd <value> [places]    - Convert decimal to CSD\n  to_csdnnz <value> [nnz]    - Convert decimal to CSD with limited non-zeros\n  to_decimal <csd_string>    - Convert CSD to decimal\n\nExamples:\n  csd-rs to_csd 28.5 2\n  csd-rs to_csdnnz 28.5 4\n  csd-rs to_decimal '+00-00.+'";
+        let help = "Usage: csd-rs <command> [args]\n\nCommands:\n  to_csd <value> [places]    - Convert decimal to CSD\n  to_csdnnz <value> [nnz]    - Convert decimal to CSD with limited non-zeros\n  to_decimal <csd_string>    - Convert CSD to decimal\n  verilog <value|csd> --width N [--frac-bits F] [--module NAME] [--out FILE]\n                             - Generate a Verilog constant multiplier\n  filter <coeffs.txt> --width N [--frac-bits F] [--module NAME] [--out FILE]\n                             - Generate a multi-coefficient block, one '[name] value|csd' per line\n\nExamples:\n  csd-rs to_csd 28.5 2\n  csd-rs to_csdnnz 28.5 4\n  csd-rs to_decimal '+00-00.+'\n  csd-rs verilog 229 --width 8 --out times229.v\n  csd-rs filter taps.txt --width 12 --frac-bits 10";
         return Ok(help.to_string());
     }
 
@@ -61,16 +237,22 @@ pub fn run_cli(args: &[String]) -> Result<String, String> {
         "to_csd" => handle_to_csd(args),
         "to_csdnnz" => handle_to_csdnnz(args),
         "to_decimal" => handle_to_decimal(args),
+        #[cfg(feature = "multiplier")]
+        "verilog" => handle_verilog(args),
+        #[cfg(feature = "multiplier")]
+        "filter" => handle_filter(args),
         _ => Err(format!("Unknown command: {}", args[1])),
     }
 }
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
-
     match run_cli(&args) {
         Ok(output) => println!("{}", output),
-        Err(error) => eprintln!("{}", error),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
     }
 }
 
@@ -336,4 +518,129 @@ mod tests {
         let decimal: f64 = run_cli(&args2).unwrap().parse().unwrap();
         assert!((decimal - 42.5).abs() < 0.1);
     }
+
+    fn cli_args(args: &[&str]) -> Vec<String> {
+        std::iter::once("csd-rs")
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_handle_verilog_decimal_and_csd() {
+        let from_value = run_cli(&cli_args(&["verilog", "229", "--width", "8"])).unwrap();
+        let from_csd = run_cli(&cli_args(&["verilog", "+00-00+0+", "--width", "8"])).unwrap();
+        assert_eq!(from_value, from_csd);
+        assert!(from_value.contains("module csd_multiplier ("));
+        assert!(from_value.contains("input signed [7:0] x"));
+    }
+
+    #[test]
+    fn test_handle_verilog_module_and_frac_bits() {
+        let args = cli_args(&[
+            "verilog",
+            "-0.75",
+            "--width",
+            "8",
+            "--frac-bits",
+            "2",
+            "--module",
+            "gain",
+        ]);
+        let verilog = handle_verilog(&args).unwrap();
+        assert!(verilog.contains("module gain ("));
+        assert!(verilog.contains("2 fractional bits"));
+    }
+
+    #[test]
+    fn test_handle_verilog_errors() {
+        let err = handle_verilog(&cli_args(&["verilog", "28.5", "--width", "8"])).unwrap_err();
+        assert!(err.contains("not an integer"));
+        let err = handle_verilog(&cli_args(&["verilog", "229"])).unwrap_err();
+        assert!(err.contains("requires --width N"));
+        let err = handle_verilog(&cli_args(&["verilog", "--width", "8"])).unwrap_err();
+        assert!(err.contains("requires one value or CSD string"));
+        let err = handle_verilog(&cli_args(&["verilog", "+.0.+", "--width", "8"])).unwrap_err();
+        assert!(err.contains("Invalid character"), "{}", err);
+        assert!(handle_verilog(&cli_args(&["verilog", "0.+++", "--width", "8"])).is_ok());
+        let err = handle_verilog(&cli_args(&["verilog", "3", "--width", "x"])).unwrap_err();
+        assert!(err.contains("Error parsing --width"));
+        let args = cli_args(&["verilog", "+0-", "--width", "8", "--frac-bits", "2"]);
+        let err = handle_verilog(&args).unwrap_err();
+        assert!(
+            err.contains("--frac-bits only applies to decimal values"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_handle_verilog_csd_or_decimal() {
+        assert!(is_csd("+00-00+0+"));
+        assert!(is_csd("-"));
+        // Any sign digit makes it CSD: "+0" is 2 and "-0.0" is -1
+        assert!(is_csd("+0"));
+        assert!(is_csd("-0.0"));
+        assert!(!is_csd("0"));
+        assert!(!is_csd("-5"));
+        for (csd, value) in [("+000", "8"), ("+0", "2"), ("-00", "-4")] {
+            assert_eq!(
+                run_cli(&cli_args(&["verilog", csd, "--width", "8"])).unwrap(),
+                run_cli(&cli_args(&["verilog", value, "--width", "8"])).unwrap(),
+                "{}",
+                csd
+            );
+        }
+        let v = run_cli(&cli_args(&["verilog", "+0.0", "--width", "8"])).unwrap();
+        // 2.0 with one fractional bit: 4x in fixed point
+        assert!(v.contains("assign result = x_shift2;"), "{}", v);
+    }
+
+    #[test]
+    fn test_handle_verilog_wide_input() {
+        let verilog = run_cli(&cli_args(&["verilog", "229", "--width", "200"])).unwrap();
+        assert!(verilog.contains("input signed [199:0] x"));
+        let max = usize::MAX.to_string();
+        let err = run_cli(&cli_args(&["verilog", "5", "--width", &max])).unwrap_err();
+        assert!(err.contains("Product needs up to"), "{}", err);
+        let err = run_cli(&cli_args(&[
+            "verilog",
+            "0.3",
+            "--width",
+            "8",
+            "--frac-bits",
+            &max,
+        ]))
+        .unwrap_err();
+        assert!(err.contains("too large to quantize"), "{}", err);
+    }
+
+    #[test]
+    fn test_handle_filter_and_out() {
+        let dir = std::env::temp_dir();
+        let coeffs = dir.join(format!("csd_rs_taps_{}.txt", std::process::id()));
+        let out = dir.join(format!("csd_rs_taps_{}.v", std::process::id()));
+        std::fs::write(&coeffs, "# taps\n229\n\ny_neg -229\n+0-\n").unwrap();
+        let args = cli_args(&[
+            "filter",
+            coeffs.to_str().unwrap(),
+            "--width",
+            "8",
+            "--out",
+            out.to_str().unwrap(),
+        ]);
+        let message = handle_filter(&args).unwrap();
+        assert!(message.starts_with("Wrote "));
+        let verilog = std::fs::read_to_string(&out).unwrap();
+        assert!(verilog.contains("module csd_filter ("));
+        assert!(verilog.contains("output signed [15:0] y0,"));
+        assert!(verilog.contains("output signed [15:0] y_neg,"));
+        assert!(verilog.contains("output signed [9:0] y2"));
+
+        std::fs::write(&coeffs, "y0 3 extra\n").unwrap();
+        let args = cli_args(&["filter", coeffs.to_str().unwrap(), "--width", "8"]);
+        assert!(handle_filter(&args).unwrap_err().contains("line 1"));
+        std::fs::remove_file(&coeffs).unwrap();
+        std::fs::remove_file(&out).unwrap();
+    }
 }
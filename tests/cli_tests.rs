@@ -237,3 +237,20 @@ fn test_cli_to_csd_small_fraction() {
     assert!(!stdout.trim().is_empty());
     assert_eq!(stderr.trim(), "");
 }
+
+#[test]
+#[serial]
+fn test_cli_verilog() {
+    let (stdout, _stderr, _) =
+        run_csd_rs(&["verilog", "229", "--width", "8", "--module", "times229"]);
+    assert!(stdout.contains("module times229 ("));
+    assert!(stdout.contains("endmodule"));
+}
+
+#[test]
+#[serial]
+fn test_cli_verilog_missing_width() {
+    let (_stdout, stderr, exit_code) = run_csd_rs(&["verilog", "229"]);
+    assert!(stderr.contains("requires --width N"));
+    assert_ne!(exit_code, 0);
+}
@@ -0,0 +1,15 @@
+// CSD Multiplier for pattern: +00-00+0+ (value: 229)
+module csd_multiplier (
+    input signed [7:0] x,      // Input value
+    output signed [15:0] result // Result of multiplication
+);
+
+    // Create shifted versions of input
+    wire signed [15:0] x_shift8 = $signed({ {8{x[7]}}, x}) << 8;
+    wire signed [12:0] x_shift5 = $signed({ {5{x[7]}}, x}) << 5;
+    wire signed [9:0] x_shift2 = $signed({ {2{x[7]}}, x}) << 2;
+    wire signed [7:0] x_shift0 = x << 0;
+
+    // CSD implementation
+    assign result = x_shift8 - x_shift5 + x_shift2 + x_shift0;
+endmodule
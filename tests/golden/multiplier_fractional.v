@@ -0,0 +1,14 @@
+
+module csd_multiplier (
+    input signed [5:0] x,      // Input value
+    output signed [10:0] result // Result of multiplication (2 fractional bits)
+);
+
+    // Create shifted versions of input
+    wire signed [10:0] x_shift5 = x <<< 5;
+    wire signed [7:0] x_shift2 = x <<< 2;
+    wire signed [5:0] x_shift0 = x <<< 0;
+
+    // CSD implementation
+    assign result = x_shift5 - x_shift2 + x_shift0;
+endmodule
@@ -0,0 +1,18 @@
+
+module csd_multiplier (
+    input signed [7:0] x,      // Input value
+    output signed [14:0] result // Result of multiplication
+);
+
+    // Create shifted versions of input
+    wire signed [14:0] x_shift7 = x <<< 7;
+    wire signed [12:0] x_shift5 = x <<< 5;
+    wire signed [10:0] x_shift3 = x <<< 3;
+    wire signed [8:0] x_shift1 = x <<< 1;
+
+    // LCSRe: repeated pattern "+0-0"
+    wire signed [14:0] _pat = x_shift7 - x_shift5;
+
+    // CSD implementation (LCSRe optimized)
+    assign result = _pat + (_pat >>> 4);
+endmodule
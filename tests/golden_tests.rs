@@ -0,0 +1,63 @@
+//! Golden-file tests: `CsdMultiplier::generate_verilog` and the free
+//! `generate_csd_multiplier` functions must produce byte-identical Verilog.
+
+use csd::csd_multiplier::{
+    generate_csd_multiplier, generate_csd_multiplier_with, CommentStyle, CsdMultiplier,
+    MultiplierOptions, SignExtension, VerilogStyle,
+};
+
+/// (golden file contents, csd, input width, max_power)
+const DEFAULT_STYLE: [(&str, &str, usize, usize); 3] = [
+    (include_str!("golden/multiplier_229.v"), "+00-00+0+", 8, 8),
+    (include_str!("golden/multiplier_lcsre.v"), "+0-0+0-0", 8, 7),
+    (
+        include_str!("golden/multiplier_fractional.v"),
+        "+00-.0+",
+        6,
+        3,
+    ),
+];
+
+#[test]
+fn default_style_matches_golden_files() {
+    for (golden, csd, n, m) in DEFAULT_STYLE {
+        let multiplier = CsdMultiplier::new(csd, n, m).unwrap();
+        assert_eq!(multiplier.generate_verilog(), golden, "struct, {csd}");
+        assert_eq!(
+            generate_csd_multiplier(csd, n, m).unwrap(),
+            golden,
+            "free function, {csd}"
+        );
+    }
+}
+
+#[test]
+fn classic_style_matches_golden_file() {
+    let golden = include_str!("golden/multiplier_classic.v");
+    let style = VerilogStyle::new()
+        .sign_extension(SignExtension::Concatenation)
+        .comments(CommentStyle::Verbose);
+    let options = MultiplierOptions::new().verilog_style(style);
+    let multiplier = CsdMultiplier::new("+00-00+0+", 8, 8).unwrap();
+    assert_eq!(multiplier.generate_verilog_with(&options).unwrap(), golden);
+    assert_eq!(
+        generate_csd_multiplier_with("+00-00+0+", 8, 8, &options).unwrap(),
+        golden
+    );
+}
+
+#[test]
+fn struct_and_free_function_agree_on_every_small_coefficient() {
+    for c in (-200..=200).filter(|&c: &i32| c != 0) {
+        let csd = csd::to_csd_i(c);
+        let m = csd.len() - 1;
+        for n in [4, 8] {
+            let multiplier = CsdMultiplier::new(&csd, n, m).unwrap();
+            assert_eq!(
+                multiplier.generate_verilog(),
+                generate_csd_multiplier(&csd, n, m).unwrap(),
+                "{csd} at width {n}"
+            );
+        }
+    }
+}
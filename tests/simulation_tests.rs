@@ -0,0 +1,150 @@
+//! Exhaustive simulation of generated multipliers against `x * to_decimal_i(csd)`
+
+use csd::csd_multiplier::fir::{fir_filter, FirForm, FirOptions};
+use csd::csd_multiplier::simulator::{verify_csd_multiplier, verify_csd_multipliers};
+use csd::csd_multiplier::{AdderStructure, MultiplierOptions, MultiplierSpec, OutputRounding};
+use csd::{to_csd, to_csd_i, to_decimal_i};
+
+/// Every non-zero coefficient in -300..=300 as (csd, max_power).
+fn coefficients() -> Vec<(String, usize)> {
+    (-300..=300)
+        .filter(|&c: &i32| c != 0)
+        .map(|c| {
+            let csd = to_csd_i(c);
+            let max_power = csd.len() - 1;
+            (csd, max_power)
+        })
+        .collect()
+}
+
+#[test]
+fn single_coefficients_match_exact_product() {
+    let options = [
+        MultiplierOptions::new(),
+        MultiplierOptions::new().adder_structure(AdderStructure::Tree),
+        MultiplierOptions::new().share_patterns(false),
+    ];
+    for (csd, max_power) in coefficients() {
+        for opts in &options {
+            let report = verify_csd_multiplier(&csd, 6, max_power, opts).unwrap();
+            assert!(
+                report.passed(),
+                "{} ({}): {:?}",
+                csd,
+                to_decimal_i(&csd),
+                report.first_mismatch
+            );
+        }
+    }
+}
+
+#[test]
+fn filter_bank_matches_exact_products() {
+    let coeffs: Vec<MultiplierSpec> = [75, -45, 101, 83, -117, 91]
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let csd = to_csd_i(c);
+            MultiplierSpec {
+                name: format!("y{}", i),
+                csd: format!("{:0>8}", csd),
+                input_width: 8,
+                max_power: 7,
+            }
+        })
+        .collect();
+    for structure in [AdderStructure::Chain, AdderStructure::Tree] {
+        let options = MultiplierOptions::new().adder_structure(structure);
+        let report = verify_csd_multipliers(&coeffs, "bank", &options).unwrap();
+        assert!(report.passed(), "{:?}", report.first_mismatch);
+        assert_eq!(report.vectors, 256);
+    }
+}
+
+#[test]
+fn fractional_coefficients_match_scaled_product() {
+    let truncate = MultiplierOptions::new().output_frac_bits(1);
+    let round = MultiplierOptions::new()
+        .output_frac_bits(1)
+        .rounding(OutputRounding::Nearest);
+    for k in (-60..=60i32).filter(|k| k % 8 != 0) {
+        let csd = to_csd(f64::from(k) / 8.0, 3);
+        let max_power = csd.find('.').unwrap() - 1;
+        for opts in [&MultiplierOptions::new(), &truncate, &round] {
+            let report = verify_csd_multiplier(&csd, 6, max_power, opts).unwrap();
+            assert!(report.passed(), "{}: {:?}", csd, report.first_mismatch);
+        }
+    }
+}
+
+#[test]
+fn quantized_coefficients_match_scaled_product() {
+    for value in [
+        0.3,
+        -0.4,
+        std::f64::consts::FRAC_1_SQRT_2,
+        -0.125,
+        0.9,
+        -0.99,
+    ] {
+        let spec = MultiplierSpec::from_f64("y", value, 6, 8).unwrap();
+        let report =
+            verify_csd_multiplier(&spec.csd, 8, spec.max_power, &MultiplierOptions::new()).unwrap();
+        assert!(report.passed(), "{}: {:?}", spec.csd, report.first_mismatch);
+    }
+}
+
+#[test]
+fn heterogeneous_filter_bank_matches_exact_products() {
+    let mut coeffs: Vec<MultiplierSpec> = [75, -45, 3, 117, -7, 1]
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| MultiplierSpec::from_integer(&format!("y{}", i), c, 8))
+        .collect();
+    coeffs.push(MultiplierSpec::from_f64("y6", -0.4, 6, 8).unwrap());
+    for structure in [AdderStructure::Chain, AdderStructure::Tree] {
+        let options = MultiplierOptions::new().adder_structure(structure);
+        let report = verify_csd_multipliers(&coeffs, "bank", &options).unwrap();
+        assert!(report.passed(), "{:?}", report.first_mismatch);
+    }
+}
+
+#[test]
+fn fir_filters_match_exact_convolution() {
+    // Linear-phase lowpass, an antisymmetric differentiator and an asymmetric filter
+    let filters: [&[f64]; 3] = [
+        &[0.011, -0.042, 0.0, 0.29, 0.5, 0.29, 0.0, -0.042, 0.011],
+        &[-0.125, 0.4, 0.0, -0.4, 0.125],
+        &[0.9, -0.35, 0.2, 0.07],
+    ];
+    let width = 9;
+    let x: Vec<i128> = (0..400).map(|i| (i * 7919 % 512) - 256).collect();
+    for taps in filters {
+        for form in [FirForm::Transposed, FirForm::Direct] {
+            let options = FirOptions::new().form(form).coefficient_frac_bits(10);
+            let filter = fir_filter(taps, width, &options).unwrap();
+            let c = filter.coefficients();
+            let expected: Vec<i128> = (0..x.len())
+                .map(|n| (0..c.len().min(n + 1)).map(|k| c[k] * x[n - k]).sum())
+                .collect();
+            assert_eq!(filter.simulate(&x), expected, "{:?} {:?}", taps, form);
+        }
+    }
+}
+
+#[test]
+fn folded_coefficients_match_exact_products() {
+    // Duplicates, negations and power-of-two multiples of a few base values
+    let mut coeffs = Vec::new();
+    for (base, i) in [3i64, 45, -77, 117].iter().zip(0..) {
+        for (j, scale) in [1i64, -1, 2, -4, 8].iter().enumerate() {
+            let name = format!("y{}_{}", i, j);
+            coeffs.push(MultiplierSpec::from_integer(&name, base * scale, 10));
+        }
+    }
+    for structure in [AdderStructure::Chain, AdderStructure::Tree] {
+        let options = MultiplierOptions::new().adder_structure(structure);
+        let report = verify_csd_multipliers(&coeffs, "bank", &options).unwrap();
+        assert!(report.passed(), "{:?}", report.first_mismatch);
+    }
+}